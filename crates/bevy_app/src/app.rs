@@ -9,8 +9,9 @@ use alloc::{
 };
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
-    component::RequiredComponentsError,
+    component::{ComponentMutability, RequiredComponentsError},
     error::{ErrorHandler, FallbackErrorHandler},
+    index::{IndexableComponent, ValueIndex},
     intern::Interned,
     message::{message_update_system, MessageCursor},
     observer::IntoObserver,
@@ -1181,6 +1182,27 @@ impl App {
         self.world_mut().register_disabling_component::<C>();
     }
 
+    /// Starts maintaining a [`ValueIndex`](bevy_ecs::index::ValueIndex) of the component `C`,
+    /// used by the [`QueryByIndex`](bevy_ecs::index::QueryByIndex) system parameter.
+    ///
+    /// If `C` is mutable, [`ValueIndex::update`] is run in [`First`] to pick up the changes made
+    /// to `C` in place. See [`World::register_index`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `C` already exists in an archetype, or already has an `on_insert` or
+    /// `on_discard` hook.
+    pub fn register_index<C: IndexableComponent>(&mut self) -> &mut Self {
+        if self.world().contains_resource::<ValueIndex<C>>() {
+            return self;
+        }
+        self.world_mut().register_index::<C>();
+        if C::Mutability::MUTABLE {
+            self.add_systems(First, ValueIndex::<C>::update);
+        }
+        self
+    }
+
     /// Returns a reference to the main [`SubApp`]'s [`World`]. This is the same as calling
     /// [`app.main().world()`].
     ///
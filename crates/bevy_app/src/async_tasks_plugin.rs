@@ -0,0 +1,38 @@
+use crate::{App, Plugin, Update};
+use bevy_ecs::{
+    async_world::{run_async_tasks, AsyncTasks},
+    schedule::{common_conditions::resource_exists, IntoScheduleConfigs},
+};
+
+/// Runs the [async tasks](bevy_ecs::async_world) of the app once per frame, in [`Update`].
+///
+/// This is not part of `DefaultPlugins`: [`run_async_tasks`] is an exclusive system, so it is a
+/// sync point in the schedule. It only runs once a task has been spawned.
+///
+/// ```
+/// # use bevy_app::{App, AsyncTasksPlugin};
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Resource, Default)]
+/// struct Loaded(bool);
+///
+/// let mut app = App::new();
+/// app.add_plugins(AsyncTasksPlugin).init_resource::<Loaded>();
+/// app.world_mut().spawn_async(|world| async move {
+///     world.next_frame().await;
+///     world.with(|world| world.resource_mut::<Loaded>().0 = true).await;
+/// });
+/// app.update();
+/// app.update();
+/// assert!(app.world().resource::<Loaded>().0);
+/// ```
+#[derive(Default)]
+pub struct AsyncTasksPlugin;
+
+impl Plugin for AsyncTasksPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            run_async_tasks.run_if(resource_exists::<AsyncTasks>),
+        );
+    }
+}
@@ -0,0 +1,370 @@
+//! Per-asset-type memory budgets, with least-recently-used eviction of assets that can be
+//! transparently reloaded.
+//!
+//! An [`AssetBudget`] is registered for an asset type using [`AssetApp::init_asset_budget`]. It
+//! tracks the reported size of every asset of that type. Assets accessed through
+//! [`ReloadableHandle`]s (using the [`ReloadableAssets`] system param) are kept alive by the budget
+//! itself. When the total size of the assets exceeds the budget, the least recently used of those
+//! assets that aren't also held by a regular [`Handle`] are unloaded. The next access through a
+//! [`ReloadableHandle`] reloads the asset from its [`AssetPath`].
+//!
+//! [`AssetApp::init_asset_budget`]: crate::AssetApp::init_asset_budget
+
+use crate::{Asset, AssetEvent, AssetId, AssetPath, AssetServer, Assets, Handle};
+use alloc::{sync::Arc, vec::Vec};
+use bevy_ecs::{
+    message::MessageReader,
+    resource::Resource,
+    system::{Res, ResMut, SystemParam},
+};
+use bevy_platform::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+};
+use core::{
+    fmt::{Debug, Formatter},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+/// A memory budget for assets of type `A`.
+///
+/// See the [module docs](crate::budget) for how assets are evicted.
+#[derive(Resource)]
+pub struct AssetBudget<A: Asset> {
+    max_bytes: usize,
+    size_of: fn(&A) -> usize,
+    sizes: HashMap<AssetId<A>, usize>,
+    total_bytes: usize,
+    retained: Mutex<RetainedAssets<A>>,
+}
+
+/// The assets kept alive by an [`AssetBudget`] on behalf of [`ReloadableHandle`]s.
+struct RetainedAssets<A: Asset> {
+    /// Incremented on every access, to order the assets by recency.
+    clock: u64,
+    assets: HashMap<AssetPath<'static>, RetainedAsset<A>>,
+}
+
+struct RetainedAsset<A: Asset> {
+    handle: Handle<A>,
+    last_used: u64,
+}
+
+impl<A: Asset> AssetBudget<A> {
+    /// Creates a budget of `max_bytes` for assets of type `A`.
+    ///
+    /// By default, the size of an asset is `size_of::<A>()`, which doesn't include any heap
+    /// allocations it owns. Use [`AssetBudget::with_size`] to report a more accurate size.
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            size_of: |_| size_of::<A>(),
+            sizes: HashMap::default(),
+            total_bytes: 0,
+            retained: Mutex::new(RetainedAssets {
+                clock: 0,
+                assets: HashMap::default(),
+            }),
+        }
+    }
+
+    /// Sets the function used to compute the size of an asset, in bytes.
+    pub fn with_size(mut self, size_of: fn(&A) -> usize) -> Self {
+        self.size_of = size_of;
+        self
+    }
+
+    /// Returns the maximum total size of the assets of type `A`, in bytes.
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Sets the maximum total size of the assets of type `A`, in bytes. Assets will be evicted
+    /// the next time [`evict_assets_over_budget`] runs if this is exceeded.
+    pub fn set_max_bytes(&mut self, max_bytes: usize) {
+        self.max_bytes = max_bytes;
+    }
+
+    /// Returns the size of the asset with the given `id`, as last reported when it was added or
+    /// modified.
+    pub fn size(&self, id: impl Into<AssetId<A>>) -> Option<usize> {
+        self.sizes.get(&id.into()).copied()
+    }
+
+    /// Returns the total size of all the assets of type `A`, in bytes.
+    pub fn total_bytes(&self) -> usize {
+        self.total_bytes
+    }
+
+    /// Returns `true` if the asset loaded from `path` is currently kept alive by this budget.
+    pub fn is_retained<'a>(&self, path: impl Into<AssetPath<'a>>) -> bool {
+        self.retained
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .assets
+            .contains_key(&path.into())
+    }
+
+    /// Marks the asset at `path` as used, loading it if it isn't retained, and returns its id.
+    fn retain(&self, path: &AssetPath<'static>, asset_server: &AssetServer) -> AssetId<A> {
+        let mut retained = self.retained.lock().unwrap_or_else(PoisonError::into_inner);
+        retained.clock += 1;
+        let clock = retained.clock;
+        let asset = retained
+            .assets
+            .entry(path.clone())
+            .or_insert_with(|| RetainedAsset {
+                handle: asset_server.load(path.clone()),
+                last_used: clock,
+            });
+        asset.last_used = clock;
+        asset.handle.id()
+    }
+
+    fn set_size(&mut self, id: AssetId<A>, size: Option<usize>) {
+        let old = match size {
+            Some(size) => self.sizes.insert(id, size),
+            None => self.sizes.remove(&id),
+        };
+        self.total_bytes = self.total_bytes - old.unwrap_or(0) + size.unwrap_or(0);
+    }
+}
+
+/// A reference to an asset that doesn't keep it alive, but reloads it from its [`AssetPath`] when
+/// it is accessed through [`ReloadableAssets`] after being evicted by its [`AssetBudget`].
+pub struct ReloadableHandle<A: Asset> {
+    path: AssetPath<'static>,
+    marker: PhantomData<fn() -> A>,
+}
+
+impl<A: Asset> ReloadableHandle<A> {
+    /// Creates a handle to the asset at `path`. This doesn't start loading the asset.
+    pub fn new(path: impl Into<AssetPath<'static>>) -> Self {
+        Self {
+            path: path.into(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the path of the asset.
+    pub fn path(&self) -> &AssetPath<'static> {
+        &self.path
+    }
+}
+
+impl<A: Asset> Clone for ReloadableHandle<A> {
+    fn clone(&self) -> Self {
+        Self::new(self.path.clone())
+    }
+}
+
+impl<A: Asset> Debug for ReloadableHandle<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ReloadableHandle")
+            .field("type", &A::short_type_path())
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl<A: Asset> PartialEq for ReloadableHandle<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+impl<A: Asset> Eq for ReloadableHandle<A> {}
+
+impl<A: Asset> Hash for ReloadableHandle<A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.path.hash(state);
+    }
+}
+
+/// A [`SystemParam`] for accessing assets through [`ReloadableHandle`]s.
+///
+/// Requires an [`AssetBudget`] for `A`, see [`AssetApp::init_asset_budget`](crate::AssetApp::init_asset_budget).
+#[derive(SystemParam)]
+pub struct ReloadableAssets<'w, A: Asset> {
+    assets: Res<'w, Assets<A>>,
+    budget: Res<'w, AssetBudget<A>>,
+    asset_server: Res<'w, AssetServer>,
+}
+
+impl<'w, A: Asset> ReloadableAssets<'w, A> {
+    /// Starts loading the asset at `path` and returns a [`ReloadableHandle`] to it.
+    pub fn load(&self, path: impl Into<AssetPath<'static>>) -> ReloadableHandle<A> {
+        let handle = ReloadableHandle::new(path);
+        self.id(&handle);
+        handle
+    }
+
+    /// Returns the current id of the asset referenced by `handle`, marking it as used. If the
+    /// asset was evicted (or never loaded), it starts loading again, with a new id.
+    pub fn id(&self, handle: &ReloadableHandle<A>) -> AssetId<A> {
+        self.budget.retain(&handle.path, &self.asset_server)
+    }
+
+    /// Returns the asset referenced by `handle`, marking it as used. Returns `None` if the asset
+    /// is still loading, in which case it will be available in a later frame.
+    pub fn get(&self, handle: &ReloadableHandle<A>) -> Option<&A> {
+        self.assets.get(self.id(handle))
+    }
+
+    /// Returns the [`AssetBudget`] for `A`.
+    pub fn budget(&self) -> &AssetBudget<A> {
+        &self.budget
+    }
+}
+
+/// Updates the sizes tracked by the [`AssetBudget`] for `A`, then evicts the least recently used
+/// assets that are only held by the budget until the total size is within the budget.
+pub fn evict_assets_over_budget<A: Asset>(
+    mut budget: ResMut<AssetBudget<A>>,
+    assets: Res<Assets<A>>,
+    mut events: MessageReader<AssetEvent<A>>,
+) {
+    for event in events.read() {
+        match *event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                let size = assets.get(id).map(budget.size_of);
+                budget.set_size(id, size);
+            }
+            AssetEvent::Removed { id } => budget.set_size(id, None),
+            AssetEvent::Unused { .. } | AssetEvent::LoadedWithDependencies { .. } => {}
+        }
+    }
+
+    if budget.total_bytes <= budget.max_bytes {
+        return;
+    }
+
+    let budget = &mut *budget;
+    let retained = budget
+        .retained
+        .get_mut()
+        .unwrap_or_else(PoisonError::into_inner);
+    let mut candidates = retained
+        .assets
+        .iter()
+        .filter(|(_, asset)| match &asset.handle {
+            // Only the budget holds this handle, so dropping it unloads the asset.
+            Handle::Strong(handle) => Arc::strong_count(handle) == 1,
+            Handle::Uuid(..) => false,
+        })
+        .filter_map(|(path, asset)| {
+            // Evicting an asset that is still loading doesn't free anything.
+            let size = *budget.sizes.get(&asset.handle.id())?;
+            Some((asset.last_used, path.clone(), asset.handle.id(), size))
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_unstable_by_key(|(last_used, ..)| *last_used);
+
+    for (_, path, id, size) in candidates {
+        if budget.total_bytes <= budget.max_bytes {
+            break;
+        }
+        retained.assets.remove(&path);
+        // The asset is removed once the handle drop is processed. Stop counting it now so that we
+        // don't evict more than necessary in the meantime.
+        budget.sizes.remove(&id);
+        budget.total_bytes -= size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        tests::{create_app, run_app_until, serialize_as_cool_text, CoolText, CoolTextLoader},
+        AssetApp,
+    };
+    use alloc::string::String;
+    use bevy_ecs::{system::SystemState, world::World};
+    use std::path::Path;
+
+    fn get_text(world: &mut World, handle: &ReloadableHandle<CoolText>) -> Option<String> {
+        let mut state = SystemState::<ReloadableAssets<CoolText>>::new(world);
+        let assets = state.get(world).unwrap();
+        assets.get(handle).map(|text| text.text.clone())
+    }
+
+    #[test]
+    fn evicts_least_recently_used_and_reloads() {
+        let (mut app, dir) = create_app();
+        dir.insert_asset_text(Path::new("a.cool.ron"), &serialize_as_cool_text("aaaaaa"));
+        dir.insert_asset_text(Path::new("b.cool.ron"), &serialize_as_cool_text("bbbbbb"));
+        app.init_asset::<CoolText>()
+            .register_asset_loader(CoolTextLoader)
+            .init_asset_budget(AssetBudget::<CoolText>::new(10).with_size(|t| t.text.len()));
+
+        let a = ReloadableHandle::<CoolText>::new("a.cool.ron");
+        let b = ReloadableHandle::<CoolText>::new("b.cool.ron");
+        let budget = |world: &World| world.resource::<AssetBudget<CoolText>>().total_bytes();
+
+        run_app_until(&mut app, |world| {
+            get_text(world, &a)?;
+            (budget(world) == 6).then_some(())
+        });
+        let a_id = app
+            .world()
+            .resource::<AssetServer>()
+            .get_path_id("a.cool.ron")
+            .unwrap()
+            .typed::<CoolText>();
+
+        // Loading b exceeds the budget, so a (the least recently used) is evicted.
+        run_app_until(&mut app, |world| {
+            get_text(world, &b)?;
+            let budget = world.resource::<AssetBudget<CoolText>>();
+            (!budget.is_retained("a.cool.ron")).then_some(())
+        });
+        assert_eq!(budget(app.world()), 6);
+        run_app_until(&mut app, |world| {
+            let loaded = world.resource::<Assets<CoolText>>().get(a_id).is_some();
+            (!loaded).then_some(())
+        });
+
+        // Accessing a again reloads it, evicting b instead.
+        let text = run_until_some(&mut app, |world| get_text(world, &a));
+        assert_eq!(text, "aaaaaa");
+        run_app_until(&mut app, |world| {
+            let budget = world.resource::<AssetBudget<CoolText>>();
+            (!budget.is_retained("b.cool.ron")).then_some(())
+        });
+        assert!(app
+            .world()
+            .resource::<AssetBudget<CoolText>>()
+            .is_retained("a.cool.ron"));
+    }
+
+    #[test]
+    fn does_not_evict_assets_with_strong_handles() {
+        let (mut app, dir) = create_app();
+        dir.insert_asset_text(Path::new("a.cool.ron"), &serialize_as_cool_text("aaaaaa"));
+        app.init_asset::<CoolText>()
+            .register_asset_loader(CoolTextLoader)
+            .init_asset_budget(AssetBudget::<CoolText>::new(1).with_size(|t| t.text.len()));
+
+        let _strong: Handle<CoolText> = app.world().resource::<AssetServer>().load("a.cool.ron");
+        let a = ReloadableHandle::<CoolText>::new("a.cool.ron");
+        run_until_some(&mut app, |world| get_text(world, &a));
+        for _ in 0..3 {
+            app.update();
+        }
+
+        let budget = app.world().resource::<AssetBudget<CoolText>>();
+        assert!(budget.is_retained("a.cool.ron"));
+        assert_eq!(budget.total_bytes(), 6);
+    }
+
+    fn run_until_some<T>(app: &mut bevy_app::App, mut f: impl FnMut(&mut World) -> Option<T>) -> T {
+        let mut result = None;
+        run_app_until(app, |world| {
+            result = f(world);
+            result.as_ref().map(|_| ())
+        });
+        result.unwrap()
+    }
+}
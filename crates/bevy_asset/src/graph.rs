@@ -0,0 +1,186 @@
+use crate::{AssetPath, LoadState, UntypedAssetId};
+use alloc::{
+    collections::VecDeque,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use bevy_platform::collections::HashMap;
+use core::fmt::Write;
+use serde::{Serialize, Serializer};
+
+/// A snapshot of the assets tracked by the [`AssetServer`](crate::AssetServer) and the dependencies
+/// between them, as returned by [`AssetServer::dependency_graph`](crate::AssetServer::dependency_graph).
+/// Its nodes are sorted by asset path, then by id.
+///
+/// This can be exported using [`AssetDependencyGraph::to_dot`] to be rendered with Graphviz, or
+/// serialized (for example to JSON) using `serde`.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct AssetDependencyGraph {
+    /// The assets in the graph.
+    pub nodes: Vec<AssetGraphNode>,
+    /// The dependencies between the assets in [`AssetDependencyGraph::nodes`].
+    pub edges: Vec<AssetGraphEdge>,
+}
+
+/// An asset in an [`AssetDependencyGraph`].
+#[derive(Clone, Debug, Serialize)]
+pub struct AssetGraphNode {
+    /// The id of the asset. This is serialized using its [`Display`](core::fmt::Display)
+    /// implementation, and is only meaningful while the asset is alive.
+    #[serde(serialize_with = "serialize_id")]
+    pub id: UntypedAssetId,
+    /// The [`TypePath`](bevy_reflect::TypePath) of the asset type, if the type was registered
+    /// with the [`AssetServer`](crate::AssetServer).
+    pub type_path: Option<&'static str>,
+    /// The path the asset was loaded from, if any.
+    pub path: Option<AssetPath<'static>>,
+    /// The [`LoadState`] of the asset.
+    #[serde(serialize_with = "serialize_load_state")]
+    pub load_state: LoadState,
+    /// The number of strong [`Handle`](crate::Handle)s to the asset that are currently alive.
+    /// These can be held by users, or by the assets that depend on it.
+    pub strong_handles: usize,
+    /// The paths this asset's loader read while loading it. These are only tracked when watching
+    /// for changes.
+    pub loader_dependencies: Vec<AssetPath<'static>>,
+}
+
+/// A dependency between two nodes of an [`AssetDependencyGraph`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct AssetGraphEdge {
+    /// The index of the dependent node in [`AssetDependencyGraph::nodes`].
+    pub dependent: usize,
+    /// The index of the dependency node in [`AssetDependencyGraph::nodes`].
+    pub dependency: usize,
+}
+
+fn serialize_id<S: Serializer>(id: &UntypedAssetId, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(id)
+}
+
+fn serialize_load_state<S: Serializer>(
+    state: &LoadState,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(match state {
+        LoadState::NotLoaded => "NotLoaded",
+        LoadState::Loading => "Loading",
+        LoadState::Loaded => "Loaded",
+        LoadState::Failed(_) => "Failed",
+    })
+}
+
+impl AssetDependencyGraph {
+    /// Returns the index of the node for the asset with the given `id`, if it is in the graph.
+    pub fn node_index(&self, id: impl Into<UntypedAssetId>) -> Option<usize> {
+        let id = id.into();
+        self.nodes.iter().position(|node| node.id == id)
+    }
+
+    /// Returns the node for the asset with the given `id`, if it is in the graph.
+    pub fn node(&self, id: impl Into<UntypedAssetId>) -> Option<&AssetGraphNode> {
+        self.node_index(id).map(|index| &self.nodes[index])
+    }
+
+    /// Returns the direct dependencies of the asset with the given `id`.
+    pub fn dependencies(
+        &self,
+        id: impl Into<UntypedAssetId>,
+    ) -> impl Iterator<Item = &AssetGraphNode> {
+        let index = self.node_index(id);
+        self.edges
+            .iter()
+            .filter(move |edge| Some(edge.dependent) == index)
+            .map(|edge| &self.nodes[edge.dependency])
+    }
+
+    /// Returns the assets that directly depend on the asset with the given `id`.
+    pub fn dependents(
+        &self,
+        id: impl Into<UntypedAssetId>,
+    ) -> impl Iterator<Item = &AssetGraphNode> {
+        let index = self.node_index(id);
+        self.edges
+            .iter()
+            .filter(move |edge| Some(edge.dependency) == index)
+            .map(|edge| &self.nodes[edge.dependent])
+    }
+
+    /// Returns the subgraph made up of the asset with the given `id` and all of its recursive
+    /// dependencies. This is everything that loading the asset pulls in.
+    ///
+    /// Returns an empty graph if the asset is not in this graph.
+    pub fn recursive_dependencies(&self, id: impl Into<UntypedAssetId>) -> AssetDependencyGraph {
+        self.reachable(id.into(), |edge| (edge.dependent, edge.dependency))
+    }
+
+    /// Returns the subgraph made up of the asset with the given `id` and all of the assets that
+    /// (recursively) depend on it. This is everything that is keeping the asset alive through
+    /// dependencies.
+    ///
+    /// Returns an empty graph if the asset is not in this graph.
+    pub fn recursive_dependents(&self, id: impl Into<UntypedAssetId>) -> AssetDependencyGraph {
+        self.reachable(id.into(), |edge| (edge.dependency, edge.dependent))
+    }
+
+    /// Returns the subgraph of nodes reachable from `id`, following edges from the first to the
+    /// second index returned by `direction`.
+    fn reachable(
+        &self,
+        id: UntypedAssetId,
+        direction: impl Fn(&AssetGraphEdge) -> (usize, usize),
+    ) -> AssetDependencyGraph {
+        let Some(root) = self.node_index(id) else {
+            return AssetDependencyGraph::default();
+        };
+        // Maps indices in `self` to indices in the subgraph.
+        let mut remap = HashMap::<usize, usize>::default();
+        remap.insert(root, 0);
+        let mut nodes = vec![self.nodes[root].clone()];
+        let mut queue = VecDeque::from([root]);
+        while let Some(current) = queue.pop_front() {
+            for edge in &self.edges {
+                let (from, to) = direction(edge);
+                if from != current || remap.contains_key(&to) {
+                    continue;
+                }
+                remap.insert(to, nodes.len());
+                nodes.push(self.nodes[to].clone());
+                queue.push_back(to);
+            }
+        }
+        let edges = self
+            .edges
+            .iter()
+            .filter_map(|edge| {
+                Some(AssetGraphEdge {
+                    dependent: *remap.get(&edge.dependent)?,
+                    dependency: *remap.get(&edge.dependency)?,
+                })
+            })
+            .collect();
+        AssetDependencyGraph { nodes, edges }
+    }
+
+    /// Renders the graph in the [DOT](https://graphviz.org/doc/info/lang.html) language, with
+    /// edges pointing from each asset to its dependencies.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph assets {\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let name = match &node.path {
+                Some(path) => path.to_string(),
+                None => format!("{}", node.id),
+            };
+            let type_path = node.type_path.unwrap_or("<unknown>");
+            let label = format!("{name}\n{type_path}");
+            let _ = writeln!(dot, "    {index} [label={label:?}];");
+        }
+        for edge in &self.edges {
+            let _ = writeln!(dot, "    {} -> {};", edge.dependent, edge.dependency);
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
@@ -16,6 +16,7 @@ pub mod embedded;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod file;
 pub mod memory;
+pub mod pak;
 pub mod processor_gated;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
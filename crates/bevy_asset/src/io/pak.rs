@@ -0,0 +1,608 @@
+//! A backend reading [`Asset`]s from a single pack file.
+//!
+//! Shipping thousands of small asset files is slow to install and to open. A pack file bundles
+//! them, along with their `.meta` files, into one file: build it with a [`PakWriter`], then read
+//! from it with a [`PakAssetReader`], usually registered as an asset source:
+//!
+//! ```no_run
+//! # use bevy_app::App;
+//! # use bevy_asset::{AssetApp, io::{AssetSourceBuilder, AssetSourceId, pak::PakAssetReader}};
+//! let reader = PakAssetReader::open("assets.pak").expect("assets.pak should be a pack file");
+//! App::new().register_asset_source(
+//!     AssetSourceId::Default,
+//!     AssetSourceBuilder::platform_default("assets", None)
+//!         .with_reader(move || Box::new(reader.clone())),
+//! );
+//! ```
+//!
+//! # Format
+//!
+//! A pack file starts with an index of its entries, followed by their data. All integers are
+//! little-endian.
+//!
+//! - The magic bytes `BPAK`, then the format version as a `u32` and the number of entries as a
+//!   `u32`.
+//! - For each entry: a `u8` that is `0` for an asset and `1` for asset metadata, the length of its
+//!   path as a `u32` and the path itself, in UTF-8 with `/` separators, then the offset of its data
+//!   from the end of the index and the length of its data, both as `u64`s.
+//! - The data of the entries.
+//!
+//! [`Asset`]: crate::Asset
+
+use crate::io::{
+    AssetReader, AssetReaderError, PathStream, Reader, ReaderNotSeekableError, SeekableReader,
+    SliceReader,
+};
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+use bevy_platform::collections::HashMap;
+use core::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use futures_io::{AsyncRead, AsyncSeek};
+#[cfg(not(target_arch = "wasm32"))]
+use futures_lite::AsyncSeekExt;
+use std::{
+    io::{self, Read, Write},
+    path::{Component, Path, PathBuf},
+};
+
+const MAGIC: &[u8; 4] = b"BPAK";
+const VERSION: u32 = 1;
+const ASSET_ENTRY: u8 = 0;
+const META_ENTRY: u8 = 1;
+
+/// The location of an entry's data, relative to the end of the index.
+#[derive(Clone, Copy, Debug)]
+struct PakEntry {
+    offset: u64,
+    len: u64,
+}
+
+#[derive(Debug)]
+enum PakData {
+    Bytes(Arc<[u8]>),
+    #[cfg(not(target_arch = "wasm32"))]
+    File(PathBuf),
+}
+
+#[derive(Debug)]
+struct PakIndex {
+    assets: HashMap<PathBuf, PakEntry>,
+    metadata: HashMap<PathBuf, PakEntry>,
+    /// The assets and directories directly in each directory, including the root at the empty path.
+    directories: HashMap<PathBuf, BTreeSet<PathBuf>>,
+    /// The position of the entries' data in the pack file.
+    data_start: u64,
+    data: PakData,
+}
+
+/// An [`AssetReader`] reading assets and their metadata from a pack file written by a [`PakWriter`].
+///
+/// Only the index of the pack file is read when it is opened; the data of an asset is read when
+/// the asset is. Cloning this reader is cheap, and clones share the index.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Clone, Debug)]
+pub struct PakAssetReader(Arc<PakIndex>);
+
+impl PakAssetReader {
+    /// Opens the pack file at `path`, reading its index.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)?;
+        let len = file.metadata()?.len();
+        let mut file = io::BufReader::new(file);
+        Self::read_index(&mut file, len, PakData::File(path.to_owned()))
+    }
+
+    /// Reads a pack file that is already in memory.
+    pub fn from_bytes(bytes: impl Into<Arc<[u8]>>) -> io::Result<Self> {
+        let bytes = bytes.into();
+        Self::read_index(
+            &mut &bytes[..],
+            bytes.len() as u64,
+            PakData::Bytes(bytes.clone()),
+        )
+    }
+
+    /// Reads the index of a pack file of `len` bytes from `reader`.
+    fn read_index(reader: &mut impl Read, len: u64, data: PakData) -> io::Result<Self> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a pack file"));
+        }
+        let version = read_u32(reader)?;
+        if version != VERSION {
+            return Err(invalid_data("unsupported pack file version"));
+        }
+
+        let mut index = PakIndex {
+            assets: HashMap::default(),
+            metadata: HashMap::default(),
+            directories: HashMap::default(),
+            data_start: 12,
+            data,
+        };
+        // The smallest entry has an empty path.
+        const MIN_ENTRY_LEN: u64 = 1 + 4 + 8 + 8;
+        let entry_count = read_u32(reader)?;
+        if u64::from(entry_count) * MIN_ENTRY_LEN > len.saturating_sub(index.data_start) {
+            return Err(invalid_data("index out of bounds"));
+        }
+        for _ in 0..entry_count {
+            let mut kind = [0];
+            reader.read_exact(&mut kind)?;
+            let path_len = u64::from(read_u32(reader)?);
+            if path_len > len.saturating_sub(index.data_start + MIN_ENTRY_LEN) {
+                return Err(invalid_data("index out of bounds"));
+            }
+            let mut path = alloc::vec![0; path_len as usize];
+            reader.read_exact(&mut path)?;
+            let path = String::from_utf8(path).map_err(|_| invalid_data("invalid entry path"))?;
+            let entry = PakEntry {
+                offset: read_u64(reader)?,
+                len: read_u64(reader)?,
+            };
+            index.data_start += MIN_ENTRY_LEN + path_len;
+
+            let path = PathBuf::from(path);
+            match kind[0] {
+                ASSET_ENTRY => {
+                    let mut child = path.as_path();
+                    while let Some(parent) = child.parent() {
+                        let children = index.directories.entry(parent.to_owned()).or_default();
+                        if !children.insert(child.to_owned()) {
+                            break;
+                        }
+                        child = parent;
+                    }
+                    index.assets.insert(path, entry);
+                }
+                META_ENTRY => {
+                    index.metadata.insert(path, entry);
+                }
+                _ => return Err(invalid_data("invalid entry kind")),
+            }
+        }
+
+        for entry in index.assets.values().chain(index.metadata.values()) {
+            if index.entry_range(entry).is_none_or(|(_, end)| end > len) {
+                return Err(invalid_data("entry out of bounds"));
+            }
+        }
+
+        Ok(Self(Arc::new(index)))
+    }
+
+    async fn read_entry<'a>(
+        &'a self,
+        path: &Path,
+        entry: Option<&PakEntry>,
+    ) -> Result<PakReader<'a>, AssetReaderError> {
+        let entry = entry.ok_or_else(|| AssetReaderError::NotFound(path.to_owned()))?;
+        let (start, end) = self
+            .0
+            .entry_range(entry)
+            .ok_or_else(|| invalid_data("entry out of bounds"))?;
+        match &self.0.data {
+            PakData::Bytes(bytes) => usize::try_from(start)
+                .ok()
+                .zip(usize::try_from(end).ok())
+                .and_then(|(start, end)| bytes.get(start..end))
+                .map(|bytes| PakReader::Slice(SliceReader::new(bytes)))
+                .ok_or_else(|| invalid_data("entry out of bounds").into()),
+            #[cfg(not(target_arch = "wasm32"))]
+            PakData::File(pak_path) => {
+                let mut file = async_fs::File::open(pak_path).await?;
+                // The pack file may have changed since its index was read.
+                if file.metadata().await?.len() < end {
+                    return Err(invalid_data("entry out of bounds").into());
+                }
+                file.seek(io::SeekFrom::Start(start)).await?;
+                Ok(PakReader::File(PakFileReader {
+                    file,
+                    start,
+                    len: entry.len,
+                    position: 0,
+                }))
+            }
+        }
+    }
+}
+
+impl PakIndex {
+    /// Returns the start and end position of the data of `entry` in the pack file, or `None` if
+    /// they overflow.
+    fn entry_range(&self, entry: &PakEntry) -> Option<(u64, u64)> {
+        let start = self.data_start.checked_add(entry.offset)?;
+        Some((start, start.checked_add(entry.len)?))
+    }
+}
+
+/// The [`Reader`] returned by [`PakAssetReader`].
+enum PakReader<'a> {
+    Slice(SliceReader<'a>),
+    #[cfg(not(target_arch = "wasm32"))]
+    File(PakFileReader),
+}
+
+/// Streams the data of an entry from a pack file on disk.
+#[cfg(not(target_arch = "wasm32"))]
+struct PakFileReader {
+    file: async_fs::File,
+    /// The position of the entry's data in the pack file.
+    start: u64,
+    len: u64,
+    /// The position of the reader in the entry's data.
+    position: u64,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl AsyncRead for PakFileReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let remaining = self.len.saturating_sub(self.position);
+        let max = usize::try_from(remaining)
+            .unwrap_or(usize::MAX)
+            .min(buf.len());
+        if max == 0 {
+            return Poll::Ready(Ok(0));
+        }
+        let read = ready!(Pin::new(&mut self.file).poll_read(cx, &mut buf[..max]))?;
+        self.position += read as u64;
+        Poll::Ready(Ok(read))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl AsyncSeek for PakFileReader {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: io::SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let position = match pos {
+            io::SeekFrom::Start(offset) => Some(offset),
+            io::SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+            io::SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+        let Some(file_position) = position.and_then(|position| self.start.checked_add(position))
+        else {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "seek position out of bounds",
+            )));
+        };
+        ready!(Pin::new(&mut self.file).poll_seek(cx, io::SeekFrom::Start(file_position)))?;
+        self.position = file_position - self.start;
+        Poll::Ready(Ok(self.position))
+    }
+}
+
+impl AsyncRead for PakReader<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            PakReader::Slice(reader) => Pin::new(reader).poll_read(cx, buf),
+            #[cfg(not(target_arch = "wasm32"))]
+            PakReader::File(reader) => Pin::new(reader).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncSeek for PakReader<'_> {
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: io::SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        match self.get_mut() {
+            PakReader::Slice(reader) => Pin::new(reader).poll_seek(cx, pos),
+            #[cfg(not(target_arch = "wasm32"))]
+            PakReader::File(reader) => Pin::new(reader).poll_seek(cx, pos),
+        }
+    }
+}
+
+impl Reader for PakReader<'_> {
+    fn seekable(&mut self) -> Result<&mut dyn SeekableReader, ReaderNotSeekableError> {
+        Ok(self)
+    }
+}
+
+impl AssetReader for PakAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.read_entry(path, self.0.assets.get(path)).await
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.read_entry(path, self.0.metadata.get(path)).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let children = self
+            .0
+            .directories
+            .get(path)
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_owned()))?;
+        let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(children.clone()));
+        Ok(stream)
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        Ok(self.0.directories.contains_key(path))
+    }
+}
+
+/// Builds a pack file, to be read by a [`PakAssetReader`].
+///
+/// ```
+/// # use bevy_asset::io::pak::PakWriter;
+/// let mut writer = PakWriter::new();
+/// writer
+///     .add_asset("textures/grass.png", b"...".to_vec())
+///     .add_meta("textures/grass.png", b"(meta_format_version: \"1.0\", ...)".to_vec());
+/// let bytes = writer.to_bytes().unwrap();
+/// ```
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Default, Debug, Clone)]
+pub struct PakWriter {
+    assets: BTreeMap<PathBuf, Vec<u8>>,
+    metadata: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl PakWriter {
+    /// Creates an empty [`PakWriter`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the asset at `path`, replacing any asset previously added at that path.
+    pub fn add_asset(&mut self, path: impl Into<PathBuf>, bytes: Vec<u8>) -> &mut Self {
+        self.assets.insert(path.into(), bytes);
+        self
+    }
+
+    /// Adds the metadata of the asset at `path`, replacing any metadata previously added for it.
+    ///
+    /// As with [`AssetWriter::write_meta`](crate::io::AssetWriter::write_meta), `path` is the path
+    /// of the asset, without a `.meta` extension.
+    pub fn add_meta(&mut self, path: impl Into<PathBuf>, bytes: Vec<u8>) -> &mut Self {
+        self.metadata.insert(path.into(), bytes);
+        self
+    }
+
+    /// Adds every file in the `root` directory and its subdirectories, at their path relative to
+    /// `root`. Files with a `.meta` extension are added as the metadata of the matching asset.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_directory(&mut self, root: impl AsRef<Path>) -> io::Result<&mut Self> {
+        let root = root.as_ref();
+        let mut directories = alloc::vec![root.to_owned()];
+        while let Some(directory) = directories.pop() {
+            for entry in std::fs::read_dir(directory)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    directories.push(path);
+                    continue;
+                }
+                let bytes = std::fs::read(&path)?;
+                let relative = path.strip_prefix(root).unwrap().to_owned();
+                if relative
+                    .extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("meta"))
+                {
+                    self.add_meta(relative.with_extension(""), bytes);
+                } else {
+                    self.add_asset(relative, bytes);
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Writes the pack file to `writer`.
+    pub fn write(&self, mut writer: impl Write) -> io::Result<()> {
+        let entries = self
+            .assets
+            .iter()
+            .map(|(path, bytes)| (ASSET_ENTRY, path, bytes))
+            .chain(
+                self.metadata
+                    .iter()
+                    .map(|(path, bytes)| (META_ENTRY, path, bytes)),
+            )
+            .map(|(kind, path, bytes)| Ok((kind, pak_path(path)?, bytes)))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        let entry_count = u32::try_from(entries.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "too many entries"))?;
+        writer.write_all(&entry_count.to_le_bytes())?;
+        let mut offset = 0u64;
+        for (kind, path, bytes) in &entries {
+            writer.write_all(&[*kind])?;
+            let path_len = u32::try_from(path.len())
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "entry path too long"))?;
+            writer.write_all(&path_len.to_le_bytes())?;
+            writer.write_all(path.as_bytes())?;
+            writer.write_all(&offset.to_le_bytes())?;
+            writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+            offset += bytes.len() as u64;
+        }
+        for (_, _, bytes) in &entries {
+            writer.write_all(bytes)?;
+        }
+        writer.flush()
+    }
+
+    /// Returns the bytes of the pack file.
+    pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.write(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+/// Converts `path` to the UTF-8, `/`-separated form stored in pack files.
+fn pak_path(path: &Path) -> io::Result<String> {
+    let mut components = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => components.push(name.to_str().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "asset paths must be UTF-8")
+            })?),
+            Component::CurDir => {}
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "asset paths must be relative and normalized",
+                ))
+            }
+        }
+    }
+    Ok(components.join("/"))
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec};
+    use futures_lite::{future::block_on, StreamExt};
+
+    fn read(reader: &PakAssetReader, path: &str) -> Result<Vec<u8>, AssetReaderError> {
+        block_on(async {
+            let mut bytes = Vec::new();
+            reader
+                .read(Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(bytes)
+        })
+    }
+
+    #[test]
+    fn read_assets_and_directories() {
+        let mut writer = PakWriter::new();
+        writer
+            .add_asset("a.txt", b"a".to_vec())
+            .add_asset("textures/b.png", b"bb".to_vec())
+            .add_asset("textures/grass/c.png", b"ccc".to_vec())
+            .add_meta("textures/b.png", b"meta".to_vec());
+        let reader = PakAssetReader::from_bytes(writer.to_bytes().unwrap()).unwrap();
+
+        assert_eq!(read(&reader, "a.txt").unwrap(), b"a");
+        assert_eq!(read(&reader, "textures/b.png").unwrap(), b"bb");
+        assert_eq!(read(&reader, "textures/grass/c.png").unwrap(), b"ccc");
+        assert_eq!(
+            read(&reader, "missing.png"),
+            Err(AssetReaderError::NotFound(PathBuf::from("missing.png")))
+        );
+        let meta = block_on(reader.read_meta_bytes(Path::new("textures/b.png"))).unwrap();
+        assert_eq!(meta, b"meta");
+        assert!(block_on(reader.read_meta_bytes(Path::new("a.txt"))).is_err());
+
+        assert!(block_on(reader.is_directory(Path::new("textures/grass"))).unwrap());
+        assert!(!block_on(reader.is_directory(Path::new("a.txt"))).unwrap());
+        let listing: Vec<_> = block_on(async {
+            reader
+                .read_directory(Path::new("textures"))
+                .await
+                .unwrap()
+                .collect()
+                .await
+        });
+        assert_eq!(
+            listing,
+            vec![
+                PathBuf::from("textures/b.png"),
+                PathBuf::from("textures/grass")
+            ]
+        );
+    }
+
+    #[test]
+    fn reject_invalid_pack_files() {
+        assert!(PakAssetReader::from_bytes(b"ZIP!".to_vec()).is_err());
+        let mut bytes = PakWriter::new()
+            .add_asset("a.txt", b"a".to_vec())
+            .to_bytes()
+            .unwrap();
+        bytes.truncate(bytes.len() - 1);
+        assert!(PakAssetReader::from_bytes(bytes).is_err());
+
+        // An entry whose offset overflows, and one whose path is longer than the file.
+        let mut bytes = PakWriter::new()
+            .add_asset("a.txt", b"a".to_vec())
+            .to_bytes()
+            .unwrap();
+        let offset = 12 + 1 + 4 + "a.txt".len();
+        bytes[offset..offset + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(PakAssetReader::from_bytes(bytes.clone()).is_err());
+        bytes[13..17].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(PakAssetReader::from_bytes(bytes).is_err());
+    }
+
+    #[test]
+    fn stream_entries_from_file() {
+        let path =
+            std::env::temp_dir().join(format!("bevy_asset_pak_test_{}.pak", std::process::id()));
+        let mut writer = PakWriter::new();
+        writer
+            .add_asset("a.txt", b"aaa".to_vec())
+            .add_asset("b.txt", b"bbbb".to_vec());
+        writer.write(std::fs::File::create(&path).unwrap()).unwrap();
+        let reader = PakAssetReader::open(&path).unwrap();
+
+        assert_eq!(read(&reader, "a.txt").unwrap(), b"aaa");
+        assert_eq!(read(&reader, "b.txt").unwrap(), b"bbbb");
+        // Seeking stays within the entry.
+        let bytes = block_on(async {
+            let mut entry = reader.read(Path::new("b.txt")).await.unwrap();
+            let entry = entry.seekable().unwrap();
+            entry.seek(io::SeekFrom::End(-2)).await.unwrap();
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes).await.unwrap();
+            bytes
+        });
+        assert_eq!(bytes, b"bb");
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -151,6 +151,7 @@ extern crate std;
 extern crate self as bevy_asset;
 
 pub mod asset_changed;
+pub mod budget;
 pub mod io;
 pub mod meta;
 pub mod processor;
@@ -175,6 +176,7 @@ mod assets;
 mod direct_access_ext;
 mod event;
 mod folder;
+mod graph;
 mod handle;
 mod id;
 mod loader;
@@ -191,6 +193,7 @@ pub use direct_access_ext::DirectAssetAccessExt;
 pub use event::*;
 pub use folder::*;
 pub use futures_lite::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+pub use graph::*;
 pub use handle::*;
 pub use id::*;
 pub use loader::*;
@@ -594,6 +597,14 @@ pub trait AssetApp {
     ///   mutable access to this resource this causes a conflict, but they rarely actually
     ///   modify the same underlying asset.
     fn init_asset<A: Asset>(&mut self) -> &mut Self;
+    /// Limits the memory used by assets of type `A` to the given [`AssetBudget`], evicting the
+    /// least recently used assets accessed through [`ReloadableHandle`]s when it is exceeded.
+    ///
+    /// The asset type must have been initialized using [`AssetApp::init_asset`].
+    ///
+    /// [`AssetBudget`]: budget::AssetBudget
+    /// [`ReloadableHandle`]: budget::ReloadableHandle
+    fn init_asset_budget<A: Asset>(&mut self, budget: budget::AssetBudget<A>) -> &mut Self;
     /// Registers the asset type `T` using `[App::register]`,
     /// and adds [`ReflectAsset`] type data to `T` and [`ReflectHandle`] type data to [`Handle<T>`] in the type registry.
     ///
@@ -687,6 +698,13 @@ impl AssetApp for App {
             )
     }
 
+    fn init_asset_budget<A: Asset>(&mut self, budget: budget::AssetBudget<A>) -> &mut Self {
+        self.insert_resource(budget).add_systems(
+            PostUpdate,
+            budget::evict_assets_over_budget::<A>.after(AssetEventSystems),
+        )
+    }
+
     fn register_asset_reflect<A>(&mut self) -> &mut Self
     where
         A: Asset + Reflect + FromReflect + GetTypeRegistration,
@@ -737,9 +755,10 @@ mod tests {
             AssetWatcher, Reader,
         },
         loader::{AssetLoader, LoadContext},
-        Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets, InvalidGenerationError, LoadState, LoadedAsset,
-        UnapprovedPathMode, UntypedHandle, VisitAssetDependencies, WriteDefaultMetaError,
+        Asset, AssetApp, AssetDependencyGraph, AssetEvent, AssetGraphEdge, AssetId, AssetLoadError,
+        AssetLoadFailedEvent, AssetPath, AssetPlugin, AssetServer, Assets, InvalidGenerationError,
+        LoadPriority, LoadState, LoadedAsset, UnapprovedPathMode, UntypedHandle,
+        VisitAssetDependencies, WriteDefaultMetaError,
     };
     use alloc::{
         boxed::Box,
@@ -1573,6 +1592,92 @@ mod tests {
         assert_eq!(get_started_load_count(app.world()), 3);
     }
 
+    #[test]
+    fn dependency_graph() {
+        let (mut app, dir) = create_app();
+        let cool_text = |text: &str, dependencies: &[&str]| {
+            let cool_text_ron = CoolTextRon {
+                text: text.into(),
+                dependencies: dependencies.iter().map(ToString::to_string).collect(),
+                embedded_dependencies: vec![],
+                sub_texts: vec![],
+            };
+            ron::ser::to_string(&cool_text_ron).unwrap()
+        };
+        dir.insert_asset_text(Path::new("a.cool.ron"), &cool_text("a", &["b.cool.ron"]));
+        dir.insert_asset_text(Path::new("b.cool.ron"), &cool_text("b", &["c.cool.ron"]));
+        dir.insert_asset_text(Path::new("c.cool.ron"), &cool_text("c", &[]));
+        dir.insert_asset_text(Path::new("d.cool.ron"), &cool_text("d", &[]));
+
+        app.init_asset::<CoolText>()
+            .register_asset_loader(CoolTextLoader);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load("a.cool.ron");
+        let d: Handle<CoolText> = asset_server.load("d.cool.ron");
+        run_app_until(&mut app, |_| {
+            (asset_server.is_loaded_with_dependencies(&a) && asset_server.is_loaded(&d))
+                .then_some(())
+        });
+
+        let b = asset_server.get_path_id("b.cool.ron").unwrap();
+        let c = asset_server.get_path_id("c.cool.ron").unwrap();
+        assert_eq!(asset_server.get_dependencies(&a), Some(vec![b]));
+        assert_eq!(asset_server.get_dependents(c), Some(vec![b]));
+        assert_eq!(asset_server.get_dependents(&a), Some(vec![]));
+
+        let graph = asset_server.dependency_graph();
+        assert_eq!(graph.nodes.len(), 4);
+        assert_eq!(graph.edges.len(), 2);
+        let node = graph.node(c).unwrap();
+        assert_eq!(node.type_path, Some(CoolText::type_path()));
+        assert!(node.load_state.is_loaded());
+        // Only b holds a handle to c.
+        assert_eq!(node.strong_handles, 1);
+
+        let paths = |graph: &AssetDependencyGraph| {
+            graph
+                .nodes
+                .iter()
+                .map(|node| node.path.as_ref().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        // Nodes are sorted by path.
+        assert_eq!(
+            paths(&graph),
+            ["a.cool.ron", "b.cool.ron", "c.cool.ron", "d.cool.ron"]
+        );
+        assert_eq!(
+            graph.edges,
+            [
+                AssetGraphEdge {
+                    dependent: 0,
+                    dependency: 1
+                },
+                AssetGraphEdge {
+                    dependent: 1,
+                    dependency: 2
+                }
+            ]
+        );
+
+        let pulled_in_by_a = graph.recursive_dependencies(&a);
+        assert_eq!(
+            paths(&pulled_in_by_a),
+            ["a.cool.ron", "b.cool.ron", "c.cool.ron"]
+        );
+        assert_eq!(pulled_in_by_a.edges.len(), 2);
+        assert_eq!(
+            paths(&graph.recursive_dependents(c)),
+            ["c.cool.ron", "b.cool.ron", "a.cool.ron"]
+        );
+
+        let dot = pulled_in_by_a.to_dot();
+        assert!(dot.starts_with("digraph assets {"));
+        assert!(dot.contains("0 -> 1;"));
+        assert!(dot.contains("1 -> 2;"));
+        assert!(!dot.contains("d.cool.ron"));
+    }
+
     const SIMPLE_TEXT: &str = r#"
 (
     text: "dep",
@@ -2365,6 +2470,123 @@ mod tests {
         }
     }
 
+    /// A loader that reports the path of each load it starts, and blocks on a receiver until the
+    /// test lets it finish.
+    #[derive(TypePath)]
+    struct ReportingGatedLoader {
+        started_sender: Sender<AssetPath<'static>>,
+        gate_receiver: Receiver<()>,
+    }
+
+    impl AssetLoader for ReportingGatedLoader {
+        type Asset = TestAsset;
+        type Error = std::io::Error;
+        type Settings = ();
+
+        async fn load(
+            &self,
+            _reader: &mut dyn Reader,
+            _settings: &Self::Settings,
+            load_context: &mut LoadContext<'_>,
+        ) -> Result<Self::Asset, Self::Error> {
+            self.started_sender
+                .send_blocking(load_context.path().clone_owned())
+                .unwrap();
+            let _ = self.gate_receiver.recv().await;
+            Ok(TestAsset)
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["ron"]
+        }
+    }
+
+    /// Runs `app` until `receiver` reports that a load has started, and returns its path.
+    fn run_app_until_load_starts(
+        app: &mut App,
+        receiver: &Receiver<AssetPath<'static>>,
+    ) -> AssetPath<'static> {
+        let mut path = None;
+        run_app_until(app, |_| {
+            path = receiver.try_recv().ok();
+            path.as_ref().map(|_| ())
+        });
+        path.unwrap()
+    }
+
+    #[test]
+    fn load_priorities_and_cancellation() {
+        let (mut app, dir) = create_app();
+
+        let (started_sender, started_receiver) = async_channel::unbounded();
+        let (gate_sender, gate_receiver) = async_channel::unbounded();
+
+        app.init_asset::<TestAsset>()
+            .register_asset_loader(ReportingGatedLoader {
+                started_sender,
+                gate_receiver,
+            });
+
+        for path in ["a.ron", "b.ron", "c.ron"] {
+            dir.insert_asset_text(Path::new(path), "blah");
+        }
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        asset_server.set_max_concurrent_loads(Some(1));
+
+        let a = asset_server.load::<TestAsset>("a.ron");
+        assert_eq!(
+            run_app_until_load_starts(&mut app, &started_receiver),
+            "a.ron".into()
+        );
+
+        // Both of these are queued behind `a`.
+        let b = asset_server
+            .load_builder()
+            .with_priority(LoadPriority::LOW)
+            .load::<TestAsset>("b.ron");
+        let c = asset_server
+            .load_builder()
+            .with_priority(LoadPriority::LOW)
+            .load::<TestAsset>("c.ron");
+        assert!(asset_server.set_load_priority(&c, LoadPriority::HIGH));
+        // `a` has already started, so it can't be reprioritized.
+        assert!(!asset_server.set_load_priority(&a, LoadPriority::HIGH));
+
+        // Cancelling `a` frees its slot for `c`, which now has the highest priority.
+        assert!(asset_server.cancel_load(&a));
+        assert!(!asset_server.cancel_load(&a));
+        assert!(matches!(
+            asset_server.get_load_state(&a),
+            Some(LoadState::NotLoaded)
+        ));
+        assert_eq!(
+            run_app_until_load_starts(&mut app, &started_receiver),
+            "c.ron".into()
+        );
+
+        gate_sender.send_blocking(()).unwrap();
+        run_app_until(&mut app, |world| get(world, c.id()).map(|_| ()));
+        assert_eq!(
+            run_app_until_load_starts(&mut app, &started_receiver),
+            "b.ron".into()
+        );
+
+        gate_sender.send_blocking(()).unwrap();
+        run_app_until(&mut app, |world| get(world, b.id()).map(|_| ()));
+        assert!(get(app.world(), a.id()).is_none());
+
+        // Loading the cancelled asset again starts a new load.
+        let a_again = asset_server.load::<TestAsset>("a.ron");
+        assert_eq!(a_again, a);
+        assert_eq!(
+            run_app_until_load_starts(&mut app, &started_receiver),
+            "a.ron".into()
+        );
+        gate_sender.send_blocking(()).unwrap();
+        run_app_until(&mut app, |world| get(world, a.id()).map(|_| ()));
+    }
+
     // Creates a basic app with the default asset source engineered to get back the asset event
     // sender.
     fn create_app_with_source_event_sender() -> (App, Dir, Sender<AssetSourceEvent>) {
@@ -4,7 +4,7 @@ use crate::{
     meta::{AssetHash, AssetMeta, AssetMetaDyn, ProcessedInfo, ProcessedInfoMinimal, Settings},
     path::AssetPath,
     Asset, AssetIndex, AssetLoadError, AssetServer, AssetServerMode, Assets, ErasedAssetIndex,
-    Handle, UntypedAssetId, UntypedHandle,
+    Handle, LoadPriority, UntypedAssetId, UntypedHandle,
 };
 use alloc::{boxed::Box, string::ToString, vec::Vec};
 use atomicow::CowArc;
@@ -382,6 +382,8 @@ pub struct LoadContext<'a> {
     /// need the dependency information, for example during asset processing.
     pub(crate) should_load_dependencies: bool,
     populate_hashes: bool,
+    /// The priority of this load, which is inherited by the dependencies it loads.
+    pub(crate) priority: LoadPriority,
     asset_path: AssetPath<'static>,
     pub(crate) dependencies: HashSet<ErasedAssetIndex>,
     /// Direct dependencies used by this loader.
@@ -404,12 +406,14 @@ impl<'a> LoadContext<'a> {
         asset_path: AssetPath<'static>,
         should_load_dependencies: bool,
         populate_hashes: bool,
+        priority: LoadPriority,
     ) -> Self {
         Self {
             asset_server,
             asset_path,
             populate_hashes,
             should_load_dependencies,
+            priority,
             dependencies: HashSet::default(),
             loader_dependencies: HashMap::default(),
             labeled_assets: Default::default(),
@@ -453,6 +457,7 @@ impl<'a> LoadContext<'a> {
             self.asset_path.clone(),
             self.should_load_dependencies,
             self.populate_hashes,
+            self.priority,
         )
     }
 
@@ -665,6 +670,7 @@ impl<'a> LoadContext<'a> {
                 reader,
                 self.should_load_dependencies,
                 self.populate_hashes,
+                self.priority,
             )
             .await
             .map_err(|error| LoadDirectError::LoadError {
@@ -123,6 +123,7 @@ impl<'ctx, 'builder> NestedLoadBuilder<'ctx, 'builder> {
                     self.meta_transform,
                     (),
                     self.override_unapproved,
+                    self.load_context.priority,
                 )
         } else {
             self.load_context
@@ -240,6 +241,7 @@ impl<'ctx, 'builder> NestedLoadBuilder<'ctx, 'builder> {
                 self.meta_transform,
                 (),
                 self.override_unapproved,
+                self.load_context.priority,
             )
         } else {
             self.load_context
@@ -0,0 +1,363 @@
+use crate::{meta::AssetHash, AssetPath};
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use async_lock::Mutex;
+use bevy_ecs::error::BevyError;
+use bevy_platform::collections::HashMap;
+use bevy_tasks::BoxedFuture;
+use futures_lite::StreamExt;
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use thiserror::Error;
+use tracing::warn;
+
+/// The processed outputs of a single asset, as stored in a [`ProcessorCache`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CachedProcessedAsset {
+    /// The bytes of the processed asset.
+    pub asset: Vec<u8>,
+    /// The bytes of the processed asset's `.meta` file. This includes the
+    /// [`ProcessedInfo`](crate::meta::ProcessedInfo) produced when the asset was processed.
+    pub meta: Vec<u8>,
+}
+
+/// A content-addressed store of processed asset outputs, used by the
+/// [`AssetProcessor`](super::AssetProcessor) to avoid re-running processors on inputs it has
+/// already seen.
+///
+/// Entries are keyed by a hash of the asset path, the asset source bytes, its `.meta` (which
+/// includes the processor settings), the processor type path, and the processor's
+/// [`Process::VERSION`](super::Process::VERSION). This means that switching branches or reverting a
+/// file back to a previous state will reuse the outputs produced the last time the asset was
+/// processed with those inputs. The path is part of the key because processed outputs can depend
+/// on it, for example through the paths of their dependencies.
+pub trait ProcessorCache: Send + Sync + 'static {
+    /// Returns the cached outputs for `key`, if they exist.
+    fn get<'a>(
+        &'a self,
+        key: &'a AssetHash,
+    ) -> BoxedFuture<'a, Result<Option<CachedProcessedAsset>, BevyError>>;
+
+    /// Stores `asset` as the outputs for `key`, replacing any existing entry.
+    fn put<'a>(
+        &'a self,
+        key: &'a AssetHash,
+        asset: &'a CachedProcessedAsset,
+    ) -> BoxedFuture<'a, Result<(), BevyError>>;
+}
+
+/// Computes the [`ProcessorCache`] key for the asset at `asset_path` with the given source `hash`
+/// (see [`ProcessedInfo::hash`](crate::meta::ProcessedInfo::hash)), processed by the processor with
+/// the given type path and version.
+pub fn get_processor_cache_key(
+    asset_path: &AssetPath,
+    hash: AssetHash,
+    processor_type_path: &str,
+    processor_version: u32,
+) -> AssetHash {
+    let asset_path = asset_path.to_string();
+    let mut hasher = blake3::Hasher::new();
+    // The length keeps the path from running into the rest of the key.
+    hasher.update(&(asset_path.len() as u64).to_le_bytes());
+    hasher.update(asset_path.as_bytes());
+    hasher.update(&hash);
+    hasher.update(processor_type_path.as_bytes());
+    hasher.update(&processor_version.to_le_bytes());
+    *hasher.finalize().as_bytes()
+}
+
+/// A [`ProcessorCache`] that stores each entry as a file in a local directory.
+///
+/// When the total size of the entries exceeds [`FileProcessorCache::max_size`], the least recently
+/// used entries are evicted. Recency is tracked using the modification time of each entry file, so
+/// it is preserved across runs. The directory is only scanned the first time an entry is stored;
+/// after that, the size and recency of the entries are tracked in memory.
+pub struct FileProcessorCache {
+    /// The directory entries are stored in.
+    pub root: PathBuf,
+    /// The maximum total size of all entries, in bytes.
+    pub max_size: u64,
+    /// The entries in [`FileProcessorCache::root`], or `None` if it hasn't been scanned yet.
+    index: Mutex<Option<CacheIndex>>,
+}
+
+/// The size and recency of the entries of a [`FileProcessorCache`].
+#[derive(Default)]
+struct CacheIndex {
+    total_size: u64,
+    /// The size and last use of each entry, by path.
+    entries: HashMap<PathBuf, (u64, u64)>,
+    /// The path of each entry, by last use.
+    by_last_use: BTreeMap<u64, PathBuf>,
+    next_use: u64,
+}
+
+impl CacheIndex {
+    /// Scans the entries in `root`, ordering their uses by modification time.
+    async fn load(root: &Path) -> Result<Self, std::io::Error> {
+        let mut entries = Vec::new();
+        let mut read_dir = async_fs::read_dir(root).await?;
+        while let Some(entry) = read_dir.try_next().await? {
+            let metadata = entry.metadata().await?;
+            // Skip in-progress writes (see `put`).
+            if !metadata.is_file() || entry.path().extension().is_some_and(|ext| ext == "tmp") {
+                continue;
+            }
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            entries.push((modified, metadata.len(), entry.path()));
+        }
+        entries.sort_unstable_by_key(|entry| entry.0);
+        let mut index = Self::default();
+        for (_, len, path) in entries {
+            index.insert(path, len);
+        }
+        Ok(index)
+    }
+
+    /// Adds or replaces the entry at `path`, as the most recently used one.
+    fn insert(&mut self, path: PathBuf, len: u64) {
+        if let Some((old_len, last_use)) = self.entries.remove(&path) {
+            self.total_size -= old_len;
+            self.by_last_use.remove(&last_use);
+        }
+        self.total_size += len;
+        self.entries.insert(path.clone(), (len, self.next_use));
+        self.by_last_use.insert(self.next_use, path);
+        self.next_use += 1;
+    }
+
+    /// Marks the entry at `path` as the most recently used one.
+    fn touch(&mut self, path: &Path) {
+        if let Some((len, _)) = self.entries.get(path) {
+            self.insert(path.to_owned(), *len);
+        }
+    }
+
+    /// Removes the least recently used entry, returning its path.
+    fn pop_least_recently_used(&mut self) -> Option<PathBuf> {
+        let (_, path) = self.by_last_use.pop_first()?;
+        let (len, _) = self.entries.remove(&path).unwrap();
+        self.total_size -= len;
+        Some(path)
+    }
+}
+
+const CACHE_PATH: &str = "imported_assets/cache";
+
+/// The default [`FileProcessorCache::max_size`]: 1 GiB.
+pub const DEFAULT_PROCESSOR_CACHE_MAX_SIZE: u64 = 1024 * 1024 * 1024;
+
+impl Default for FileProcessorCache {
+    fn default() -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let base_path = crate::io::file::get_base_path();
+        #[cfg(target_arch = "wasm32")]
+        let base_path = PathBuf::new();
+        Self::new(base_path.join(CACHE_PATH), DEFAULT_PROCESSOR_CACHE_MAX_SIZE)
+    }
+}
+
+impl FileProcessorCache {
+    /// Creates a new cache in the `root` directory, holding at most `max_size` bytes.
+    pub fn new(root: impl Into<PathBuf>, max_size: u64) -> Self {
+        Self {
+            root: root.into(),
+            max_size,
+            index: Mutex::new(None),
+        }
+    }
+
+    fn entry_path(&self, key: &AssetHash) -> PathBuf {
+        let mut name = String::with_capacity(key.len() * 2);
+        for byte in key {
+            name.push_str(&format!("{byte:02x}"));
+        }
+        self.root.join(name)
+    }
+
+    /// Records that the entry at `path` was stored with `len` bytes, then removes the least
+    /// recently used entries until the total size is at most [`FileProcessorCache::max_size`].
+    async fn insert_and_evict(&self, path: PathBuf, len: u64) -> Result<(), std::io::Error> {
+        let mut guard = self.index.lock().await;
+        let index = match guard.take() {
+            Some(mut index) => {
+                index.insert(path, len);
+                index
+            }
+            // The new entry is already on disk, so it's picked up by the scan.
+            None => CacheIndex::load(&self.root).await?,
+        };
+        let index = guard.insert(index);
+        while index.total_size > self.max_size {
+            let Some(path) = index.pop_least_recently_used() else {
+                break;
+            };
+            if let Err(err) = async_fs::remove_file(&path).await {
+                warn!("Failed to evict processor cache entry {path:?}: {err}");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error that occurs when reading an entry of a [`FileProcessorCache`].
+#[derive(Error, Debug)]
+pub enum ReadProcessorCacheError {
+    /// The entry file is too short to contain its header or meta bytes.
+    #[error("Processor cache entry {0:?} is truncated")]
+    Truncated(PathBuf),
+}
+
+// Entries are stored as a little-endian u64 meta length, followed by the meta bytes, followed by
+// the asset bytes.
+const ENTRY_HEADER_LEN: usize = size_of::<u64>();
+
+fn decode_entry(path: &Path, bytes: Vec<u8>) -> Result<CachedProcessedAsset, BevyError> {
+    let truncated = || ReadProcessorCacheError::Truncated(path.to_owned());
+    let header = bytes.get(..ENTRY_HEADER_LEN).ok_or_else(truncated)?;
+    let meta_len = u64::from_le_bytes(header.try_into().unwrap());
+    let meta_end = usize::try_from(meta_len)
+        .ok()
+        .and_then(|len| ENTRY_HEADER_LEN.checked_add(len))
+        .filter(|end| *end <= bytes.len())
+        .ok_or_else(truncated)?;
+    Ok(CachedProcessedAsset {
+        meta: bytes[ENTRY_HEADER_LEN..meta_end].to_vec(),
+        asset: bytes[meta_end..].to_vec(),
+    })
+}
+
+impl ProcessorCache for FileProcessorCache {
+    fn get<'a>(
+        &'a self,
+        key: &'a AssetHash,
+    ) -> BoxedFuture<'a, Result<Option<CachedProcessedAsset>, BevyError>> {
+        Box::pin(async move {
+            let path = self.entry_path(key);
+            let bytes = match async_fs::read(&path).await {
+                Ok(bytes) => bytes,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                Err(err) => return Err(err.into()),
+            };
+            // Mark the entry as recently used. Failing to update its modification time only
+            // affects eviction order in later runs, so it's not worth failing the lookup over.
+            if let Some(index) = &mut *self.index.lock().await {
+                index.touch(&path);
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                let path = path.clone();
+                let _ = blocking::unblock(move || {
+                    std::fs::File::options()
+                        .write(true)
+                        .open(path)
+                        .and_then(|file| file.set_modified(SystemTime::now()))
+                })
+                .await;
+            }
+            decode_entry(&path, bytes).map(Some)
+        })
+    }
+
+    fn put<'a>(
+        &'a self,
+        key: &'a AssetHash,
+        asset: &'a CachedProcessedAsset,
+    ) -> BoxedFuture<'a, Result<(), BevyError>> {
+        Box::pin(async move {
+            async_fs::create_dir_all(&self.root).await?;
+            let mut bytes =
+                Vec::with_capacity(ENTRY_HEADER_LEN + asset.meta.len() + asset.asset.len());
+            bytes.extend_from_slice(&(asset.meta.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&asset.meta);
+            bytes.extend_from_slice(&asset.asset);
+            // Write to a temporary file first so that a crash can never leave a partial entry
+            // behind under the real key.
+            let path = self.entry_path(key);
+            let temp_path = path.with_extension("tmp");
+            async_fs::write(&temp_path, &bytes).await?;
+            async_fs::rename(&temp_path, &path).await?;
+            self.insert_and_evict(path, bytes.len() as u64).await?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use futures_lite::future::block_on;
+
+    #[test]
+    fn entry_round_trip() {
+        let asset = CachedProcessedAsset {
+            asset: b"processed".to_vec(),
+            meta: b"(meta)".to_vec(),
+        };
+        let mut bytes = (asset.meta.len() as u64).to_le_bytes().to_vec();
+        bytes.extend_from_slice(&asset.meta);
+        bytes.extend_from_slice(&asset.asset);
+
+        let path = Path::new("entry");
+        assert_eq!(decode_entry(path, bytes.clone()).unwrap(), asset);
+        assert!(decode_entry(path, bytes[..10].to_vec()).is_err());
+    }
+
+    #[test]
+    fn cache_key_depends_on_path_and_processor_version() {
+        let hash = [7; 32];
+        let path = AssetPath::from("a.png");
+        assert_eq!(
+            get_processor_cache_key(&path, hash, "Processor", 0),
+            get_processor_cache_key(&path, hash, "Processor", 0)
+        );
+        assert_ne!(
+            get_processor_cache_key(&path, hash, "Processor", 0),
+            get_processor_cache_key(&AssetPath::from("b.png"), hash, "Processor", 0)
+        );
+        assert_ne!(
+            get_processor_cache_key(&path, hash, "Processor", 0),
+            get_processor_cache_key(&path, hash, "Processor", 1)
+        );
+        assert_ne!(
+            get_processor_cache_key(&path, hash, "Processor", 0),
+            get_processor_cache_key(&path, hash, "Other", 0)
+        );
+    }
+
+    #[test]
+    fn evicts_least_recently_used_entries() {
+        let root = std::env::temp_dir().join(format!(
+            "bevy_asset_processor_cache_test_{}",
+            std::process::id()
+        ));
+        // Each entry takes 8 header bytes and 10 asset bytes.
+        let cache = FileProcessorCache::new(&root, 40);
+        let entry = |byte| CachedProcessedAsset {
+            asset: vec![byte; 10],
+            meta: Vec::new(),
+        };
+        let (a, b, c) = ([0; 32], [1; 32], [2; 32]);
+        block_on(async {
+            cache.put(&a, &entry(0)).await.unwrap();
+            cache.put(&b, &entry(1)).await.unwrap();
+            // Using `a` makes `b` the least recently used entry.
+            assert_eq!(cache.get(&a).await.unwrap(), Some(entry(0)));
+            cache.put(&c, &entry(2)).await.unwrap();
+
+            assert_eq!(cache.get(&a).await.unwrap(), Some(entry(0)));
+            assert_eq!(cache.get(&b).await.unwrap(), None);
+            assert_eq!(cache.get(&c).await.unwrap(), Some(entry(2)));
+        });
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
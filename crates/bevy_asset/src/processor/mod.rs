@@ -37,10 +37,12 @@
 //!
 //! In most cases, [`LoadTransformAndSave`] should be sufficient.
 
+mod cache;
 mod log;
 mod process;
 
 use async_lock::RwLockReadGuardArc;
+pub use cache::*;
 pub use log::*;
 pub use process::*;
 
@@ -93,6 +95,10 @@ use {
 /// A [`ProcessorTransactionLog`] is produced, which uses "write-ahead logging" to make the [`AssetProcessor`] crash and failure resistant. If a failed/unfinished
 /// transaction from a previous run is detected, the affected asset(s) will be re-processed.
 ///
+/// If a [`ProcessorCache`] is set using [`AssetProcessorData::set_cache`], processed outputs are also stored by content. When an asset's inputs
+/// match ones that were processed before (for example after switching branches or reverting a file), the cached outputs are written instead
+/// of running the processor again.
+///
 /// [`AssetProcessor`] can be cloned. It is backed by an [`Arc`] so clones will share state. Clones can be freely used in parallel.
 #[derive(Resource, Clone)]
 pub struct AssetProcessor {
@@ -111,6 +117,8 @@ pub struct AssetProcessorData {
     /// avoids needing to use [`block_on`](bevy_tasks::block_on) to set the factory).
     log_factory: Mutex<Option<Box<dyn ProcessorTransactionLogFactory>>>,
     log: async_lock::RwLock<Option<Box<dyn ProcessorTransactionLog>>>,
+    /// The content-addressed cache of processed outputs, if any.
+    cache: RwLock<Option<Arc<dyn ProcessorCache>>>,
     /// The processors that will be used to process assets.
     processors: RwLock<Processors>,
     sources: Arc<AssetSources>,
@@ -1144,6 +1152,24 @@ impl AssetProcessor {
             }
         }
 
+        // Only processed outputs are cached, since copying an unprocessed asset is already cheap.
+        let cache = match (&processor, self.data.cache()) {
+            (Some(processor), Some(cache)) => {
+                let key = get_processor_cache_key(
+                    asset_path,
+                    new_hash,
+                    processor.type_path(),
+                    processor.version(),
+                );
+                Some((cache, key))
+            }
+            _ => None,
+        };
+        let cached = match &cache {
+            Some((cache, key)) => self.get_cached_outputs(cache.as_ref(), key, new_hash).await,
+            None => None,
+        };
+
         // Note: this lock must remain alive until all processed asset and meta writes have finished (or failed)
         // See ProcessedAssetInfo::file_transaction_lock docs for more info
         let _transaction_lock = {
@@ -1162,7 +1188,29 @@ impl AssetProcessor {
         // Directly writing to the asset destination in the processor necessitates this behavior
         // TODO: this class of failure can be recovered via re-processing + smarter log validation that allows for duplicate transactions in the event of failures
         self.log_begin_processing(asset_path).await;
-        if let Some(processor) = processor {
+        if let Some((cached, processed_info)) = cached {
+            debug!("Reusing cached processor outputs for {}", asset_path);
+            let mut writer = processed_writer.write(path).await.map_err(writer_err)?;
+            writer
+                .write_all(&cached.asset)
+                .await
+                .map_err(|e| ProcessError::AssetWriterError {
+                    path: asset_path.clone(),
+                    err: AssetWriterError::Io(e),
+                })?;
+            writer
+                .flush()
+                .await
+                .map_err(|e| ProcessError::AssetWriterError {
+                    path: asset_path.clone(),
+                    err: AssetWriterError::Io(e),
+                })?;
+            processed_writer
+                .write_meta_bytes(path, &cached.meta)
+                .await
+                .map_err(writer_err)?;
+            new_processed_info = processed_info;
+        } else if let Some(processor) = processor {
             // Unwrap is ok since we have a processor, so the `AssetAction` must have been
             // `AssetAction::Process` (which includes its settings).
             let settings = source_meta.process_settings().unwrap();
@@ -1222,6 +1270,11 @@ impl AssetProcessor {
                 .write_meta_bytes(path, &meta_bytes)
                 .await
                 .map_err(writer_err)?;
+
+            if let Some((cache, key)) = &cache {
+                self.put_cached_outputs(source, asset_path, cache.as_ref(), key, meta_bytes)
+                    .await;
+            }
         } else {
             // See the reasoning for processing why it's ok to do a second read here.
             let mut reader_for_copy = reader.read(path).await.map_err(reader_err)?;
@@ -1244,6 +1297,77 @@ impl AssetProcessor {
         Ok(ProcessResult::Processed(new_processed_info))
     }
 
+    /// Looks up the outputs stored under `key` in the `cache`, returning them along with their
+    /// [`ProcessedInfo`] if they are still valid. Outputs are only valid if every process dependency
+    /// they were produced with is currently processed with the same full hash.
+    async fn get_cached_outputs(
+        &self,
+        cache: &dyn ProcessorCache,
+        key: &AssetHash,
+        hash: AssetHash,
+    ) -> Option<(CachedProcessedAsset, ProcessedInfo)> {
+        let cached = match cache.get(key).await {
+            Ok(cached) => cached?,
+            Err(err) => {
+                warn!("Failed to read from the processor cache: {err}");
+                return None;
+            }
+        };
+        let processed_info = ron::de::from_bytes::<ProcessedInfoMinimal>(&cached.meta)
+            .ok()?
+            .processed_info?;
+        if processed_info.hash != hash {
+            return None;
+        }
+        for dependency in &processed_info.process_dependencies {
+            // Dependencies must be processed before we can compare against their hashes, the same
+            // as if the processor loaded them itself.
+            self.data
+                .wait_until_processed(dependency.path.clone())
+                .await;
+        }
+        let infos = self.data.processing_state.asset_infos.read().await;
+        for dependency in &processed_info.process_dependencies {
+            let live_hash = infos
+                .get(&dependency.path)
+                .and_then(|i| i.processed_info.as_ref())
+                .map(|i| i.full_hash);
+            if live_hash != Some(dependency.full_hash) {
+                return None;
+            }
+        }
+        Some((cached, processed_info))
+    }
+
+    /// Stores the freshly processed outputs of `asset_path` in the `cache` under `key`. Failures are
+    /// logged, since they only mean the asset will have to be processed again in the future.
+    async fn put_cached_outputs(
+        &self,
+        source: &AssetSource,
+        asset_path: &AssetPath<'static>,
+        cache: &dyn ProcessorCache,
+        key: &AssetHash,
+        meta: Vec<u8>,
+    ) {
+        // Read back what we just wrote, bypassing the gate (since this asset is not done
+        // processing yet).
+        let Some(reader) = source.ungated_processed_reader() else {
+            return;
+        };
+        let mut asset = Vec::new();
+        let result = match reader.read(asset_path.path()).await {
+            Ok(mut reader) => reader.read_to_end(&mut asset).await.map_err(Into::into),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            warn!("Failed to read processed asset {asset_path} for the processor cache: {err}");
+            return;
+        }
+        if let Err(err) = cache.put(key, &CachedProcessedAsset { asset, meta }).await {
+            warn!("Failed to write {asset_path} to the processor cache: {err}");
+        }
+    }
+
     async fn validate_transaction_log_and_recover(&self) {
         let log_factory = self
             .data
@@ -1347,6 +1471,7 @@ impl AssetProcessorData {
             sources,
             log_factory: Mutex::new(Some(Box::new(FileTransactionLogFactory::default()))),
             log: Default::default(),
+            cache: Default::default(),
             processors: Default::default(),
         }
     }
@@ -1373,6 +1498,22 @@ impl AssetProcessorData {
         Ok(())
     }
 
+    /// Sets the [`ProcessorCache`] used to reuse previously processed outputs. Use
+    /// [`FileProcessorCache`] to store them in a local directory.
+    ///
+    /// No cache is used by default.
+    pub fn set_cache(&self, cache: impl ProcessorCache) {
+        *self.cache.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(cache));
+    }
+
+    /// Returns the [`ProcessorCache`] set by [`AssetProcessorData::set_cache`], if any.
+    pub fn cache(&self) -> Option<Arc<dyn ProcessorCache>> {
+        self.cache
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
     /// Returns a future that will not finish until the path has been processed.
     pub async fn wait_until_processed(&self, path: AssetPath<'static>) -> ProcessStatus {
         self.processing_state.wait_until_processed(path).await
@@ -28,9 +28,11 @@ use crate::{
         AssetReader, AssetReaderError, AssetSourceBuilder, AssetSourceBuilders, AssetSourceEvent,
         AssetSourceId, AssetWatcher, PathStream, Reader,
     },
+    meta::AssetHash,
     processor::{
-        AssetProcessor, GetProcessorError, LoadTransformAndSave, LogEntry, Process, ProcessContext,
-        ProcessError, ProcessorState, ProcessorTransactionLog, ProcessorTransactionLogFactory,
+        AssetProcessor, CachedProcessedAsset, GetProcessorError, LoadTransformAndSave, LogEntry,
+        Process, ProcessContext, ProcessError, ProcessorCache, ProcessorState,
+        ProcessorTransactionLog, ProcessorTransactionLogFactory,
     },
     saver::{tests::CoolTextSaver, AssetSaver},
     tests::{
@@ -1679,6 +1681,122 @@ fn only_reprocesses_wrong_hash_on_startup() {
     );
 }
 
+#[test]
+fn reuses_cached_outputs_when_source_is_reverted() {
+    /// A [`ProcessorCache`] that keeps its entries in memory.
+    #[derive(Default, Clone)]
+    struct MemoryProcessorCache(Arc<Mutex<HashMap<AssetHash, CachedProcessedAsset>>>);
+
+    impl ProcessorCache for MemoryProcessorCache {
+        fn get<'a>(
+            &'a self,
+            key: &'a AssetHash,
+        ) -> BoxedFuture<'a, Result<Option<CachedProcessedAsset>, BevyError>> {
+            Box::pin(async move {
+                let entries = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+                Ok(entries.get(key).cloned())
+            })
+        }
+
+        fn put<'a>(
+            &'a self,
+            key: &'a AssetHash,
+            asset: &'a CachedProcessedAsset,
+        ) -> BoxedFuture<'a, Result<(), BevyError>> {
+            Box::pin(async move {
+                let mut entries = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+                entries.insert(*key, asset.clone());
+                Ok(())
+            })
+        }
+    }
+
+    #[derive(TypePath, Clone)]
+    struct CountAddText(Arc<Mutex<u32>>);
+
+    impl MutateAsset<CoolText> for CountAddText {
+        fn mutate(&self, text: &mut CoolText) {
+            *self.0.lock().unwrap_or_else(PoisonError::into_inner) += 1;
+            text.text.push_str(" processed");
+        }
+    }
+
+    let AppWithProcessor {
+        mut app,
+        source_gate,
+        default_source_dirs:
+            ProcessingDirs {
+                source: source_dir,
+                processed: processed_dir,
+                source_event_sender,
+            },
+        ..
+    } = create_app_with_asset_processor(&[]);
+
+    let cache = MemoryProcessorCache::default();
+    app.world()
+        .resource::<AssetProcessor>()
+        .data()
+        .set_cache(cache.clone());
+
+    let count = Arc::new(Mutex::new(0));
+    let get_process_count = || *count.lock().unwrap_or_else(PoisonError::into_inner);
+    type CoolTextProcessor = LoadTransformAndSave<
+        CoolTextLoader,
+        RootAssetTransformer<CountAddText, CoolText>,
+        CoolTextSaver,
+    >;
+    app.init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader)
+        .register_asset_processor(CoolTextProcessor::new(
+            RootAssetTransformer::new(CountAddText(count.clone())),
+            CoolTextSaver,
+        ))
+        .set_default_asset_processor::<CoolTextProcessor>("cool.ron");
+
+    let path = Path::new("abc.cool.ron");
+    let guard = source_gate.write_blocking();
+    source_dir.insert_asset_text(path, &serialize_as_cool_text("abc"));
+    run_app_until_finished_processing(&mut app, guard);
+
+    assert_eq!(
+        read_asset_as_string(&processed_dir, path),
+        serialize_as_cool_text("abc processed")
+    );
+    assert_eq!(get_process_count(), 1);
+    assert_eq!(cache.0.lock().unwrap().len(), 1);
+
+    // Change the source, which requires processing it again.
+    let guard = source_gate.write_blocking();
+    source_dir.insert_asset_text(path, &serialize_as_cool_text("def"));
+    source_event_sender
+        .send_blocking(AssetSourceEvent::ModifiedAsset(path.to_path_buf()))
+        .unwrap();
+    run_app_until_finished_processing(&mut app, guard);
+
+    assert_eq!(
+        read_asset_as_string(&processed_dir, path),
+        serialize_as_cool_text("def processed")
+    );
+    assert_eq!(get_process_count(), 2);
+    assert_eq!(cache.0.lock().unwrap().len(), 2);
+
+    // Revert the source. The outputs from the first run are reused without running the processor.
+    let guard = source_gate.write_blocking();
+    source_dir.insert_asset_text(path, &serialize_as_cool_text("abc"));
+    source_event_sender
+        .send_blocking(AssetSourceEvent::ModifiedAsset(path.to_path_buf()))
+        .unwrap();
+    run_app_until_finished_processing(&mut app, guard);
+
+    assert_eq!(
+        read_asset_as_string(&processed_dir, path),
+        serialize_as_cool_text("abc processed")
+    );
+    assert_eq!(get_process_count(), 2);
+}
+
 #[test]
 fn writes_short_default_meta_for_processor() {
     let AppWithProcessor {
@@ -1,12 +1,14 @@
 use crate::{
     meta::{AssetHash, MetaTransform},
-    Asset, AssetHandleProvider, AssetIndex, AssetLoadError, AssetPath, DependencyLoadState,
-    ErasedAssetIndex, ErasedLoadedAsset, Handle, InternalAssetEvent, LoadState,
-    RecursiveDependencyLoadState, StrongHandle, UntypedHandle,
+    Asset, AssetDependencyGraph, AssetGraphEdge, AssetGraphNode, AssetHandleProvider, AssetIndex,
+    AssetLoadError, AssetPath, DependencyLoadState, ErasedAssetIndex, ErasedLoadedAsset, Handle,
+    InternalAssetEvent, LoadState, RecursiveDependencyLoadState, StrongHandle, UntypedAssetId,
+    UntypedHandle,
 };
 use alloc::{
     borrow::ToOwned,
     boxed::Box,
+    string::ToString,
     sync::{Arc, Weak},
     vec::Vec,
 };
@@ -35,6 +37,11 @@ pub(crate) struct AssetInfo {
     failed_rec_dependencies: HashSet<ErasedAssetIndex>,
     dependents_waiting_on_load: HashSet<ErasedAssetIndex>,
     dependents_waiting_on_recursive_dep_load: HashSet<ErasedAssetIndex>,
+    /// The direct dependencies of this asset, as reported by its [`LoadedAsset`] the last time it
+    /// was loaded.
+    ///
+    /// [`LoadedAsset`]: crate::loader::LoadedAsset
+    dependencies: HashSet<ErasedAssetIndex>,
     /// The asset paths required to load this asset. Hashes will only be set for processed assets.
     /// This is set using the value from [`LoadedAsset`].
     /// This will only be populated if [`AssetInfos::watching_for_changes`] is set to `true` to
@@ -61,6 +68,7 @@ impl AssetInfo {
             failed_dependencies: HashSet::default(),
             loading_rec_dependencies: HashSet::default(),
             failed_rec_dependencies: HashSet::default(),
+            dependencies: HashSet::default(),
             loader_dependencies: HashMap::default(),
             dependents_waiting_on_load: HashSet::default(),
             dependents_waiting_on_recursive_dep_load: HashSet::default(),
@@ -91,6 +99,8 @@ pub(crate) struct AssetInfos {
     /// This should only be set when watching for changes to avoid unnecessary work.
     pub(crate) living_labeled_assets: HashMap<AssetPath<'static>, HashSet<Box<str>>>,
     pub(crate) handle_providers: TypeIdMap<AssetHandleProvider>,
+    /// The [`TypePath`](bevy_reflect::TypePath) of each registered asset type.
+    pub(crate) asset_type_paths: TypeIdMap<&'static str>,
     pub(crate) dependency_loaded_event_sender: TypeIdMap<fn(&mut World, AssetIndex)>,
     pub(crate) dependency_failed_event_sender:
         TypeIdMap<fn(&mut World, AssetIndex, AssetPath<'static>, AssetLoadError)>,
@@ -298,6 +308,79 @@ impl AssetInfos {
         self.infos.contains_key(&index)
     }
 
+    /// Returns the direct dependencies of the asset at `index` that are still tracked.
+    pub(crate) fn get_dependencies(
+        &self,
+        index: ErasedAssetIndex,
+    ) -> Option<impl Iterator<Item = ErasedAssetIndex> + '_> {
+        let info = self.infos.get(&index)?;
+        Some(
+            info.dependencies
+                .iter()
+                .copied()
+                .filter(|dependency| self.infos.contains_key(dependency)),
+        )
+    }
+
+    /// Returns the assets that directly depend on the asset at `index`.
+    pub(crate) fn get_dependents(
+        &self,
+        index: ErasedAssetIndex,
+    ) -> Option<impl Iterator<Item = ErasedAssetIndex> + '_> {
+        if !self.infos.contains_key(&index) {
+            return None;
+        }
+        Some(
+            self.infos
+                .iter()
+                .filter(move |(_, info)| info.dependencies.contains(&index))
+                .map(|(dependent, _)| *dependent),
+        )
+    }
+
+    /// Builds an [`AssetDependencyGraph`] of all tracked assets.
+    pub(crate) fn dependency_graph(&self) -> AssetDependencyGraph {
+        // Sort the assets by path, then by id, so that the graph doesn't depend on the order of
+        // the hash map.
+        let mut infos = self
+            .infos
+            .iter()
+            .map(|(index, info)| {
+                let id = UntypedAssetId::from(*index);
+                (info.path.as_ref().map(ToString::to_string), id, index, info)
+            })
+            .collect::<Vec<_>>();
+        infos.sort_unstable_by(|a, b| (&a.0, a.1).cmp(&(&b.0, b.1)));
+
+        let mut node_indices = HashMap::<ErasedAssetIndex, usize>::default();
+        let mut nodes = Vec::with_capacity(infos.len());
+        for (_, id, index, info) in &infos {
+            node_indices.insert(**index, nodes.len());
+            nodes.push(AssetGraphNode {
+                id: *id,
+                type_path: self.asset_type_paths.get(&index.type_id).copied(),
+                path: info.path.clone(),
+                load_state: info.load_state.clone(),
+                strong_handles: info.weak_handle.strong_count(),
+                loader_dependencies: info.loader_dependencies.keys().cloned().collect(),
+            });
+        }
+        let mut edges = Vec::new();
+        for (_, _, index, info) in &infos {
+            let dependent = node_indices[*index];
+            for dependency in &info.dependencies {
+                if let Some(&dependency) = node_indices.get(dependency) {
+                    edges.push(AssetGraphEdge {
+                        dependent,
+                        dependency,
+                    });
+                }
+            }
+        }
+        edges.sort_unstable_by_key(|edge| (edge.dependent, edge.dependency));
+        AssetDependencyGraph { nodes, edges }
+    }
+
     pub(crate) fn get_mut(&mut self, index: ErasedAssetIndex) -> Option<&mut AssetInfo> {
         self.infos.get_mut(&index)
     }
@@ -424,6 +507,7 @@ impl AssetInfos {
         }
 
         loaded_asset.value.insert(loaded_asset_index.index, world);
+        let dependencies = loaded_asset.dependencies.clone();
         let mut loading_deps = loaded_asset.dependencies;
         let mut failed_deps = <HashSet<_>>::default();
         let mut dep_error = None;
@@ -524,6 +608,7 @@ impl AssetInfos {
             info.failed_dependencies = failed_deps;
             info.loading_rec_dependencies = loading_rec_deps;
             info.failed_rec_dependencies = failed_rec_deps;
+            info.dependencies = dependencies;
             info.load_state = LoadState::Loaded;
             info.dep_load_state = dep_load_state;
             info.rec_dep_load_state = rec_dep_load_state.clone();
@@ -0,0 +1,297 @@
+use crate::ErasedAssetIndex;
+use alloc::{collections::BinaryHeap, sync::Arc, vec::Vec};
+use bevy_platform::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+use core::cmp::Reverse;
+
+/// The priority of an asset load started with [`LoadBuilder::with_priority`](super::LoadBuilder::with_priority).
+///
+/// When [`AssetServer::set_max_concurrent_loads`](super::AssetServer::set_max_concurrent_loads)
+/// limits the number of loads that can run at once, queued loads with a higher priority start
+/// first. Loads with equal priorities start in the order they were requested.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LoadPriority(pub i32);
+
+impl LoadPriority {
+    /// A priority for loads that should yield to everything else, such as prefetching.
+    pub const LOW: Self = Self(-100);
+    /// The priority used by [`AssetServer::load`](super::AssetServer::load) and by loads that
+    /// don't set a priority.
+    pub const NORMAL: Self = Self(0);
+    /// A priority for loads that are needed as soon as possible.
+    pub const HIGH: Self = Self(100);
+}
+
+/// Hands out "load slots" to asset loads, limiting how many of them read from their sources at
+/// once. Loads that can't get a slot wait in a queue ordered by [`LoadPriority`].
+#[derive(Default)]
+pub(crate) struct LoadQueue {
+    state: Mutex<LoadQueueState>,
+}
+
+#[derive(Default)]
+struct LoadQueueState {
+    /// The maximum number of slots that can be held at once. `None` means unlimited.
+    max_concurrent_loads: Option<usize>,
+    /// The number of slots currently held, including slots granted to loads that haven't woken
+    /// up yet.
+    active: usize,
+    /// The ticket of the next load. This also orders queued loads with equal priorities.
+    next_ticket: u64,
+    /// The queued and running loads. Whoever removes a load from here is responsible for
+    /// releasing its slot, if it holds one.
+    loads: HashMap<u64, QueuedLoad>,
+    /// The tickets of queued loads, ordered by priority and then by ticket. Entries are not
+    /// removed when a load is granted, cancelled or reprioritized, so they are checked against
+    /// [`Self::loads`] when popped.
+    queue: BinaryHeap<(LoadPriority, Reverse<u64>)>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LoadStatus {
+    /// The load is waiting for a slot.
+    Queued,
+    /// The load has been given a slot, but hasn't woken up to take it yet.
+    Granted,
+    /// The load holds a slot and is running.
+    Running,
+}
+
+struct QueuedLoad {
+    index: ErasedAssetIndex,
+    priority: LoadPriority,
+    status: LoadStatus,
+    /// Wakes the load up once it has been granted a slot. Dropping this means the load was
+    /// cancelled.
+    sender: async_channel::Sender<()>,
+}
+
+impl LoadQueueState {
+    fn has_capacity(&self) -> bool {
+        self.max_concurrent_loads
+            .is_none_or(|max_concurrent_loads| self.active < max_concurrent_loads)
+    }
+
+    /// Grants free slots to the highest priority queued loads.
+    fn grant(&mut self) {
+        while self.has_capacity() {
+            let Some((priority, Reverse(ticket))) = self.queue.pop() else {
+                return;
+            };
+            // Skip entries for loads that are gone, no longer queued, or have been reprioritized.
+            let Some(load) = self
+                .loads
+                .get_mut(&ticket)
+                .filter(|load| load.status == LoadStatus::Queued && load.priority == priority)
+            else {
+                continue;
+            };
+            load.status = LoadStatus::Granted;
+            // If the receiver is gone, the slot is being dropped and will release the grant.
+            let _ = load.sender.try_send(());
+            self.active += 1;
+        }
+    }
+
+    /// Removes the load with the given `ticket`, releasing its slot if it holds one.
+    fn remove(&mut self, ticket: u64) {
+        if let Some(load) = self.loads.remove(&ticket)
+            && load.status != LoadStatus::Queued
+        {
+            self.active -= 1;
+            self.grant();
+        }
+    }
+}
+
+impl LoadQueue {
+    fn lock(&self) -> MutexGuard<'_, LoadQueueState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn max_concurrent_loads(&self) -> Option<usize> {
+        self.lock().max_concurrent_loads
+    }
+
+    pub(crate) fn set_max_concurrent_loads(&self, max_concurrent_loads: Option<usize>) {
+        let mut state = self.lock();
+        state.max_concurrent_loads = max_concurrent_loads;
+        state.grant();
+    }
+
+    /// Waits for a slot for the load of the asset at `index`. Returns `None` if the load was
+    /// cancelled while it was queued. The slot is released when the returned [`LoadSlot`] is
+    /// dropped.
+    pub(crate) async fn acquire(
+        self: Arc<Self>,
+        index: ErasedAssetIndex,
+        priority: LoadPriority,
+    ) -> Option<LoadSlot> {
+        let (sender, receiver) = async_channel::bounded(1);
+        let (ticket, status) = {
+            let mut state = self.lock();
+            let ticket = state.next_ticket;
+            state.next_ticket += 1;
+            let status = if state.has_capacity() {
+                state.active += 1;
+                LoadStatus::Running
+            } else {
+                LoadStatus::Queued
+            };
+            state.loads.insert(
+                ticket,
+                QueuedLoad {
+                    index,
+                    priority,
+                    status,
+                    sender,
+                },
+            );
+            if status == LoadStatus::Queued {
+                state.queue.push((priority, Reverse(ticket)));
+            }
+            (ticket, status)
+        };
+        // Dropping the slot (e.g. because the load task was dropped) gives up our place in the
+        // queue.
+        let slot = LoadSlot {
+            queue: self,
+            ticket,
+            receiver,
+        };
+        if status == LoadStatus::Running {
+            return Some(slot);
+        }
+
+        // This either receives the grant, or fails because the load was cancelled.
+        let _ = slot.receiver.recv().await;
+        let mut state = slot.queue.lock();
+        let load = state.loads.get_mut(&ticket)?;
+        load.status = LoadStatus::Running;
+        drop(state);
+        Some(slot)
+    }
+
+    /// Changes the priority of the queued loads for the asset at `index`. Returns `false` if the
+    /// asset has no queued loads.
+    pub(crate) fn set_priority(&self, index: ErasedAssetIndex, priority: LoadPriority) -> bool {
+        let mut state = self.lock();
+        let state = &mut *state;
+        let mut found = false;
+        for (ticket, load) in &mut state.loads {
+            if load.index == index && load.status == LoadStatus::Queued {
+                // The old entry in the queue is skipped once its priority no longer matches.
+                if load.priority != priority {
+                    load.priority = priority;
+                    state.queue.push((priority, Reverse(*ticket)));
+                }
+                found = true;
+            }
+        }
+        found
+    }
+
+    /// Cancels all queued and running loads for the asset at `index`, releasing their slots.
+    /// Returns `false` if the asset has no queued or running loads.
+    pub(crate) fn cancel(&self, index: ErasedAssetIndex) -> bool {
+        let mut state = self.lock();
+        let tickets = state
+            .loads
+            .iter()
+            .filter(|(_, load)| load.index == index)
+            .map(|(ticket, _)| *ticket)
+            .collect::<Vec<_>>();
+        for ticket in &tickets {
+            state.remove(*ticket);
+        }
+        !tickets.is_empty()
+    }
+}
+
+/// A slot held by a load, or its place in the queue while it waits for one. This is released when
+/// dropped.
+pub(crate) struct LoadSlot {
+    queue: Arc<LoadQueue>,
+    ticket: u64,
+    receiver: async_channel::Receiver<()>,
+}
+
+impl LoadSlot {
+    /// Completes once the load holding this slot has been cancelled.
+    pub(crate) async fn cancelled(&self) {
+        // The grant has already been received, so this only returns once the sender is dropped.
+        let _ = self.receiver.recv().await;
+    }
+}
+
+impl Drop for LoadSlot {
+    fn drop(&mut self) {
+        self.queue.lock().remove(self.ticket);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AssetIndex;
+    use alloc::boxed::Box;
+    use bevy_tasks::block_on;
+    use core::any::TypeId;
+    use futures_lite::future::poll_once;
+
+    fn index(index: u32) -> ErasedAssetIndex {
+        ErasedAssetIndex {
+            index: AssetIndex {
+                generation: 0,
+                index,
+            },
+            type_id: TypeId::of::<()>(),
+        }
+    }
+
+    #[test]
+    fn grants_slots_by_priority() {
+        let queue = Arc::new(LoadQueue::default());
+        queue.set_max_concurrent_loads(Some(1));
+
+        let running = block_on(queue.clone().acquire(index(0), LoadPriority::NORMAL)).unwrap();
+        let mut low = Box::pin(queue.clone().acquire(index(1), LoadPriority::LOW));
+        let mut normal = Box::pin(queue.clone().acquire(index(2), LoadPriority::NORMAL));
+        let mut bumped = Box::pin(queue.clone().acquire(index(3), LoadPriority::LOW));
+        let mut cancelled = Box::pin(queue.clone().acquire(index(4), LoadPriority::HIGH));
+        assert!(block_on(poll_once(&mut low)).is_none());
+        assert!(block_on(poll_once(&mut normal)).is_none());
+        assert!(block_on(poll_once(&mut bumped)).is_none());
+        assert!(block_on(poll_once(&mut cancelled)).is_none());
+
+        // Only queued loads can be reprioritized.
+        assert!(queue.set_priority(index(3), LoadPriority::HIGH));
+        assert!(!queue.set_priority(index(0), LoadPriority::HIGH));
+
+        assert!(queue.cancel(index(4)));
+        assert!(block_on(&mut cancelled).is_none());
+
+        drop(running);
+        assert!(block_on(poll_once(&mut low)).is_none());
+        assert!(block_on(poll_once(&mut normal)).is_none());
+        let slot = block_on(&mut bumped).unwrap();
+
+        drop(slot);
+        assert!(block_on(poll_once(&mut low)).is_none());
+        let slot = block_on(&mut normal).unwrap();
+
+        drop(slot);
+        let slot = block_on(&mut low).unwrap();
+        assert_eq!(queue.lock().active, 1);
+
+        // Cancelling a running load releases its slot.
+        assert!(queue.cancel(index(1)));
+        assert!(!queue.cancel(index(1)));
+        block_on(slot.cancelled());
+        assert_eq!(queue.lock().active, 0);
+        drop(slot);
+        assert_eq!(queue.lock().active, 0);
+    }
+}
@@ -1,4 +1,5 @@
 mod info;
+mod load_queue;
 mod loaders;
 
 use crate::{
@@ -14,10 +15,10 @@ use crate::{
         MetaTransform, Settings,
     },
     path::AssetPath,
-    Asset, AssetEvent, AssetHandleProvider, AssetId, AssetIndex, AssetLoadFailedEvent,
-    AssetMetaCheck, Assets, DeserializeMetaError, ErasedAssetIndex, ErasedLoadedAsset, Handle,
-    LoadedUntypedAsset, UnapprovedPathMode, UntypedAssetId, UntypedAssetLoadFailedEvent,
-    UntypedHandle, VisitAssetDependencies,
+    Asset, AssetDependencyGraph, AssetEvent, AssetHandleProvider, AssetId, AssetIndex,
+    AssetLoadFailedEvent, AssetMetaCheck, Assets, DeserializeMetaError, ErasedAssetIndex,
+    ErasedLoadedAsset, Handle, LoadedUntypedAsset, UnapprovedPathMode, UntypedAssetId,
+    UntypedAssetLoadFailedEvent, UntypedHandle, VisitAssetDependencies,
 };
 use alloc::{borrow::ToOwned, boxed::Box, vec, vec::Vec};
 use alloc::{
@@ -42,11 +43,14 @@ use core::{
 use crossbeam_channel::{Receiver, Sender};
 use futures_lite::{FutureExt, StreamExt};
 use info::*;
+use load_queue::*;
 use loaders::*;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 use tracing::{error, info, warn};
 
+pub use load_queue::LoadPriority;
+
 /// Loads and tracks the state of [`Asset`] values from a configured [`AssetReader`](crate::io::AssetReader).
 /// This can be used to kick off new asset loads and retrieve their current load states.
 ///
@@ -77,6 +81,7 @@ pub(crate) struct AssetServerData {
     mode: AssetServerMode,
     meta_check: AssetMetaCheck,
     unapproved_path_mode: UnapprovedPathMode,
+    load_queue: Arc<LoadQueue>,
 }
 
 /// The "asset mode" the server is currently in.
@@ -150,6 +155,7 @@ impl AssetServer {
                 loaders,
                 infos: RwLock::new(infos),
                 unapproved_path_mode,
+                load_queue: Default::default(),
             }),
         }
     }
@@ -232,6 +238,10 @@ impl AssetServer {
         infos
             .dependency_failed_event_sender
             .insert(TypeId::of::<A>(), failed_sender::<A>);
+
+        infos
+            .asset_type_paths
+            .insert(TypeId::of::<A>(), A::type_path());
     }
 
     pub(crate) fn register_handle_provider(&self, handle_provider: AssetHandleProvider) {
@@ -372,6 +382,59 @@ impl AssetServer {
         LoadBuilder::new(self)
     }
 
+    /// Returns the maximum number of loads that can read from their sources at the same time, or
+    /// `None` if it is unlimited. See [`AssetServer::set_max_concurrent_loads`].
+    pub fn max_concurrent_loads(&self) -> Option<usize> {
+        self.data.load_queue.max_concurrent_loads()
+    }
+
+    /// Limits the number of loads started by [`AssetServer::load`] or a [`LoadBuilder`] that can
+    /// run at the same time. Loads beyond this limit are queued, and start in order of their
+    /// [`LoadPriority`] as running loads finish. `None` (the default) means unlimited.
+    ///
+    /// Loads that were already started keep running if the limit is lowered.
+    pub fn set_max_concurrent_loads(&self, max_concurrent_loads: Option<usize>) {
+        self.data
+            .load_queue
+            .set_max_concurrent_loads(max_concurrent_loads);
+    }
+
+    /// Changes the [`LoadPriority`] of the queued load for the asset with the given `id`. Returns
+    /// `false` if the asset has no queued load, for example because it has already started loading.
+    ///
+    /// See [`LoadBuilder::with_priority`] for details.
+    pub fn set_load_priority(&self, id: impl Into<UntypedAssetId>, priority: LoadPriority) -> bool {
+        let Ok(index) = id.into().try_into() else {
+            return false;
+        };
+        self.data.load_queue.set_priority(index, priority)
+    }
+
+    /// Cancels the load of the asset with the given `id`, whether it is still queued or already
+    /// reading from its source. This frees its load slot for the next queued load (see
+    /// [`AssetServer::set_max_concurrent_loads`]), and sets its [`LoadState`] back to
+    /// [`LoadState::NotLoaded`], so loading the asset again will start a new load. Returns `false`
+    /// if the asset has no load started by [`AssetServer::load`] or a [`LoadBuilder`] in progress.
+    ///
+    /// Handles to the asset remain valid. Assets that depend on it won't finish loading their
+    /// dependencies until it is loaded again. A load that has already finished reading may still
+    /// be applied by the next run of [`handle_internal_asset_events`].
+    pub fn cancel_load(&self, id: impl Into<UntypedAssetId>) -> bool {
+        let Ok(index) = id.into().try_into() else {
+            return false;
+        };
+        let mut infos = self.write_infos();
+        if !self.data.load_queue.cancel(index) {
+            return false;
+        }
+        if let Some(info) = infos.get_mut(index) {
+            info.load_state = LoadState::NotLoaded;
+            info.dep_load_state = DependencyLoadState::NotLoaded;
+            info.rec_dep_load_state = RecursiveDependencyLoadState::NotLoaded;
+        }
+        true
+    }
+
     /// Same as [`load`](AssetServer::load), but you can load assets from unapproved paths
     /// if [`AssetPlugin::unapproved_path_mode`](super::AssetPlugin::unapproved_path_mode)
     /// is [`Deny`](UnapprovedPathMode::Deny).
@@ -534,6 +597,7 @@ impl AssetServer {
         meta_transform: Option<MetaTransform>,
         guard: G,
         override_unapproved: bool,
+        priority: LoadPriority,
     ) -> UntypedHandle {
         let path = path.into().into_owned();
         if path.path() == Path::new("") {
@@ -564,7 +628,7 @@ impl AssetServer {
         );
 
         if should_load {
-            self.spawn_load_task(handle.clone(), path, infos, guard);
+            self.spawn_load_task(handle.clone(), path, infos, guard, priority);
         }
 
         handle
@@ -576,6 +640,7 @@ impl AssetServer {
         path: AssetPath<'static>,
         mut infos: RwLockWriteGuard<AssetInfos>,
         guard: G,
+        priority: LoadPriority,
     ) {
         infos.stats.started_load_tasks += 1;
 
@@ -583,24 +648,33 @@ impl AssetServer {
         #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
         drop(infos);
 
+        // `spawn_load_task` is only called with Strong handles, so this is safe.
+        let index = (&handle).try_into().unwrap();
         let owned_handle = handle.clone();
         let server = self.clone();
         let task = IoTaskPool::get().spawn(async move {
-            if let Err(err) = server
-                .load_internal(Some(owned_handle), path, false, None)
-                .await
-            {
-                error!("{}", err);
-            }
+            let load_queue = server.data.load_queue.clone();
+            let Some(slot) = load_queue.acquire(index, priority).await else {
+                // The load was cancelled before it started.
+                return;
+            };
+            let load = async {
+                if let Err(err) = server
+                    .load_internal(Some(owned_handle), path, false, None, priority)
+                    .await
+                {
+                    error!("{}", err);
+                }
+            };
+            // Stop loading if the load is cancelled.
+            slot.cancelled().or(load).await;
             drop(guard);
         });
 
         #[cfg(not(any(target_arch = "wasm32", not(feature = "multi_threaded"))))]
         {
             let mut infos = infos;
-            infos
-                .pending_tasks
-                .insert((&handle).try_into().unwrap(), task);
+            infos.pending_tasks.insert(index, task);
         }
 
         #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
@@ -625,6 +699,7 @@ impl AssetServer {
         meta_transform: Option<MetaTransform>,
         guard: G,
         override_unapproved: bool,
+        priority: LoadPriority,
     ) -> Handle<LoadedUntypedAsset> {
         let path = path.into().into_owned();
         if path.path() == Path::new("") {
@@ -668,27 +743,38 @@ impl AssetServer {
 
         let server = self.clone();
         let task = IoTaskPool::get().spawn(async move {
-            let path_clone = path.clone();
-            match server
-                .load_internal(None, path, false, None)
-                .await
-                .map(|h| {
-                    h.expect("handle must be returned, since we didn't pass in an input handle")
-                }) {
-                Ok(handle) => server.send_asset_event(InternalAssetEvent::Loaded {
-                    index,
-                    loaded_asset: LoadedAsset::new_with_dependencies(LoadedUntypedAsset { handle })
-                        .into(),
-                }),
-                Err(err) => {
-                    error!("{err}");
-                    server.send_asset_event(InternalAssetEvent::Failed {
+            let load_queue = server.data.load_queue.clone();
+            let Some(slot) = load_queue.acquire(index, priority).await else {
+                // The load was cancelled before it started.
+                return;
+            };
+            let load = async {
+                let path_clone = path.clone();
+                match server
+                    .load_internal(None, path, false, None, priority)
+                    .await
+                    .map(|h| {
+                        h.expect("handle must be returned, since we didn't pass in an input handle")
+                    }) {
+                    Ok(handle) => server.send_asset_event(InternalAssetEvent::Loaded {
                         index,
-                        path: path_clone,
-                        error: err,
-                    });
-                }
+                        loaded_asset: LoadedAsset::new_with_dependencies(LoadedUntypedAsset {
+                            handle,
+                        })
+                        .into(),
+                    }),
+                    Err(err) => {
+                        error!("{err}");
+                        server.send_asset_event(InternalAssetEvent::Failed {
+                            index,
+                            path: path_clone,
+                            error: err,
+                        });
+                    }
+                };
             };
+            // Stop loading if the load is cancelled.
+            slot.cancelled().or(load).await;
             drop(guard);
         });
 
@@ -744,6 +830,7 @@ impl AssetServer {
         path: AssetPath<'a>,
         force: bool,
         meta_transform: Option<MetaTransform>,
+        priority: LoadPriority,
     ) -> Result<Option<UntypedHandle>, AssetLoadError> {
         let input_handle_type_id = input_handle.as_ref().map(UntypedHandle::type_id);
 
@@ -876,6 +963,7 @@ impl AssetServer {
                 &mut *reader,
                 true,
                 false,
+                priority,
             )
             .await
         {
@@ -971,7 +1059,15 @@ impl AssetServer {
                 let requests = server
                     .read_infos()
                     .get_path_handles(&path)
-                    .map(|handle| server.load_internal(Some(handle), path.clone(), true, None))
+                    .map(|handle| {
+                        server.load_internal(
+                            Some(handle),
+                            path.clone(),
+                            true,
+                            None,
+                            LoadPriority::NORMAL,
+                        )
+                    })
                     .collect::<Vec<_>>();
 
                 for result in requests {
@@ -993,7 +1089,10 @@ impl AssetServer {
                 // map from asset index to loader).
                 if !reloaded && server.read_infos().should_reload(&path) {
                     server.write_infos().stats.started_load_tasks += 1;
-                    match server.load_internal(None, path.clone(), true, None).await {
+                    match server
+                        .load_internal(None, path.clone(), true, None, LoadPriority::NORMAL)
+                        .await
+                    {
                         Ok(_) => reloaded = true,
                         Err(err) => error!("{}", err),
                     }
@@ -1290,6 +1389,46 @@ impl AssetServer {
             .map(|i| i.rec_dep_load_state.clone())
     }
 
+    /// Retrieves the direct dependencies of a given asset `id`, as reported by its loader the last
+    /// time it was loaded. Returns `None` if the asset is not tracked by the [`AssetServer`].
+    ///
+    /// See [`AssetServer::dependency_graph`] to inspect recursive dependencies.
+    pub fn get_dependencies(&self, id: impl Into<UntypedAssetId>) -> Option<Vec<UntypedAssetId>> {
+        let Ok(index) = id.into().try_into() else {
+            // Always say we don't have Uuid assets.
+            return None;
+        };
+        Some(
+            self.read_infos()
+                .get_dependencies(index)?
+                .map(Into::into)
+                .collect(),
+        )
+    }
+
+    /// Retrieves the assets that directly depend on a given asset `id`. Returns `None` if the asset
+    /// is not tracked by the [`AssetServer`].
+    ///
+    /// Each dependent holds a strong handle to the asset, keeping it alive.
+    pub fn get_dependents(&self, id: impl Into<UntypedAssetId>) -> Option<Vec<UntypedAssetId>> {
+        let Ok(index) = id.into().try_into() else {
+            // Always say we don't have Uuid assets.
+            return None;
+        };
+        Some(
+            self.read_infos()
+                .get_dependents(index)?
+                .map(Into::into)
+                .collect(),
+        )
+    }
+
+    /// Returns a snapshot of every asset tracked by the [`AssetServer`] and the dependencies
+    /// between them.
+    pub fn dependency_graph(&self) -> AssetDependencyGraph {
+        self.read_infos().dependency_graph()
+    }
+
     /// Retrieves the main [`LoadState`] of a given asset `id`.
     ///
     /// This is the same as [`AssetServer::get_load_state`] except the result is unwrapped. If
@@ -1660,11 +1799,17 @@ impl AssetServer {
         reader: &mut dyn Reader,
         load_dependencies: bool,
         populate_hashes: bool,
+        priority: LoadPriority,
     ) -> Result<ErasedLoadedAsset, AssetLoadError> {
         // TODO: experiment with this
         let asset_path = asset_path.clone_owned();
-        let load_context =
-            LoadContext::new(self, asset_path.clone(), load_dependencies, populate_hashes);
+        let load_context = LoadContext::new(
+            self,
+            asset_path.clone(),
+            load_dependencies,
+            populate_hashes,
+            priority,
+        );
         let load = AssertUnwindSafe(loader.load(reader, settings, load_context)).catch_unwind();
         #[cfg(feature = "trace")]
         let load = {
@@ -1886,6 +2031,8 @@ pub struct LoadBuilder<'a> {
     override_unapproved: bool,
     /// A "guard" that is held until the load has fully completed.
     guard: Option<Box<dyn Send + Sync + 'static>>,
+    /// The priority of the load relative to other queued loads.
+    priority: LoadPriority,
 }
 
 impl<'a> LoadBuilder<'a> {
@@ -1897,6 +2044,7 @@ impl<'a> LoadBuilder<'a> {
             meta_transform: None,
             override_unapproved: false,
             guard: None,
+            priority: LoadPriority::NORMAL,
         }
     }
 
@@ -1951,6 +2099,21 @@ impl<'a> LoadBuilder<'a> {
         self
     }
 
+    /// Sets the [`LoadPriority`] of the load. Defaults to [`LoadPriority::NORMAL`].
+    ///
+    /// This only has an effect when the number of concurrent loads is limited with
+    /// [`AssetServer::set_max_concurrent_loads`], in which case queued loads with a higher priority
+    /// start first. The priority of a queued load can be changed later with
+    /// [`AssetServer::set_load_priority`]. Dependencies started by the asset's loader inherit
+    /// this priority.
+    ///
+    /// If the asset is already loading, this doesn't change the priority of the existing load.
+    #[must_use = "the load doesn't start until LoadBuilder has been consumed"]
+    pub fn with_priority(mut self, priority: LoadPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
     /// Begins loading an [`Asset`] of type `A` stored at `path`. This will not block on the asset load. Instead,
     /// it returns a "strong" [`Handle`]. When the [`Asset`] is loaded (and enters [`LoadState::Loaded`]), it will be added to the
     /// associated [`Assets`] resource.
@@ -2010,6 +2173,7 @@ impl<'a> LoadBuilder<'a> {
             self.meta_transform,
             self.guard,
             self.override_unapproved,
+            self.priority,
         )
     }
 
@@ -2034,7 +2198,7 @@ impl<'a> LoadBuilder<'a> {
         self.asset_server.write_infos().stats.started_load_tasks += 1;
 
         self.asset_server
-            .load_internal(None, path, false, None)
+            .load_internal(None, path, false, None, self.priority)
             .await
             .map(|h| h.expect("handle must be returned, since we didn't pass in an input handle"))
     }
@@ -2054,6 +2218,7 @@ impl<'a> LoadBuilder<'a> {
             self.meta_transform,
             self.guard,
             self.override_unapproved,
+            self.priority,
         )
     }
 }
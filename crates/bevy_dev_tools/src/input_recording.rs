@@ -0,0 +1,802 @@
+//! Recording of raw input messages and their deterministic playback.
+//!
+//! [`InputRecordingPlugin`] records every raw input message of the app with the frame it was
+//! received on, and writes them to a [`ron`] file when the app exits. In a later run, the same
+//! plugin in playback mode writes the recorded messages back on the same frames, while ignoring
+//! the live input. Both runs step time by a fixed amount with
+//! [`TimeUpdateStrategy::ManualDuration`], so that systems see the same input at the same time.
+//!
+//! The cursor messages of the windows are recorded along with the input messages. During playback,
+//! the cursor position of each window is also set to its recorded position, so that clicks land
+//! where they were recorded regardless of where the live cursor is.
+//!
+//! This makes it possible to reproduce bugs found while playing, and to write regression tests
+//! driven by recorded input.
+
+use std::{fs, path::PathBuf};
+
+use bevy_app::{App, AppExit, Last, Plugin, PreUpdate};
+use bevy_ecs::{
+    entity::{Entity, EntityHashMap},
+    error::{BevyError, ResultSeverityExt, Severity},
+    message::{MessageReader, MessageWriter, Messages},
+    query::With,
+    resource::Resource,
+    schedule::IntoScheduleConfigs,
+    system::{Commands, Query, Res, ResMut, SystemParam},
+};
+use bevy_input::{
+    gamepad::{
+        GamepadConnectionEvent, RawGamepadAxisChangedEvent, RawGamepadButtonChangedEvent,
+        RawGamepadEvent,
+    },
+    gestures::{DoubleTapGesture, PanGesture, PinchGesture, RotationGesture},
+    keyboard::{KeyboardFocusLost, KeyboardInput},
+    mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    touch::TouchInput,
+    InputSourceSystems, InputSystems,
+};
+use bevy_math::Vec2;
+use bevy_time::TimeUpdateStrategy;
+use bevy_window::{CursorEntered, CursorLeft, CursorMoved, PrimaryWindow, Window};
+use core::time::Duration;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+/// The frame time used for playback when neither the plugin nor the recording specify one.
+pub const DEFAULT_FRAME_TIME: Duration = Duration::from_nanos(16_666_667);
+
+/// A plugin which records the raw input of the app to a file, or plays it back from one.
+///
+/// Requires [`InputPlugin`](bevy_input::InputPlugin). See the [module docs](self) for details.
+pub struct InputRecordingPlugin {
+    /// Whether to record or play back input.
+    pub mode: InputRecordingMode,
+    /// The fixed time step to advance time by each frame.
+    ///
+    /// When recording, time advances normally if this is `None`. When playing back, the frame
+    /// time of the recording is used if this is `None`, and [`DEFAULT_FRAME_TIME`] if the
+    /// recording doesn't have one either.
+    pub frame_time: Option<Duration>,
+    /// When playing back, whether to exit the app once all recorded frames have been played.
+    pub exit_when_finished: bool,
+}
+
+/// What an [`InputRecordingPlugin`] does.
+pub enum InputRecordingMode {
+    /// Record input, and write it to the given path when the app exits.
+    Record(PathBuf),
+    /// Play back the input recorded in the file at the given path.
+    PlaybackFile(PathBuf),
+    /// Play back an input recording.
+    Playback(InputRecording),
+}
+
+impl InputRecordingPlugin {
+    /// Records input to the file at `path`.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self {
+            mode: InputRecordingMode::Record(path.into()),
+            frame_time: None,
+            exit_when_finished: false,
+        }
+    }
+
+    /// Plays back the input recorded in the file at `path`.
+    pub fn playback(path: impl Into<PathBuf>) -> Self {
+        Self {
+            mode: InputRecordingMode::PlaybackFile(path.into()),
+            frame_time: None,
+            exit_when_finished: false,
+        }
+    }
+
+    /// Plays back an input recording, for example one built by a test.
+    pub fn playback_recording(recording: InputRecording) -> Self {
+        Self {
+            mode: InputRecordingMode::Playback(recording),
+            frame_time: None,
+            exit_when_finished: false,
+        }
+    }
+
+    /// Sets the fixed time step to advance time by each frame.
+    pub fn with_frame_time(mut self, frame_time: Duration) -> Self {
+        self.frame_time = Some(frame_time);
+        self
+    }
+
+    /// Exits the app once all recorded frames have been played back.
+    pub fn exit_when_finished(mut self) -> Self {
+        self.exit_when_finished = true;
+        self
+    }
+}
+
+impl Plugin for InputRecordingPlugin {
+    fn build(&self, app: &mut App) {
+        // The cursor messages are registered by the `WindowPlugin`, which headless apps may not
+        // have.
+        app.add_message::<CursorMoved>()
+            .add_message::<CursorEntered>()
+            .add_message::<CursorLeft>();
+
+        let recording = match &self.mode {
+            InputRecordingMode::Record(path) => {
+                if let Some(frame_time) = self.frame_time {
+                    app.insert_resource(TimeUpdateStrategy::ManualDuration(frame_time));
+                }
+                app.insert_resource(InputRecorder {
+                    recording: InputRecording {
+                        frame_time: self.frame_time,
+                        ..Default::default()
+                    },
+                    frame: 0,
+                    path: Some(path.clone()),
+                })
+                .add_systems(
+                    PreUpdate,
+                    record_inputs.after(InputSourceSystems).before(InputSystems),
+                )
+                .add_systems(Last, save_recording_on_exit);
+                return;
+            }
+            InputRecordingMode::PlaybackFile(path) => match fs::read_to_string(path) {
+                Ok(content) => {
+                    ron::from_str(&content).expect("error deserializing input recording file")
+                }
+                Err(e) => {
+                    error!("Could not read input recording {}: {e}", path.display());
+                    InputRecording::default()
+                }
+            },
+            InputRecordingMode::Playback(recording) => recording.clone(),
+        };
+
+        let frame_time = self
+            .frame_time
+            .or(recording.frame_time)
+            .unwrap_or(DEFAULT_FRAME_TIME);
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(frame_time))
+            .insert_resource(InputPlayback {
+                recording,
+                frame: 0,
+                next: 0,
+                gamepads: EntityHashMap::default(),
+                cursor_positions: EntityHashMap::default(),
+                exit_when_finished: self.exit_when_finished,
+            })
+            // Run after all input sources, so that the live input they write this frame is
+            // replaced by the recorded input.
+            .add_systems(
+                PreUpdate,
+                play_back_inputs
+                    .after(InputSourceSystems)
+                    .before(InputSystems),
+            );
+    }
+}
+
+/// Input messages recorded frame by frame.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct InputRecording {
+    /// The fixed time step time advanced by while recording, if any.
+    pub frame_time: Option<Duration>,
+    /// The number of frames recorded, including the frames without input.
+    pub length: u64,
+    /// The frames which received input, in order. Frames without input are omitted.
+    pub frames: Vec<RecordedFrame>,
+}
+
+/// The input messages received during a frame.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RecordedFrame {
+    /// The frame the messages were received on, counted from the start of the recording.
+    pub frame: u64,
+    /// The messages, in the order they were received for each kind of input.
+    pub inputs: Vec<RecordedInput>,
+}
+
+/// A raw input message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum RecordedInput {
+    /// A [`KeyboardInput`] message.
+    Keyboard(KeyboardInput),
+    /// A [`KeyboardFocusLost`] message.
+    KeyboardFocusLost,
+    /// A [`MouseButtonInput`] message.
+    MouseButton(MouseButtonInput),
+    /// A [`MouseMotion`] message.
+    MouseMotion(MouseMotion),
+    /// A [`MouseWheel`] message.
+    MouseWheel(MouseWheel),
+    /// A [`CursorMoved`] message.
+    CursorMoved(CursorMoved),
+    /// A [`CursorEntered`] message.
+    CursorEntered(CursorEntered),
+    /// A [`CursorLeft`] message.
+    CursorLeft(CursorLeft),
+    /// A [`RawGamepadEvent`] message.
+    Gamepad(RawGamepadEvent),
+    /// A [`TouchInput`] message.
+    Touch(TouchInput),
+    /// A [`PinchGesture`] message.
+    Pinch(PinchGesture),
+    /// A [`RotationGesture`] message.
+    Rotation(RotationGesture),
+    /// A [`DoubleTapGesture`] message.
+    DoubleTap,
+    /// A [`PanGesture`] message.
+    Pan(PanGesture),
+}
+
+/// The input recorded so far by an [`InputRecordingPlugin`] in record mode.
+#[derive(Resource)]
+pub struct InputRecorder {
+    recording: InputRecording,
+    frame: u64,
+    path: Option<PathBuf>,
+}
+
+impl InputRecorder {
+    /// The input recorded so far.
+    pub fn recording(&self) -> &InputRecording {
+        &self.recording
+    }
+
+    /// Writes the input recorded so far to the file at `path`.
+    pub fn save(&self, path: impl Into<PathBuf>) -> Result<(), BevyError> {
+        // Use \n unconditionally so that Windows formatting is predictable.
+        let serialized =
+            ron::ser::to_string_pretty(&self.recording, PrettyConfig::default().new_line("\n"))?;
+        fs::write(path.into(), serialized)?;
+        Ok(())
+    }
+}
+
+/// The state of the playback of an [`InputRecordingPlugin`] in playback mode.
+#[derive(Resource)]
+pub struct InputPlayback {
+    recording: InputRecording,
+    frame: u64,
+    /// The index of the next frame of the recording to play back.
+    next: usize,
+    /// Maps the gamepads of the recording to the gamepads spawned for the playback.
+    gamepads: EntityHashMap<Entity>,
+    /// The recorded cursor position of each window, kept in place of the live cursor position.
+    cursor_positions: EntityHashMap<Option<Vec2>>,
+    exit_when_finished: bool,
+}
+
+impl InputPlayback {
+    /// The number of frames played back so far.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Returns `true` once all recorded frames have been played back.
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.recording.frames.len() && self.frame >= self.recording.length
+    }
+}
+
+/// Readers for every kind of raw input message.
+#[derive(SystemParam)]
+struct InputReaders<'w, 's> {
+    keyboard: MessageReader<'w, 's, KeyboardInput>,
+    keyboard_focus_lost: MessageReader<'w, 's, KeyboardFocusLost>,
+    mouse_button: MessageReader<'w, 's, MouseButtonInput>,
+    mouse_motion: MessageReader<'w, 's, MouseMotion>,
+    mouse_wheel: MessageReader<'w, 's, MouseWheel>,
+    cursor_moved: MessageReader<'w, 's, CursorMoved>,
+    cursor_entered: MessageReader<'w, 's, CursorEntered>,
+    cursor_left: MessageReader<'w, 's, CursorLeft>,
+    gamepad: MessageReader<'w, 's, RawGamepadEvent>,
+    touch: MessageReader<'w, 's, TouchInput>,
+    pinch: MessageReader<'w, 's, PinchGesture>,
+    rotation: MessageReader<'w, 's, RotationGesture>,
+    double_tap: MessageReader<'w, 's, DoubleTapGesture>,
+    pan: MessageReader<'w, 's, PanGesture>,
+}
+
+fn record_inputs(mut recorder: ResMut<InputRecorder>, mut readers: InputReaders) {
+    let mut inputs = Vec::new();
+    // Cursor messages come first, so that the cursor is in place before the buttons are played
+    // back.
+    inputs.extend(
+        readers
+            .cursor_entered
+            .read()
+            .cloned()
+            .map(RecordedInput::CursorEntered),
+    );
+    inputs.extend(
+        readers
+            .cursor_moved
+            .read()
+            .cloned()
+            .map(RecordedInput::CursorMoved),
+    );
+    inputs.extend(
+        readers
+            .cursor_left
+            .read()
+            .cloned()
+            .map(RecordedInput::CursorLeft),
+    );
+    inputs.extend(
+        readers
+            .keyboard
+            .read()
+            .cloned()
+            .map(RecordedInput::Keyboard),
+    );
+    inputs.extend(
+        readers
+            .keyboard_focus_lost
+            .read()
+            .map(|_| RecordedInput::KeyboardFocusLost),
+    );
+    inputs.extend(
+        readers
+            .mouse_button
+            .read()
+            .copied()
+            .map(RecordedInput::MouseButton),
+    );
+    inputs.extend(
+        readers
+            .mouse_motion
+            .read()
+            .copied()
+            .map(RecordedInput::MouseMotion),
+    );
+    inputs.extend(
+        readers
+            .mouse_wheel
+            .read()
+            .copied()
+            .map(RecordedInput::MouseWheel),
+    );
+    inputs.extend(readers.gamepad.read().cloned().map(RecordedInput::Gamepad));
+    inputs.extend(readers.touch.read().copied().map(RecordedInput::Touch));
+    inputs.extend(readers.pinch.read().copied().map(RecordedInput::Pinch));
+    inputs.extend(
+        readers
+            .rotation
+            .read()
+            .copied()
+            .map(RecordedInput::Rotation),
+    );
+    inputs.extend(readers.double_tap.read().map(|_| RecordedInput::DoubleTap));
+    inputs.extend(readers.pan.read().copied().map(RecordedInput::Pan));
+
+    let frame = recorder.frame;
+    if !inputs.is_empty() {
+        recorder
+            .recording
+            .frames
+            .push(RecordedFrame { frame, inputs });
+    }
+    recorder.frame += 1;
+    recorder.recording.length = recorder.frame;
+}
+
+/// A system that writes the recording to its file once the app exits.
+fn save_recording_on_exit(
+    mut exit: MessageReader<AppExit>,
+    recorder: Res<InputRecorder>,
+) -> Result<(), BevyError> {
+    if exit.read().last().is_none() {
+        return Ok(());
+    }
+    let Some(path) = &recorder.path else {
+        return Ok(());
+    };
+    recorder
+        .save(path.clone())
+        .with_severity(Severity::Warning)?;
+    info!(
+        "Saved {} frames of input to {}",
+        recorder.recording.frames.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Writers for every kind of raw input message.
+///
+/// [`Messages`] are used directly so that the live input can be cleared.
+#[derive(SystemParam)]
+struct InputWriters<'w> {
+    keyboard: ResMut<'w, Messages<KeyboardInput>>,
+    keyboard_focus_lost: ResMut<'w, Messages<KeyboardFocusLost>>,
+    mouse_button: ResMut<'w, Messages<MouseButtonInput>>,
+    mouse_motion: ResMut<'w, Messages<MouseMotion>>,
+    mouse_wheel: ResMut<'w, Messages<MouseWheel>>,
+    cursor_moved: ResMut<'w, Messages<CursorMoved>>,
+    cursor_entered: ResMut<'w, Messages<CursorEntered>>,
+    cursor_left: ResMut<'w, Messages<CursorLeft>>,
+    gamepad: ResMut<'w, Messages<RawGamepadEvent>>,
+    gamepad_connection: ResMut<'w, Messages<GamepadConnectionEvent>>,
+    gamepad_button: ResMut<'w, Messages<RawGamepadButtonChangedEvent>>,
+    gamepad_axis: ResMut<'w, Messages<RawGamepadAxisChangedEvent>>,
+    touch: ResMut<'w, Messages<TouchInput>>,
+    pinch: ResMut<'w, Messages<PinchGesture>>,
+    rotation: ResMut<'w, Messages<RotationGesture>>,
+    double_tap: ResMut<'w, Messages<DoubleTapGesture>>,
+    pan: ResMut<'w, Messages<PanGesture>>,
+}
+
+impl InputWriters<'_> {
+    /// Drops the live input, so that only the recorded input is seen.
+    fn clear(&mut self) {
+        self.keyboard.clear();
+        self.keyboard_focus_lost.clear();
+        self.mouse_button.clear();
+        self.mouse_motion.clear();
+        self.mouse_wheel.clear();
+        self.cursor_moved.clear();
+        self.cursor_entered.clear();
+        self.cursor_left.clear();
+        self.gamepad.clear();
+        self.gamepad_connection.clear();
+        self.gamepad_button.clear();
+        self.gamepad_axis.clear();
+        self.touch.clear();
+        self.pinch.clear();
+        self.rotation.clear();
+        self.double_tap.clear();
+        self.pan.clear();
+    }
+}
+
+fn play_back_inputs(
+    mut commands: Commands,
+    mut playback: ResMut<InputPlayback>,
+    mut writers: InputWriters,
+    mut exit: MessageWriter<AppExit>,
+    mut windows: Query<&mut Window>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+) {
+    writers.clear();
+
+    let playback = &mut *playback;
+    if playback.is_finished() {
+        return;
+    }
+    // Undo the live cursor movement, until the recording moves the cursor again.
+    set_cursor_positions(&mut windows, &playback.cursor_positions);
+
+    let frame = playback.frame;
+    playback.frame += 1;
+    let recorded = playback
+        .recording
+        .frames
+        .get(playback.next)
+        .filter(|recorded| recorded.frame == frame)
+        .map(|recorded| recorded.inputs.clone());
+    if recorded.is_some() {
+        playback.next += 1;
+    }
+    if playback.is_finished() {
+        info!(
+            "Finished playing back {} frames of recorded input.",
+            playback.frame
+        );
+        if playback.exit_when_finished {
+            exit.write(AppExit::Success);
+        }
+    }
+    let Some(inputs) = recorded else {
+        return;
+    };
+
+    // Window entities are usually the same from one run to the next, but fall back to the
+    // primary window if they are not.
+    let window = |recorded: Entity| {
+        if windows.contains(recorded) {
+            recorded
+        } else {
+            primary_window.single().unwrap_or(recorded)
+        }
+    };
+    // Gamepad entities are spawned when gamepads connect, like the gamepad backend does.
+    let mut gamepad = |recorded: Entity| {
+        *playback
+            .gamepads
+            .entry(recorded)
+            .or_insert_with(|| commands.spawn_empty().id())
+    };
+
+    for input in inputs {
+        match input {
+            RecordedInput::Keyboard(mut input) => {
+                input.window = window(input.window);
+                writers.keyboard.write(input);
+            }
+            RecordedInput::KeyboardFocusLost => {
+                writers.keyboard_focus_lost.write(KeyboardFocusLost);
+            }
+            RecordedInput::MouseButton(mut input) => {
+                input.window = window(input.window);
+                writers.mouse_button.write(input);
+            }
+            RecordedInput::MouseMotion(input) => {
+                writers.mouse_motion.write(input);
+            }
+            RecordedInput::MouseWheel(mut input) => {
+                input.window = window(input.window);
+                writers.mouse_wheel.write(input);
+            }
+            RecordedInput::CursorMoved(mut input) => {
+                input.window = window(input.window);
+                playback
+                    .cursor_positions
+                    .insert(input.window, Some(input.position));
+                writers.cursor_moved.write(input);
+            }
+            RecordedInput::CursorEntered(mut input) => {
+                input.window = window(input.window);
+                writers.cursor_entered.write(input);
+            }
+            RecordedInput::CursorLeft(mut input) => {
+                input.window = window(input.window);
+                playback.cursor_positions.insert(input.window, None);
+                writers.cursor_left.write(input);
+            }
+            RecordedInput::Gamepad(mut input) => {
+                // The gamepad backend writes each raw event both as a `RawGamepadEvent` and as
+                // its own message.
+                match &mut input {
+                    RawGamepadEvent::Connection(event) => {
+                        event.gamepad = gamepad(event.gamepad);
+                        writers.gamepad_connection.write(event.clone());
+                    }
+                    RawGamepadEvent::Button(event) => {
+                        event.gamepad = gamepad(event.gamepad);
+                        writers.gamepad_button.write(*event);
+                    }
+                    RawGamepadEvent::Axis(event) => {
+                        event.gamepad = gamepad(event.gamepad);
+                        writers.gamepad_axis.write(*event);
+                    }
+                }
+                writers.gamepad.write(input);
+            }
+            RecordedInput::Touch(mut input) => {
+                input.window = window(input.window);
+                writers.touch.write(input);
+            }
+            RecordedInput::Pinch(input) => {
+                writers.pinch.write(input);
+            }
+            RecordedInput::Rotation(input) => {
+                writers.rotation.write(input);
+            }
+            RecordedInput::DoubleTap => {
+                writers.double_tap.write(DoubleTapGesture);
+            }
+            RecordedInput::Pan(input) => {
+                writers.pan.write(input);
+            }
+        }
+    }
+    set_cursor_positions(&mut windows, &playback.cursor_positions);
+}
+
+/// Moves the cursor of each window to its played back position.
+fn set_cursor_positions(
+    windows: &mut Query<&mut Window>,
+    cursor_positions: &EntityHashMap<Option<Vec2>>,
+) {
+    for (&entity, &position) in cursor_positions {
+        let Ok(mut window) = windows.get_mut(entity) else {
+            continue;
+        };
+        // Only write the position when it differs, to avoid triggering change detection.
+        if window.cursor_position() != position {
+            window.set_cursor_position(position);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{App, Update};
+    use bevy_ecs::system::Local;
+    use bevy_input::{
+        keyboard::{Key, KeyCode},
+        mouse::MouseButton,
+        ButtonInput, ButtonState, InputPlugin,
+    };
+
+    use super::*;
+
+    fn key(state: ButtonState) -> KeyboardInput {
+        KeyboardInput {
+            key_code: KeyCode::Space,
+            logical_key: Key::Space,
+            state,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct PressedOnFrames(Vec<u64>);
+
+    fn track_space(
+        input: Res<ButtonInput<KeyCode>>,
+        mut pressed: ResMut<PressedOnFrames>,
+        mut frame: Local<u64>,
+    ) {
+        if input.just_pressed(KeyCode::Space) {
+            pressed.0.push(*frame);
+        }
+        *frame += 1;
+    }
+
+    #[test]
+    fn records_and_plays_back_input() {
+        let mut app = App::new();
+        app.add_plugins((
+            InputPlugin,
+            InputRecordingPlugin::record("unused.ron").with_frame_time(Duration::from_millis(10)),
+        ));
+        app.update();
+        app.world_mut().write_message(key(ButtonState::Pressed));
+        app.update();
+        app.world_mut().write_message(key(ButtonState::Released));
+        app.update();
+        app.update();
+
+        let recording = app.world().resource::<InputRecorder>().recording().clone();
+        assert_eq!(recording.frame_time, Some(Duration::from_millis(10)));
+        assert_eq!(recording.length, 4);
+        assert_eq!(
+            recording.frames,
+            [
+                RecordedFrame {
+                    frame: 1,
+                    inputs: vec![RecordedInput::Keyboard(key(ButtonState::Pressed))],
+                },
+                RecordedFrame {
+                    frame: 2,
+                    inputs: vec![RecordedInput::Keyboard(key(ButtonState::Released))],
+                },
+            ]
+        );
+
+        // The recording survives a round trip through its file format.
+        let serialized = ron::to_string(&recording).unwrap();
+        let recording: InputRecording = ron::from_str(&serialized).unwrap();
+
+        let mut app = App::new();
+        app.add_plugins((
+            InputPlugin,
+            InputRecordingPlugin::playback_recording(recording).exit_when_finished(),
+        ))
+        .init_resource::<PressedOnFrames>()
+        .add_systems(Update, track_space);
+        // Live input is ignored during playback.
+        app.world_mut().write_message(key(ButtonState::Pressed));
+        for _ in 0..4 {
+            assert!(app.should_exit().is_none());
+            app.update();
+        }
+
+        assert_eq!(app.world().resource::<PressedOnFrames>().0, [1]);
+        assert!(app.world().resource::<InputPlayback>().is_finished());
+        assert!(app.should_exit().is_some());
+        assert!(matches!(
+            app.world().resource::<TimeUpdateStrategy>(),
+            TimeUpdateStrategy::ManualDuration(duration) if *duration == Duration::from_millis(10)
+        ));
+    }
+
+    #[derive(Resource, Default)]
+    struct ClickedAt(Vec<Option<Vec2>>);
+
+    fn track_clicks(
+        input: Res<ButtonInput<MouseButton>>,
+        windows: Query<&Window>,
+        mut clicked: ResMut<ClickedAt>,
+    ) {
+        if input.just_pressed(MouseButton::Left) {
+            clicked.0.push(windows.single().unwrap().cursor_position());
+        }
+    }
+
+    #[test]
+    fn plays_back_clicks_at_the_recorded_cursor_position() {
+        let click = |window| MouseButtonInput {
+            button: MouseButton::Left,
+            state: ButtonState::Pressed,
+            window,
+        };
+
+        let mut app = App::new();
+        app.add_plugins((InputPlugin, InputRecordingPlugin::record("unused.ron")));
+        let window = app
+            .world_mut()
+            .spawn((Window::default(), PrimaryWindow))
+            .id();
+        app.world_mut().write_message(CursorMoved {
+            window,
+            position: Vec2::new(100.0, 50.0),
+            delta: None,
+        });
+        app.update();
+        app.world_mut().write_message(click(window));
+        app.update();
+        let recording = app.world().resource::<InputRecorder>().recording().clone();
+
+        let mut app = App::new();
+        app.add_plugins((
+            InputPlugin,
+            InputRecordingPlugin::playback_recording(recording),
+        ))
+        .init_resource::<ClickedAt>()
+        .add_systems(Update, track_clicks);
+        let window = app
+            .world_mut()
+            .spawn((Window::default(), PrimaryWindow))
+            .id();
+        app.update();
+
+        // The live cursor is moved elsewhere before the recorded click is played back.
+        let mut window = app.world_mut().get_mut::<Window>(window).unwrap();
+        window.set_cursor_position(Some(Vec2::new(10.0, 10.0)));
+        app.update();
+
+        assert_eq!(
+            app.world().resource::<ClickedAt>().0,
+            [Some(Vec2::new(100.0, 50.0))]
+        );
+    }
+
+    /// An input source, like a gamepad backend, which presses space on the first frame.
+    fn press_space_once(mut writer: MessageWriter<KeyboardInput>, mut frame: Local<u64>) {
+        if *frame == 0 {
+            writer.write(key(ButtonState::Pressed));
+        }
+        *frame += 1;
+    }
+
+    #[test]
+    fn orders_around_input_sources() {
+        let mut app = App::new();
+        app.add_plugins((InputPlugin, InputRecordingPlugin::record("unused.ron")))
+            .add_systems(PreUpdate, press_space_once.in_set(InputSourceSystems));
+        app.update();
+
+        // Input written by sources is recorded on the frame it was written.
+        let recording = app.world().resource::<InputRecorder>().recording().clone();
+        assert_eq!(
+            recording.frames,
+            [RecordedFrame {
+                frame: 0,
+                inputs: vec![RecordedInput::Keyboard(key(ButtonState::Pressed))],
+            }]
+        );
+
+        // Input written by sources is replaced by the recording during playback.
+        let mut app = App::new();
+        app.add_plugins((
+            InputPlugin,
+            InputRecordingPlugin::playback_recording(InputRecording {
+                length: 1,
+                ..Default::default()
+            }),
+        ))
+        .init_resource::<PressedOnFrames>()
+        .add_systems(PreUpdate, press_space_once.in_set(InputSourceSystems))
+        .add_systems(Update, track_space);
+        app.update();
+        app.update();
+        assert!(app.world().resource::<PressedOnFrames>().0.is_empty());
+    }
+}
@@ -2,4 +2,6 @@
 //! visualization tools (for example).
 
 pub mod plugin;
+pub mod report;
 pub mod serde;
+pub mod trace;
@@ -0,0 +1,316 @@
+//! Analysis of a recorded [`ScheduleTrace`].
+//!
+//! The report is computed from the trace alone, so it works equally on a trace that was just
+//! recorded and on one read back from a file with [`ScheduleTrace::read_chrome_trace`].
+
+use core::{fmt, time::Duration};
+
+use super::trace::{ScheduleTrace, TraceFrame, TraceSpan};
+
+/// The per-frame analysis of a [`ScheduleTrace`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TraceReport {
+    /// The report of each frame of the trace.
+    pub frames: Vec<FrameReport>,
+}
+
+/// The analysis of a single frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrameReport {
+    /// The index of the frame.
+    pub frame: u64,
+    /// The duration of the frame.
+    pub duration: Duration,
+    /// The chain of systems which determined when the frame ended, in the order they ran.
+    ///
+    /// The path starts from the system which finished last, and repeatedly steps back to the
+    /// system which finished last before the current one started, on any thread. Systems which
+    /// ran other schedules, such as [`Main::run_main`](bevy_app::Main::run_main), are not
+    /// included; the systems of the schedules they ran are.
+    pub critical_path: Vec<TraceSpan>,
+    /// The total time spent running the systems on the critical path.
+    pub critical_path_duration: Duration,
+    /// How busy each thread of the trace was during the frame.
+    pub threads: Vec<ThreadUsage>,
+    /// The number of systems skipped during the frame.
+    pub skipped_systems: usize,
+}
+
+/// How much of a frame a thread spent running systems.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThreadUsage {
+    /// The id of the thread in the trace.
+    pub thread: usize,
+    /// The name of the thread.
+    pub name: String,
+    /// The time the thread spent running systems.
+    pub busy: Duration,
+    /// The time the thread did not run any system.
+    pub idle: Duration,
+}
+
+impl ThreadUsage {
+    /// The fraction of the frame the thread spent running systems, between 0 and 1.
+    pub fn utilization(&self) -> f32 {
+        let total = self.busy + self.idle;
+        if total.is_zero() {
+            return 0.0;
+        }
+        self.busy.as_secs_f32() / total.as_secs_f32()
+    }
+}
+
+impl FrameReport {
+    /// The fraction of the frame spent running systems on the critical path, between 0 and 1.
+    ///
+    /// A low value means the frame was mostly spent outside of the systems, for example in the
+    /// executor or waiting for threads to pick up work.
+    pub fn critical_path_utilization(&self) -> f32 {
+        if self.duration.is_zero() {
+            return 0.0;
+        }
+        self.critical_path_duration.as_secs_f32() / self.duration.as_secs_f32()
+    }
+
+    /// Returns the threads which spent at most `max_utilization` of the frame running systems.
+    pub fn idle_threads(&self, max_utilization: f32) -> impl Iterator<Item = &ThreadUsage> {
+        self.threads
+            .iter()
+            .filter(move |thread| thread.utilization() <= max_utilization)
+    }
+}
+
+/// The utilization under which [`TraceReport`]'s [`Display`](fmt::Display) implementation
+/// flags a thread as idle.
+const IDLE_THREAD_UTILIZATION: f32 = 0.1;
+
+impl ScheduleTrace {
+    /// Computes the critical path and thread usage of each recorded frame.
+    pub fn report(&self) -> TraceReport {
+        TraceReport {
+            frames: self
+                .frames
+                .iter()
+                .map(|frame| self.frame_report(frame))
+                .collect(),
+        }
+    }
+
+    fn frame_report(&self, frame: &TraceFrame) -> FrameReport {
+        let leaves = leaf_spans(&frame.systems);
+        let critical_path = critical_path(&leaves);
+        let critical_path_duration = critical_path.iter().map(TraceSpan::duration).sum();
+
+        let duration = frame.duration();
+        let threads = self
+            .threads
+            .iter()
+            .map(|thread| {
+                let busy = busy_time(
+                    leaves
+                        .iter()
+                        .filter(|span| span.thread == thread.id)
+                        .map(|span| (span.start, span.end)),
+                )
+                .min(duration);
+                ThreadUsage {
+                    thread: thread.id,
+                    name: thread.name.clone(),
+                    busy,
+                    idle: duration - busy,
+                }
+            })
+            .collect();
+
+        FrameReport {
+            frame: frame.index,
+            duration,
+            critical_path,
+            critical_path_duration,
+            threads,
+            skipped_systems: frame.skipped.len(),
+        }
+    }
+}
+
+/// Returns the spans which don't contain other spans of the same thread.
+///
+/// Spans which contain others are systems running nested schedules, whose time is already
+/// accounted for by the systems of those schedules.
+fn leaf_spans(spans: &[TraceSpan]) -> Vec<&TraceSpan> {
+    let mut sorted: Vec<&TraceSpan> = spans.iter().collect();
+    sorted.sort_by(|a, b| {
+        (a.thread, a.start)
+            .cmp(&(b.thread, b.start))
+            .then(b.end.cmp(&a.end))
+    });
+
+    let mut is_parent = vec![false; sorted.len()];
+    // The indices of the spans enclosing the current one on the same thread.
+    let mut stack: Vec<usize> = Vec::new();
+    for (index, span) in sorted.iter().enumerate() {
+        while let Some(&top) = stack.last() {
+            let parent = sorted[top];
+            if parent.thread == span.thread && span.start < parent.end && span.end <= parent.end {
+                is_parent[top] = true;
+                break;
+            }
+            stack.pop();
+        }
+        stack.push(index);
+    }
+
+    sorted
+        .into_iter()
+        .zip(is_parent)
+        .filter_map(|(span, is_parent)| (!is_parent).then_some(span))
+        .collect()
+}
+
+/// Walks back from the span which ended last through the spans which ended last before each
+/// span started.
+fn critical_path(leaves: &[&TraceSpan]) -> Vec<TraceSpan> {
+    let mut by_end: Vec<&TraceSpan> = leaves.to_vec();
+    by_end.sort_by_key(|span| span.end);
+
+    let mut path = Vec::new();
+    let mut candidates = by_end.len();
+    while candidates > 0 {
+        let span = by_end[candidates - 1];
+        path.push(span.clone());
+        // Only spans which ended before this one started can have delayed it.
+        candidates = by_end[..candidates - 1].partition_point(|other| other.end <= span.start);
+    }
+    path.reverse();
+    path
+}
+
+/// Returns the total time covered by the intervals, counting overlapping time once.
+fn busy_time(intervals: impl Iterator<Item = (Duration, Duration)>) -> Duration {
+    let mut intervals: Vec<(Duration, Duration)> = intervals.collect();
+    intervals.sort();
+
+    let mut busy = Duration::ZERO;
+    let mut current: Option<(Duration, Duration)> = None;
+    for (start, end) in intervals {
+        match &mut current {
+            Some((_, current_end)) if start <= *current_end => {
+                *current_end = (*current_end).max(end);
+            }
+            _ => {
+                if let Some((current_start, current_end)) = current {
+                    busy += current_end - current_start;
+                }
+                current = Some((start, end));
+            }
+        }
+    }
+    if let Some((start, end)) = current {
+        busy += end - start;
+    }
+    busy
+}
+
+impl fmt::Display for TraceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for frame in &self.frames {
+            writeln!(
+                f,
+                "Frame {}: {:.3?}, critical path {:.3?} ({:.0}%) over {} systems, {} skipped",
+                frame.frame,
+                frame.duration,
+                frame.critical_path_duration,
+                frame.critical_path_utilization() * 100.0,
+                frame.critical_path.len(),
+                frame.skipped_systems,
+            )?;
+            for span in &frame.critical_path {
+                writeln!(
+                    f,
+                    "    {:>10.3?}  {} / {}",
+                    span.duration(),
+                    span.schedule,
+                    span.name
+                )?;
+            }
+            for thread in frame.idle_threads(IDLE_THREAD_UTILIZATION) {
+                writeln!(
+                    f,
+                    "    idle thread: {} ({:.0}% busy)",
+                    thread.name,
+                    thread.utilization() * 100.0
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule_data::trace::TraceThread;
+
+    fn span(name: &str, thread: usize, start: u64, end: u64) -> TraceSpan {
+        TraceSpan {
+            schedule: "Update".into(),
+            name: name.into(),
+            thread,
+            start: Duration::from_millis(start),
+            end: Duration::from_millis(end),
+        }
+    }
+
+    #[test]
+    fn critical_path_and_idle_threads() {
+        let trace = ScheduleTrace {
+            threads: vec![
+                TraceThread {
+                    id: 1,
+                    name: "main".into(),
+                },
+                TraceThread {
+                    id: 2,
+                    name: "worker".into(),
+                },
+                TraceThread {
+                    id: 3,
+                    name: "idle worker".into(),
+                },
+            ],
+            frames: vec![TraceFrame {
+                index: 0,
+                start: Duration::ZERO,
+                end: Duration::from_millis(10),
+                systems: vec![
+                    // `run_main` contains all the other systems of the main thread.
+                    span("run_main", 1, 0, 10),
+                    span("input", 1, 0, 2),
+                    span("physics", 2, 2, 7),
+                    span("ai", 1, 2, 4),
+                    span("render", 1, 7, 9),
+                    span("tiny", 3, 3, 3),
+                ],
+                ..Default::default()
+            }],
+        };
+
+        let report = trace.report();
+        let frame = &report.frames[0];
+        let path: Vec<_> = frame
+            .critical_path
+            .iter()
+            .map(|span| span.name.as_str())
+            .collect();
+        assert_eq!(path, ["input", "physics", "render"]);
+        assert_eq!(frame.critical_path_duration, Duration::from_millis(9));
+
+        assert_eq!(frame.threads[0].busy, Duration::from_millis(6));
+        assert_eq!(frame.threads[1].busy, Duration::from_millis(5));
+        let idle: Vec<_> = frame
+            .idle_threads(0.1)
+            .map(|thread| thread.name.as_str())
+            .collect();
+        assert_eq!(idle, ["idle worker"]);
+    }
+}
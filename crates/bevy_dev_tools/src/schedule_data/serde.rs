@@ -6,6 +6,7 @@
 
 use bevy_ecs::{
     component::{ComponentId, Components},
+    query::{AccessConflictExplanation, AccessMode, ConflictingAccess, DisjointFilter},
     schedule::{
         ApplyDeferred, ConditionWithAccess, InternedScheduleLabel, NodeId, Schedule,
         ScheduleBuildMetadata, Schedules,
@@ -233,6 +234,8 @@ pub struct SystemConflict {
     pub system_2: usize,
     /// The kind of conflict between these systems.
     pub conflicting_access: AccessConflict,
+    /// Which accesses of the systems conflict, and how they could be made disjoint.
+    pub explanations: Vec<ConflictExplanationData>,
 }
 
 /// Data for describing the kind of access conflict.
@@ -245,6 +248,67 @@ pub enum AccessConflict {
     Components(Vec<usize>),
 }
 
+/// A serializable version of [`bevy_ecs::query::AccessConflictExplanation`].
+///
+/// All indexes are into [`ScheduleData::components`]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct ConflictExplanationData {
+    /// The component both accesses conflict on, or `None` if one of them accesses all components.
+    pub component: Option<usize>,
+    /// The conflicting access of the first system.
+    pub access_1: ConflictingAccessData,
+    /// The conflicting access of the second system.
+    pub access_2: ConflictingAccessData,
+}
+
+impl ConflictExplanationData {
+    fn new(value: &AccessConflictExplanation, trace: &mut ComponentTrace) -> Self {
+        Self {
+            component: value.component.map(|id| trace.get_index(id)),
+            access_1: ConflictingAccessData::new(&value.first, trace),
+            access_2: ConflictingAccessData::new(&value.second, trace),
+        }
+    }
+}
+
+/// A serializable version of [`bevy_ecs::query::ConflictingAccess`].
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct ConflictingAccessData {
+    /// The short type name of the system parameter the access comes from, if known.
+    pub param: Option<String>,
+    /// Whether the access writes the component, rather than only reading it.
+    pub write: bool,
+    /// A filter which, added to this access, would make it disjoint from the other one.
+    pub disjoint_filter: Option<DisjointFilterData>,
+}
+
+impl ConflictingAccessData {
+    fn new(value: &ConflictingAccess, trace: &mut ComponentTrace) -> Self {
+        Self {
+            param: value
+                .source
+                .as_ref()
+                .map(|source| source.shortname().to_string()),
+            write: value.mode == AccessMode::Write,
+            disjoint_filter: value.disjoint_filter.map(|filter| match filter {
+                DisjointFilter::With(id) => DisjointFilterData::With(trace.get_index(id)),
+                DisjointFilter::Without(id) => DisjointFilterData::Without(trace.get_index(id)),
+            }),
+        }
+    }
+}
+
+/// A serializable version of [`bevy_ecs::query::DisjointFilter`].
+///
+/// All indexes are into [`ScheduleData::components`]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum DisjointFilterData {
+    /// A `With` filter on the component.
+    With(usize),
+    /// A `Without` filter on the component.
+    Without(usize),
+}
+
 /// A newtype for the index of a system set.
 ///
 /// This is the same kind of index as [`ScheduleIndex::SystemSet`], but for cases where we know we
@@ -316,6 +380,7 @@ impl ScheduleData {
         let graph = schedule.graph();
 
         let mut system_key_to_index = HashMap::new();
+        let mut system_key_to_access = HashMap::new();
         let mut system_set_key_to_index = HashMap::new();
 
         fn extract_condition_data(conditions: &[ConditionWithAccess]) -> Vec<ConditionData> {
@@ -338,6 +403,7 @@ impl ScheduleData {
 
                 let system = system_with_access.system();
                 let access = system_with_access.access();
+                system_key_to_access.insert(key, access);
                 let filtered_accesses = access.filtered_accesses();
 
                 let flags = system.flags();
@@ -428,7 +494,12 @@ impl ScheduleData {
         let conflicts = graph
             .conflicting_systems()
             .iter()
-            .map(|(system_1, system_2, conflicts)| {
+            .zip(
+                graph
+                    .conflicting_systems()
+                    .explain(|key| system_key_to_access.get(&key).copied()),
+            )
+            .map(|((system_1, system_2, conflicts), explanations)| {
                 let system_1 = system_key_to_index
                     .get(system_1)
                     .expect("the system this key refers to should have already been seen");
@@ -447,6 +518,12 @@ impl ScheduleData {
                             component_trace.get_indexes(conflicts.iter().copied()),
                         )
                     },
+                    explanations: explanations
+                        .iter()
+                        .map(|explanation| {
+                            ConflictExplanationData::new(explanation, &mut component_trace)
+                        })
+                        .collect(),
                 }
             })
             .collect();
@@ -488,7 +565,8 @@ pub mod tests {
     use bevy_platform::collections::HashMap;
 
     use crate::schedule_data::serde::{
-        AccessConflict, AccessData, AccessFiltersData, AppData, ComponentData, ExtractAppDataError,
+        AccessConflict, AccessData, AccessFiltersData, AppData, ComponentData,
+        ConflictExplanationData, ConflictingAccessData, DisjointFilterData, ExtractAppDataError,
         FilteredAccessData, ScheduleData, ScheduleIndex, SystemConflict, SystemData, SystemSetData,
         SystemSetIndex,
     };
@@ -669,7 +747,28 @@ pub mod tests {
                 // system_2`.
                 if conflict.system_1 > conflict.system_2 {
                     core::mem::swap(&mut conflict.system_1, &mut conflict.system_2);
+                    for explanation in conflict.explanations.iter_mut() {
+                        core::mem::swap(&mut explanation.access_1, &mut explanation.access_2);
+                    }
+                }
+
+                for explanation in conflict.explanations.iter_mut() {
+                    if let Some(component) = &mut explanation.component {
+                        reindex_component(component);
+                    }
+                    for access in [&mut explanation.access_1, &mut explanation.access_2] {
+                        if let Some(
+                            DisjointFilterData::With(component)
+                            | DisjointFilterData::Without(component),
+                        ) = &mut access.disjoint_filter
+                        {
+                            reindex_component(component);
+                        }
+                    }
                 }
+                conflict
+                    .explanations
+                    .sort_by_key(|explanation| explanation.component);
 
                 match &mut conflict.conflicting_access {
                     AccessConflict::World => {}
@@ -760,11 +859,35 @@ pub mod tests {
         system_1: usize,
         system_2: usize,
         conflicting_access: AccessConflict,
+        explanations: Vec<ConflictExplanationData>,
     ) -> SystemConflict {
         SystemConflict {
             system_1,
             system_2,
             conflicting_access,
+            explanations,
+        }
+    }
+
+    /// Convenience to create a [`ConflictExplanationData`] between two system params, each given
+    /// as its name, whether it writes the component, and its suggested filter.
+    pub fn explanation(
+        component: usize,
+        (param_1, write_1, disjoint_filter_1): (&str, bool, Option<DisjointFilterData>),
+        (param_2, write_2, disjoint_filter_2): (&str, bool, Option<DisjointFilterData>),
+    ) -> ConflictExplanationData {
+        ConflictExplanationData {
+            component: Some(component),
+            access_1: ConflictingAccessData {
+                param: Some(param_1.into()),
+                write: write_1,
+                disjoint_filter: disjoint_filter_1,
+            },
+            access_2: ConflictingAccessData {
+                param: Some(param_2.into()),
+                write: write_2,
+                disjoint_filter: disjoint_filter_2,
+            },
         }
     }
 
@@ -1032,6 +1155,13 @@ pub mod tests {
         fn e1(_: Query<&mut MyComponent<9>>) {}
 
         app.add_systems(Update, (a0, a1, b0, b1, c0, c1, d0, d1, (e0, e1).chain()));
+        const C0: &str =
+            "Query<(&MyComponent<2>, &mut MyComponent<3>, &MyComponent<4>, &MyComponent<5>)>";
+        const C1: &str =
+            "Query<(&mut MyComponent<2>, &MyComponent<3>, &MyComponent<4>, &MyComponent<6>)>";
+        // +1 on components for 0 = Disabled
+        let without_5 = Some(DisjointFilterData::Without(6));
+        let without_6 = Some(DisjointFilterData::Without(7));
 
         let data = app_data_from_app(&mut app).unwrap();
         assert_eq!(data.schedules.len(), 1);
@@ -1113,9 +1243,26 @@ pub mod tests {
                 // +1 on components for 0 = Disabled
 
                 // b0, b1 conflict on 1
-                conflict(2, 3, AccessConflict::Components(vec![2])),
-                // c0, c1 conflict on 2, 3
-                conflict(4, 5, AccessConflict::Components(vec![3, 4]))
+                conflict(
+                    2,
+                    3,
+                    AccessConflict::Components(vec![2]),
+                    vec![explanation(
+                        2,
+                        ("Query<&MyComponent<1>>", false, None),
+                        ("Query<&mut MyComponent<1>>", true, None),
+                    )],
+                ),
+                // c0, c1 conflict on 2, 3, and each accesses a component the other doesn't
+                conflict(
+                    4,
+                    5,
+                    AccessConflict::Components(vec![3, 4]),
+                    vec![
+                        explanation(3, (C0, false, without_6), (C1, true, without_5)),
+                        explanation(4, (C0, true, without_6), (C1, false, without_5)),
+                    ],
+                ),
             ]
         );
     }
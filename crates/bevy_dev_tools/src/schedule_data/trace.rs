@@ -0,0 +1,680 @@
+//! Recording of the systems that actually ran in each frame.
+//!
+//! [`ScheduleTracePlugin`] records when each system started and finished, on which thread, and
+//! which systems were skipped by their conditions. The recorded [`ScheduleTrace`] can be written
+//! in the Chrome trace event format, which can be opened in `chrome://tracing` or
+//! [Perfetto](https://ui.perfetto.dev), and read back later to build a
+//! [`TraceReport`](super::report::TraceReport).
+
+use alloc::collections::VecDeque;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread::{self, ThreadId},
+};
+
+use bevy_app::{App, AppExit, Last, Plugin};
+use bevy_ecs::{
+    error::{BevyError, ResultSeverityExt, Severity},
+    message::MessageReader,
+    resource::Resource,
+    schedule::{InternedScheduleLabel, ScheduleTracer, ScheduleTracing, SystemSkipReason},
+    system::Res,
+};
+use bevy_platform::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+use bevy_utils::prelude::DebugName;
+use core::time::Duration;
+use serde::{Deserialize, Serialize};
+
+/// A plugin which records a [`ScheduleTrace`] of the last frames the app ran.
+///
+/// By default, the trace is written to `<current working directory>/schedule_trace.json` when the
+/// app exits. This can be configured to a different path using [`ScheduleTraceFilePath`]. The
+/// recording can also be read at any time through the [`ScheduleTraceRecorder`] resource.
+pub struct ScheduleTracePlugin {
+    /// The number of frames to keep. Older frames are dropped as new ones are recorded.
+    pub max_frames: usize,
+    /// Whether to write the trace to [`ScheduleTraceFilePath`] when the app exits.
+    pub write_on_exit: bool,
+}
+
+impl Default for ScheduleTracePlugin {
+    fn default() -> Self {
+        Self {
+            max_frames: 300,
+            write_on_exit: true,
+        }
+    }
+}
+
+impl Plugin for ScheduleTracePlugin {
+    fn build(&self, app: &mut App) {
+        let recorder = ScheduleTraceRecorder::new(self.max_frames);
+        app.insert_resource(ScheduleTracing::new(recorder.clone()))
+            .insert_resource(recorder)
+            .init_resource::<ScheduleTraceFilePath>();
+        if self.write_on_exit {
+            app.add_systems(Last, write_trace_on_exit);
+        }
+    }
+}
+
+/// The file path where the trace is written to by [`ScheduleTracePlugin`] when the app exits.
+#[derive(Resource)]
+pub struct ScheduleTraceFilePath(pub PathBuf);
+
+impl Default for ScheduleTraceFilePath {
+    fn default() -> Self {
+        Self("schedule_trace.json".into())
+    }
+}
+
+/// A system that writes the recorded trace to [`ScheduleTraceFilePath`] once the app exits.
+fn write_trace_on_exit(
+    mut exit: MessageReader<AppExit>,
+    recorder: Res<ScheduleTraceRecorder>,
+    file_path: Res<ScheduleTraceFilePath>,
+) -> Result<(), BevyError> {
+    if exit.read().last().is_none() {
+        return Ok(());
+    }
+    recorder
+        .trace()
+        .write_chrome_trace(&file_path.0)
+        .with_severity(Severity::Warning)?;
+    Ok(())
+}
+
+/// A [`ScheduleTracer`] which records the frames run by the app into a [`ScheduleTrace`].
+///
+/// Each run of a top-level schedule, usually [`Main`](bevy_app::Main), is recorded as one frame.
+/// Insert it into the world with [`ScheduleTracing`], or use [`ScheduleTracePlugin`].
+#[derive(Resource, Clone)]
+pub struct ScheduleTraceRecorder {
+    recording: Arc<AtomicBool>,
+    state: Arc<Mutex<RecorderState>>,
+}
+
+struct RecorderState {
+    epoch: Instant,
+    max_frames: usize,
+    frames: VecDeque<TraceFrame>,
+    threads: Vec<TraceThread>,
+    thread_ids: HashMap<ThreadId, usize>,
+    next_frame: u64,
+    /// The number of schedules currently running, including nested ones.
+    depth: usize,
+    /// The frame being recorded, if any.
+    current: Option<TraceFrame>,
+    /// The schedules currently running, with the thread and time they started on.
+    running_schedules: Vec<(InternedScheduleLabel, usize, Duration)>,
+    /// The systems currently running, with the thread and time they started on.
+    running_systems: HashMap<(InternedScheduleLabel, usize), (usize, Duration)>,
+}
+
+impl RecorderState {
+    fn now(&self) -> Duration {
+        self.epoch.elapsed()
+    }
+
+    /// Returns the id of the calling thread, assigning a new one if needed.
+    fn current_thread(&mut self) -> usize {
+        let thread = thread::current();
+        if let Some(&id) = self.thread_ids.get(&thread.id()) {
+            return id;
+        }
+        // Thread 0 is used for the frames themselves in Chrome traces.
+        let id = self.threads.len() + 1;
+        let name = thread
+            .name()
+            .map(ToString::to_string)
+            .unwrap_or_else(|| format!("Thread {id}"));
+        self.thread_ids.insert(thread.id(), id);
+        self.threads.push(TraceThread { id, name });
+        id
+    }
+}
+
+impl ScheduleTraceRecorder {
+    /// Creates a recorder which keeps the last `max_frames` frames.
+    pub fn new(max_frames: usize) -> Self {
+        Self {
+            recording: Arc::new(AtomicBool::new(true)),
+            state: Arc::new(Mutex::new(RecorderState {
+                epoch: Instant::now(),
+                max_frames,
+                frames: VecDeque::new(),
+                threads: Vec::new(),
+                thread_ids: HashMap::new(),
+                next_frame: 0,
+                depth: 0,
+                current: None,
+                running_schedules: Vec::new(),
+                running_systems: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Returns whether new frames are recorded.
+    pub fn is_recording(&self) -> bool {
+        self.recording.load(Ordering::Relaxed)
+    }
+
+    /// Sets whether new frames are recorded. A frame that is being recorded is always completed.
+    pub fn set_recording(&self, recording: bool) {
+        self.recording.store(recording, Ordering::Relaxed);
+    }
+
+    /// Returns a copy of the frames recorded so far.
+    pub fn trace(&self) -> ScheduleTrace {
+        let state = self.state.lock().unwrap();
+        ScheduleTrace {
+            threads: state.threads.clone(),
+            frames: state.frames.iter().cloned().collect(),
+        }
+    }
+
+    /// Drops all recorded frames.
+    pub fn clear(&self) {
+        self.state.lock().unwrap().frames.clear();
+    }
+}
+
+impl ScheduleTracer for ScheduleTraceRecorder {
+    fn schedule_started(&self, schedule: InternedScheduleLabel) {
+        let mut state = self.state.lock().unwrap();
+        let now = state.now();
+        if state.depth == 0 && self.is_recording() {
+            let index = state.next_frame;
+            state.next_frame += 1;
+            state.current = Some(TraceFrame {
+                index,
+                start: now,
+                end: now,
+                ..Default::default()
+            });
+        }
+        state.depth += 1;
+        if state.current.is_some() {
+            let thread = state.current_thread();
+            state.running_schedules.push((schedule, thread, now));
+        }
+    }
+
+    fn schedule_finished(&self, schedule: InternedScheduleLabel) {
+        let mut state = self.state.lock().unwrap();
+        let now = state.now();
+        state.depth = state.depth.saturating_sub(1);
+        if let Some(position) = state
+            .running_schedules
+            .iter()
+            .rposition(|(label, ..)| *label == schedule)
+        {
+            let (_, thread, start) = state.running_schedules.remove(position);
+            if let Some(frame) = &mut state.current {
+                frame.schedules.push(TraceSpan {
+                    schedule: format!("{schedule:?}"),
+                    name: format!("{schedule:?}"),
+                    thread,
+                    start,
+                    end: now,
+                });
+            }
+        }
+
+        if state.depth == 0
+            && let Some(mut frame) = state.current.take()
+        {
+            frame.end = now;
+            state.running_schedules.clear();
+            state.running_systems.clear();
+            state.frames.push_back(frame);
+            while state.frames.len() > state.max_frames {
+                state.frames.pop_front();
+            }
+        }
+    }
+
+    fn system_started(&self, schedule: InternedScheduleLabel, system: usize, _name: &DebugName) {
+        let mut state = self.state.lock().unwrap();
+        if state.current.is_none() {
+            return;
+        }
+        let now = state.now();
+        let thread = state.current_thread();
+        state
+            .running_systems
+            .insert((schedule, system), (thread, now));
+    }
+
+    fn system_finished(&self, schedule: InternedScheduleLabel, system: usize, name: &DebugName) {
+        let mut state = self.state.lock().unwrap();
+        let now = state.now();
+        let Some((thread, start)) = state.running_systems.remove(&(schedule, system)) else {
+            return;
+        };
+        if let Some(frame) = &mut state.current {
+            frame.systems.push(TraceSpan {
+                schedule: format!("{schedule:?}"),
+                name: name.to_string(),
+                thread,
+                start,
+                end: now,
+            });
+        }
+    }
+
+    fn system_skipped(
+        &self,
+        schedule: InternedScheduleLabel,
+        _system: usize,
+        name: &DebugName,
+        reason: SystemSkipReason,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        if state.current.is_none() {
+            return;
+        }
+        let now = state.now();
+        let thread = state.current_thread();
+        if let Some(frame) = &mut state.current {
+            frame.skipped.push(SkippedSystem {
+                schedule: format!("{schedule:?}"),
+                name: name.to_string(),
+                thread,
+                at: now,
+                reason,
+            });
+        }
+    }
+}
+
+/// The frames recorded by a [`ScheduleTraceRecorder`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScheduleTrace {
+    /// The threads systems ran on.
+    pub threads: Vec<TraceThread>,
+    /// The recorded frames, oldest first.
+    pub frames: Vec<TraceFrame>,
+}
+
+/// A thread which ran systems.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceThread {
+    /// The id of the thread in the trace. Ids start at 1.
+    pub id: usize,
+    /// The name of the thread.
+    pub name: String,
+}
+
+/// A single run of a top-level schedule.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TraceFrame {
+    /// The index of the frame since recording started.
+    pub index: u64,
+    /// When the frame started, relative to the start of the recording.
+    pub start: Duration,
+    /// When the frame ended, relative to the start of the recording.
+    pub end: Duration,
+    /// The schedules which ran during the frame.
+    pub schedules: Vec<TraceSpan>,
+    /// The systems which ran during the frame, in the order they finished.
+    pub systems: Vec<TraceSpan>,
+    /// The systems which were skipped during the frame.
+    pub skipped: Vec<SkippedSystem>,
+}
+
+impl TraceFrame {
+    /// The duration of the frame.
+    pub fn duration(&self) -> Duration {
+        self.end.saturating_sub(self.start)
+    }
+}
+
+/// A schedule or system which ran on a thread.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceSpan {
+    /// The schedule which ran, or which the system belongs to.
+    pub schedule: String,
+    /// The name of the system, or of the schedule for schedule spans.
+    pub name: String,
+    /// The thread it ran on.
+    pub thread: usize,
+    /// When it started, relative to the start of the recording.
+    pub start: Duration,
+    /// When it ended, relative to the start of the recording.
+    pub end: Duration,
+}
+
+impl TraceSpan {
+    /// How long it ran.
+    pub fn duration(&self) -> Duration {
+        self.end.saturating_sub(self.start)
+    }
+}
+
+/// A system which was not run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SkippedSystem {
+    /// The schedule the system belongs to.
+    pub schedule: String,
+    /// The name of the system.
+    pub name: String,
+    /// The thread the executor decided to skip the system on.
+    pub thread: usize,
+    /// When the system was skipped, relative to the start of the recording.
+    pub at: Duration,
+    /// Why the system was skipped.
+    pub reason: SystemSkipReason,
+}
+
+/// The thread id used for the frame spans in Chrome traces.
+const FRAMES_THREAD: usize = 0;
+const PROCESS: u32 = 1;
+const CATEGORY_FRAME: &str = "frame";
+const CATEGORY_SCHEDULE: &str = "schedule";
+const CATEGORY_SYSTEM: &str = "system";
+const CATEGORY_SKIPPED: &str = "skipped";
+
+/// A trace in the Chrome trace event format.
+#[derive(Serialize, Deserialize)]
+struct ChromeTrace {
+    #[serde(rename = "traceEvents")]
+    trace_events: Vec<ChromeEvent>,
+    #[serde(rename = "displayTimeUnit", default)]
+    display_time_unit: Option<String>,
+}
+
+/// A single event of a Chrome trace. Only the fields used by [`ScheduleTrace`] are included.
+#[derive(Serialize, Deserialize)]
+struct ChromeEvent {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cat: Option<String>,
+    ph: String,
+    #[serde(default)]
+    ts: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dur: Option<f64>,
+    #[serde(default)]
+    pid: u32,
+    #[serde(default)]
+    tid: usize,
+    /// The scope of instant events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    s: Option<String>,
+    #[serde(default)]
+    args: ChromeArgs,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct ChromeArgs {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    frame: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schedule: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+fn to_micros(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000_000.0
+}
+
+fn from_micros(micros: f64) -> Duration {
+    Duration::from_secs_f64(micros.max(0.0) / 1_000_000.0)
+}
+
+fn parse_skip_reason(reason: &str) -> Option<SystemSkipReason> {
+    [
+        SystemSkipReason::Stepping,
+        SystemSkipReason::SetConditions,
+        SystemSkipReason::SystemConditions,
+    ]
+    .into_iter()
+    .find(|candidate| candidate.as_str() == reason)
+}
+
+impl ScheduleTrace {
+    /// Serializes the trace in the Chrome trace event format.
+    ///
+    /// Frames are on their own track, and every thread has a track with the schedules and
+    /// systems that ran on it. Skipped systems are instant events.
+    pub fn to_chrome_json(&self) -> Result<String, serde_json::Error> {
+        let mut events = Vec::new();
+        let thread_name = |tid, name: &str| ChromeEvent {
+            name: "thread_name".into(),
+            cat: None,
+            ph: "M".into(),
+            ts: 0.0,
+            dur: None,
+            pid: PROCESS,
+            tid,
+            s: None,
+            args: ChromeArgs {
+                name: Some(name.into()),
+                ..Default::default()
+            },
+        };
+        events.push(thread_name(FRAMES_THREAD, "Frames"));
+        for thread in &self.threads {
+            events.push(thread_name(thread.id, &thread.name));
+        }
+
+        for frame in &self.frames {
+            let span = |span: &TraceSpan, cat: &str| ChromeEvent {
+                name: span.name.clone(),
+                cat: Some(cat.into()),
+                ph: "X".into(),
+                ts: to_micros(span.start),
+                dur: Some(to_micros(span.duration())),
+                pid: PROCESS,
+                tid: span.thread,
+                s: None,
+                args: ChromeArgs {
+                    frame: Some(frame.index),
+                    schedule: Some(span.schedule.clone()),
+                    ..Default::default()
+                },
+            };
+            events.push(ChromeEvent {
+                name: format!("Frame {}", frame.index),
+                cat: Some(CATEGORY_FRAME.into()),
+                ph: "X".into(),
+                ts: to_micros(frame.start),
+                dur: Some(to_micros(frame.duration())),
+                pid: PROCESS,
+                tid: FRAMES_THREAD,
+                s: None,
+                args: ChromeArgs {
+                    frame: Some(frame.index),
+                    ..Default::default()
+                },
+            });
+            events.extend(frame.schedules.iter().map(|s| span(s, CATEGORY_SCHEDULE)));
+            events.extend(frame.systems.iter().map(|s| span(s, CATEGORY_SYSTEM)));
+            events.extend(frame.skipped.iter().map(|skipped| ChromeEvent {
+                name: skipped.name.clone(),
+                cat: Some(CATEGORY_SKIPPED.into()),
+                ph: "i".into(),
+                ts: to_micros(skipped.at),
+                dur: None,
+                pid: PROCESS,
+                tid: skipped.thread,
+                s: Some("t".into()),
+                args: ChromeArgs {
+                    frame: Some(frame.index),
+                    schedule: Some(skipped.schedule.clone()),
+                    reason: Some(skipped.reason.as_str().into()),
+                    ..Default::default()
+                },
+            }));
+        }
+
+        serde_json::to_string(&ChromeTrace {
+            trace_events: events,
+            display_time_unit: Some("ms".into()),
+        })
+    }
+
+    /// Reads a trace written by [`ScheduleTrace::to_chrome_json`].
+    ///
+    /// Events which were not written by [`ScheduleTrace::to_chrome_json`] are ignored.
+    pub fn from_chrome_json(json: &str) -> Result<Self, serde_json::Error> {
+        let chrome: ChromeTrace = serde_json::from_str(json)?;
+        let mut trace = ScheduleTrace::default();
+        let mut frames = HashMap::<u64, TraceFrame>::new();
+
+        // Create the frames first, so the other events can be attached to them in any order.
+        for event in &chrome.trace_events {
+            match (event.ph.as_str(), event.cat.as_deref(), event.args.frame) {
+                ("M", _, _) if event.name == "thread_name" && event.tid != FRAMES_THREAD => {
+                    trace.threads.push(TraceThread {
+                        id: event.tid,
+                        name: event.args.name.clone().unwrap_or_default(),
+                    });
+                }
+                ("X", Some(CATEGORY_FRAME), Some(index)) => {
+                    let start = from_micros(event.ts);
+                    frames.insert(
+                        index,
+                        TraceFrame {
+                            index,
+                            start,
+                            end: start + from_micros(event.dur.unwrap_or_default()),
+                            ..Default::default()
+                        },
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        for event in chrome.trace_events {
+            let Some(frame) = event.args.frame.and_then(|index| frames.get_mut(&index)) else {
+                continue;
+            };
+            let start = from_micros(event.ts);
+            let schedule = event.args.schedule.unwrap_or_default();
+            let span = || TraceSpan {
+                schedule: schedule.clone(),
+                name: event.name.clone(),
+                thread: event.tid,
+                start,
+                end: start + from_micros(event.dur.unwrap_or_default()),
+            };
+            match event.cat.as_deref() {
+                Some(CATEGORY_SCHEDULE) => frame.schedules.push(span()),
+                Some(CATEGORY_SYSTEM) => frame.systems.push(span()),
+                Some(CATEGORY_SKIPPED) => {
+                    if let Some(reason) = event.args.reason.as_deref().and_then(parse_skip_reason) {
+                        frame.skipped.push(SkippedSystem {
+                            schedule,
+                            name: event.name,
+                            thread: event.tid,
+                            at: start,
+                            reason,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        trace.threads.sort_by_key(|thread| thread.id);
+        trace.frames = frames.into_values().collect();
+        trace.frames.sort_by_key(|frame| frame.index);
+        Ok(trace)
+    }
+
+    /// Writes the trace to `path` in the Chrome trace event format.
+    pub fn write_chrome_trace(&self, path: impl AsRef<Path>) -> Result<(), BevyError> {
+        fs::write(path, self.to_chrome_json()?)?;
+        Ok(())
+    }
+
+    /// Reads a trace written by [`ScheduleTrace::write_chrome_trace`].
+    pub fn read_chrome_trace(path: impl AsRef<Path>) -> Result<Self, BevyError> {
+        Ok(Self::from_chrome_json(&fs::read_to_string(path)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{App, Update};
+    use bevy_ecs::schedule::{IntoScheduleConfigs, SystemSkipReason};
+
+    use super::*;
+
+    #[test]
+    fn records_frames_and_round_trips_through_chrome_json() {
+        let mut app = App::empty();
+        fn a() {}
+        fn b() {}
+        app.add_plugins(ScheduleTracePlugin {
+            max_frames: 2,
+            write_on_exit: false,
+        })
+        .add_systems(Update, (a, b.run_if(|| false)).chain());
+
+        for _ in 0..3 {
+            app.world_mut().run_schedule(Update);
+        }
+
+        let trace = app.world().resource::<ScheduleTraceRecorder>().trace();
+        // Only the last two frames are kept.
+        assert_eq!(
+            trace
+                .frames
+                .iter()
+                .map(|frame| frame.index)
+                .collect::<Vec<_>>(),
+            [1, 2]
+        );
+        let frame = &trace.frames[1];
+        assert_eq!(frame.schedules.len(), 1);
+        assert_eq!(frame.schedules[0].name, "Update");
+        assert_eq!(frame.systems.len(), 1);
+        assert!(frame.systems[0].name.ends_with("::a"));
+        assert_eq!(frame.skipped.len(), 1);
+        assert!(frame.skipped[0].name.ends_with("::b"));
+        assert_eq!(frame.skipped[0].reason, SystemSkipReason::SystemConditions);
+        assert!(trace
+            .threads
+            .iter()
+            .any(|thread| thread.id == frame.systems[0].thread));
+
+        let json = trace.to_chrome_json().unwrap();
+        let read = ScheduleTrace::from_chrome_json(&json).unwrap();
+        assert_eq!(read.threads, trace.threads);
+        assert_eq!(read.frames.len(), 2);
+        assert_eq!(read.frames[1].systems[0].name, frame.systems[0].name);
+        assert_eq!(read.frames[1].skipped[0].name, frame.skipped[0].name);
+        assert_eq!(read.frames[1].skipped[0].reason, frame.skipped[0].reason);
+    }
+
+    #[test]
+    fn stops_recording_new_frames() {
+        let mut app = App::empty();
+        app.add_plugins(ScheduleTracePlugin::default())
+            .add_systems(Update, || {});
+
+        app.world_mut().run_schedule(Update);
+        let recorder = app.world().resource::<ScheduleTraceRecorder>().clone();
+        recorder.set_recording(false);
+        app.world_mut().run_schedule(Update);
+        assert_eq!(recorder.trace().frames.len(), 1);
+
+        recorder.clear();
+        assert!(recorder.trace().frames.is_empty());
+    }
+}
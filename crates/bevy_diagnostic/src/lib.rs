@@ -20,6 +20,7 @@ mod entity_count_diagnostics_plugin;
 mod frame_count;
 mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
+mod storage_diagnostics_plugin;
 #[cfg(feature = "sysinfo_plugin")]
 mod system_information_diagnostics_plugin;
 
@@ -29,6 +30,10 @@ pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
 pub use frame_count::{update_frame_count, FrameCount, FrameCountPlugin};
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::{LogDiagnosticsPlugin, LogDiagnosticsState};
+pub use storage_diagnostics_plugin::{
+    ArchetypeStats, ComponentStorageStats, ResourceStats, StorageDiagnosticsPlugin, StorageStats,
+    TableStats,
+};
 #[cfg(feature = "sysinfo_plugin")]
 pub use system_information_diagnostics_plugin::{SystemInfo, SystemInformationDiagnosticsPlugin};
 
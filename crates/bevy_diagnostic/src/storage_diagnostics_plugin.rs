@@ -0,0 +1,443 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_app::prelude::*;
+use bevy_ecs::{component::ComponentId, system::Local, world::World};
+use bevy_platform::collections::HashSet;
+
+use crate::{
+    Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic, DEFAULT_MAX_HISTORY_LENGTH,
+};
+
+/// Adds diagnostics about the memory used by the [`World`]'s archetypes, tables, sparse sets
+/// and resources, and about archetype churn, to an App.
+///
+/// Sizes are measured from the layout of the stored components, and don't include the change
+/// ticks stored next to them. For a detailed breakdown per archetype, table and component, see
+/// [`StorageStats`].
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+pub struct StorageDiagnosticsPlugin {
+    /// The total number of values to keep.
+    pub max_history_length: usize,
+}
+
+impl Default for StorageDiagnosticsPlugin {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_HISTORY_LENGTH)
+    }
+}
+
+impl StorageDiagnosticsPlugin {
+    /// Creates a new `StorageDiagnosticsPlugin` with the specified `max_history_length`.
+    pub fn new(max_history_length: usize) -> Self {
+        Self { max_history_length }
+    }
+}
+
+impl Plugin for StorageDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        for path in [
+            Self::ARCHETYPE_COUNT,
+            Self::NEW_ARCHETYPES,
+            Self::ARCHETYPE_MOVES,
+            Self::TABLE_BYTES,
+            Self::SPARSE_SET_BYTES,
+            Self::RESOURCE_BYTES,
+        ] {
+            app.register_diagnostic(
+                Diagnostic::new(path).with_max_history_length(self.max_history_length),
+            );
+        }
+        // This reads the whole world, so it runs after the systems of `Update`.
+        app.add_systems(Last, Self::diagnostic_system);
+    }
+}
+
+impl StorageDiagnosticsPlugin {
+    /// Number of archetypes in the world.
+    pub const ARCHETYPE_COUNT: DiagnosticPath =
+        DiagnosticPath::const_new("storage/archetype_count");
+
+    /// Number of archetypes created since the previous frame.
+    pub const NEW_ARCHETYPES: DiagnosticPath = DiagnosticPath::const_new("storage/new_archetypes");
+
+    /// Number of times an entity moved to another archetype since the previous frame.
+    ///
+    /// This is only measured with the `track_archetype_moves` feature.
+    pub const ARCHETYPE_MOVES: DiagnosticPath =
+        DiagnosticPath::const_new("storage/archetype_moves");
+
+    /// Bytes of component data stored in tables.
+    pub const TABLE_BYTES: DiagnosticPath = DiagnosticPath::const_new("storage/table_bytes");
+
+    /// Bytes of component data stored in sparse sets.
+    pub const SPARSE_SET_BYTES: DiagnosticPath =
+        DiagnosticPath::const_new("storage/sparse_set_bytes");
+
+    /// Bytes of resource data.
+    pub const RESOURCE_BYTES: DiagnosticPath = DiagnosticPath::const_new("storage/resource_bytes");
+
+    /// Updates the storage measurements.
+    ///
+    /// The archetype churn is measured from the previous run of this system, so nothing is
+    /// reported for it the first time the system runs.
+    ///
+    /// This measures the same totals as [`StorageStats`], without building the full breakdown.
+    pub fn diagnostic_system(
+        mut diagnostics: Diagnostics,
+        world: &World,
+        mut previous: Local<Option<(usize, Option<u64>)>>,
+        mut visited_tables: Local<Vec<bool>>,
+    ) {
+        let components = world.components();
+        let storages = world.storages();
+        let component_size = |component: ComponentId| {
+            components
+                .get_info(component)
+                .map_or(0, |info| info.layout().size())
+        };
+
+        // Tables don't know which components their columns store, but the archetypes using them
+        // do, and every table is used by at least one archetype.
+        visited_tables.clear();
+        visited_tables.resize(storages.tables.len(), false);
+        let mut table_bytes = 0;
+        for archetype in world.archetypes().iter() {
+            let table = archetype.table_id();
+            if core::mem::replace(&mut visited_tables[table.as_usize()], true) {
+                continue;
+            }
+            let row_bytes: usize = archetype.table_components().map(component_size).sum();
+            table_bytes += row_bytes * storages.tables[table].entity_count() as usize;
+        }
+        let sparse_set_bytes: usize = storages
+            .sparse_sets
+            .iter()
+            .map(|(component, sparse_set)| component_size(component) * sparse_set.len())
+            .sum();
+        let resource_bytes: usize = world
+            .iter_resources()
+            .map(|(info, _)| info.layout().size())
+            .sum();
+
+        let archetype_count = world.archetypes().len();
+        let archetype_moves = world.entities().archetype_moves();
+        if let Some((previous_count, previous_moves)) = *previous {
+            diagnostics.add_measurement(&Self::NEW_ARCHETYPES, || {
+                archetype_count.saturating_sub(previous_count) as f64
+            });
+            if let (Some(moves), Some(previous_moves)) = (archetype_moves, previous_moves) {
+                diagnostics.add_measurement(&Self::ARCHETYPE_MOVES, || {
+                    moves.wrapping_sub(previous_moves) as f64
+                });
+            }
+        }
+        *previous = Some((archetype_count, archetype_moves));
+
+        diagnostics.add_measurement(&Self::ARCHETYPE_COUNT, || archetype_count as f64);
+        diagnostics.add_measurement(&Self::TABLE_BYTES, || table_bytes as f64);
+        diagnostics.add_measurement(&Self::SPARSE_SET_BYTES, || sparse_set_bytes as f64);
+        diagnostics.add_measurement(&Self::RESOURCE_BYTES, || resource_bytes as f64);
+    }
+}
+
+/// A snapshot of how the components and resources of a [`World`] are laid out in memory.
+///
+/// Sizes are measured from the layout of the stored components, and don't include the change
+/// ticks stored next to them. Since resources are stored on entities, their data is counted both
+/// in [`resources`](Self::resources) and in the table holding them.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageStats {
+    /// The archetypes of the world, in the order of their ids.
+    pub archetypes: Vec<ArchetypeStats>,
+    /// The tables of the world, in the order of their ids.
+    pub tables: Vec<TableStats>,
+    /// The sparse sets of the world, one per sparse set component.
+    pub sparse_sets: Vec<ComponentStorageStats>,
+    /// The resources of the world.
+    pub resources: Vec<ResourceStats>,
+    /// The total number of times an entity moved to another archetype, if the
+    /// `track_archetype_moves` feature is enabled.
+    ///
+    /// See [`Entities::archetype_moves`](bevy_ecs::entity::Entities::archetype_moves).
+    pub archetype_moves: Option<u64>,
+}
+
+/// The [`StorageStats`] of an archetype.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArchetypeStats {
+    /// The index of the archetype's id.
+    pub id: usize,
+    /// The index of the id of the table storing the archetype's table components.
+    pub table: usize,
+    /// The number of entities in the archetype.
+    pub entity_count: usize,
+    /// The number of components in the archetype.
+    pub component_count: usize,
+}
+
+/// The [`StorageStats`] of a table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableStats {
+    /// The index of the table's id.
+    pub id: usize,
+    /// The number of entities in the table.
+    pub entity_count: usize,
+    /// The number of entities the table can store without reallocating.
+    pub capacity: usize,
+    /// The columns of the table.
+    pub columns: Vec<ComponentStorageStats>,
+}
+
+/// The [`StorageStats`] of a table column or of a sparse set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComponentStorageStats {
+    /// The index of the stored component's id.
+    pub component: usize,
+    /// The name of the stored component.
+    pub name: String,
+    /// The number of stored components.
+    pub len: usize,
+    /// The number of bytes used by the stored components.
+    pub bytes: usize,
+}
+
+/// The [`StorageStats`] of a resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResourceStats {
+    /// The index of the resource's component id.
+    pub component: usize,
+    /// The name of the resource.
+    pub name: String,
+    /// The number of bytes used by the resource.
+    pub bytes: usize,
+}
+
+impl StorageStats {
+    /// Measures the storages of the `world`.
+    pub fn from_world(world: &World) -> Self {
+        let components = world.components();
+        let storages = world.storages();
+        let component_stats = |component: ComponentId, len: usize| {
+            let info = components.get_info(component);
+            ComponentStorageStats {
+                component: component.index(),
+                name: info.map(|info| info.name().to_string()).unwrap_or_default(),
+                len,
+                bytes: info.map_or(0, |info| info.layout().size()) * len,
+            }
+        };
+
+        let archetypes = world
+            .archetypes()
+            .iter()
+            .map(|archetype| ArchetypeStats {
+                id: archetype.id().index(),
+                table: archetype.table_id().as_usize(),
+                entity_count: archetype.len() as usize,
+                component_count: archetype.component_count(),
+            })
+            .collect();
+
+        // Tables don't know which components their columns store, but the archetypes using them
+        // do, and every table is used by at least one archetype.
+        let mut visited = HashSet::new();
+        let mut tables: Vec<_> = world
+            .archetypes()
+            .iter()
+            .filter(|archetype| visited.insert(archetype.table_id().as_usize()))
+            .map(|archetype| {
+                let table = &storages.tables[archetype.table_id()];
+                let entity_count = table.entity_count() as usize;
+                TableStats {
+                    id: archetype.table_id().as_usize(),
+                    entity_count,
+                    capacity: table.capacity(),
+                    columns: archetype
+                        .table_components()
+                        .map(|component| component_stats(component, entity_count))
+                        .collect(),
+                }
+            })
+            .collect();
+        tables.sort_by_key(|table| table.id);
+
+        let sparse_sets = storages
+            .sparse_sets
+            .iter()
+            .map(|(component, sparse_set)| component_stats(component, sparse_set.len()))
+            .collect();
+
+        let resources = world
+            .iter_resources()
+            .map(|(info, _)| ResourceStats {
+                component: info.id().index(),
+                name: info.name().to_string(),
+                bytes: info.layout().size(),
+            })
+            .collect();
+
+        Self {
+            archetypes,
+            tables,
+            sparse_sets,
+            resources,
+            archetype_moves: world.entities().archetype_moves(),
+        }
+    }
+
+    /// Returns the number of bytes of component data stored in tables.
+    pub fn table_bytes(&self) -> usize {
+        self.tables
+            .iter()
+            .flat_map(|table| &table.columns)
+            .map(|column| column.bytes)
+            .sum()
+    }
+
+    /// Returns the number of bytes of component data stored in sparse sets.
+    pub fn sparse_set_bytes(&self) -> usize {
+        self.sparse_sets
+            .iter()
+            .map(|sparse_set| sparse_set.bytes)
+            .sum()
+    }
+
+    /// Returns the number of bytes of resource data.
+    pub fn resource_bytes(&self) -> usize {
+        self.resources.iter().map(|resource| resource.bytes).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DiagnosticsPlugin, DiagnosticsStore};
+    use bevy_ecs::{component::Component, resource::Resource};
+
+    #[derive(Component)]
+    struct Position(#[expect(dead_code, reason = "only the layout is measured")] [f32; 2]);
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct Marker(#[expect(dead_code, reason = "only the layout is measured")] u64);
+
+    #[derive(Resource)]
+    struct Counter(#[expect(dead_code, reason = "only the layout is measured")] u32);
+
+    #[test]
+    fn storage_stats() {
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+        world.spawn(Position([0.; 2]));
+        world.spawn((Position([0.; 2]), Marker(0)));
+
+        let stats = StorageStats::from_world(&world);
+        let position = world.component_id::<Position>().unwrap().index();
+        let positions: usize = stats
+            .tables
+            .iter()
+            .flat_map(|table| &table.columns)
+            .filter(|column| column.component == position)
+            .map(|column| column.bytes)
+            .sum();
+        assert_eq!(positions, 2 * size_of::<Position>());
+        let marker = world.component_id::<Marker>().unwrap().index();
+        assert!(stats
+            .sparse_sets
+            .iter()
+            .any(|sparse_set| sparse_set.component == marker
+                && sparse_set.bytes == size_of::<Marker>()));
+        assert!(stats
+            .resources
+            .iter()
+            .any(|resource| resource.bytes == size_of::<Counter>()));
+        assert_eq!(
+            stats
+                .archetypes
+                .iter()
+                .map(|archetype| archetype.entity_count)
+                .sum::<usize>(),
+            world.entities().count_spawned() as usize
+        );
+    }
+
+    #[test]
+    fn archetype_churn() {
+        let mut app = App::new();
+        app.add_plugins((DiagnosticsPlugin, StorageDiagnosticsPlugin::default()));
+        let entity = app.world_mut().spawn(Position([0.; 2])).id();
+        let update = |app: &mut App| {
+            app.update();
+            let store = app.world().resource::<DiagnosticsStore>();
+            let value = |path: &DiagnosticPath| store.get_measurement(path).map(|m| m.value);
+            (
+                value(&StorageDiagnosticsPlugin::NEW_ARCHETYPES).unwrap(),
+                value(&StorageDiagnosticsPlugin::ARCHETYPE_MOVES),
+            )
+        };
+        app.update();
+        // Running the app moves some resources around every frame.
+        let (new_archetypes, moves) = update(&mut app);
+        assert_eq!(new_archetypes, 0.);
+
+        app.world_mut().entity_mut(entity).insert(Marker(0));
+        app.world_mut().entity_mut(entity).remove::<Marker>();
+        // Archetype moves are only measured with the `track_archetype_moves` feature.
+        assert_eq!(update(&mut app), (1., moves.map(|moves| moves + 2.)));
+    }
+
+    #[test]
+    fn diagnostics_match_storage_stats() {
+        use bevy_ecs::system::RunSystemOnce;
+
+        // The system is run directly on the world, since `App::update` takes some resources out
+        // of the world while the schedules run.
+        let mut world = World::new();
+        let mut store = DiagnosticsStore::default();
+        for path in [
+            StorageDiagnosticsPlugin::ARCHETYPE_COUNT,
+            StorageDiagnosticsPlugin::TABLE_BYTES,
+            StorageDiagnosticsPlugin::SPARSE_SET_BYTES,
+            StorageDiagnosticsPlugin::RESOURCE_BYTES,
+        ] {
+            store.add(Diagnostic::new(path));
+        }
+        world.insert_resource(store);
+        world.insert_resource(Counter(0));
+        world.spawn(Position([0.; 2]));
+        world.spawn((Position([0.; 2]), Marker(0)));
+        world
+            .run_system_once(StorageDiagnosticsPlugin::diagnostic_system)
+            .unwrap();
+
+        let stats = StorageStats::from_world(&world);
+        let store = world.resource::<DiagnosticsStore>();
+        let value = |path: &DiagnosticPath| store.get_measurement(path).unwrap().value;
+        assert_eq!(
+            value(&StorageDiagnosticsPlugin::ARCHETYPE_COUNT),
+            stats.archetypes.len() as f64
+        );
+        assert_eq!(
+            value(&StorageDiagnosticsPlugin::TABLE_BYTES),
+            stats.table_bytes() as f64
+        );
+        assert_eq!(
+            value(&StorageDiagnosticsPlugin::SPARSE_SET_BYTES),
+            stats.sparse_set_bytes() as f64
+        );
+        assert_eq!(
+            value(&StorageDiagnosticsPlugin::RESOURCE_BYTES),
+            stats.resource_bytes() as f64
+        );
+    }
+}
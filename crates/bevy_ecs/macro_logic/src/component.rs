@@ -43,6 +43,10 @@ pub struct DeriveComponent {
     pub relationship: Option<Relationship>,
     /// The relationship target attribute information.
     pub relationship_target: Option<RelationshipTarget>,
+    /// The many-to-many relationship attribute information.
+    pub many_to_many: Option<ManyToMany>,
+    /// The many-to-many relationship target attribute information.
+    pub many_to_many_target: Option<ManyToManyTarget>,
     /// Whether or not this component is immutable.
     pub immutable: bool,
     /// The clone behavior for this component.
@@ -66,6 +70,8 @@ impl DeriveComponent {
             requires: None,
             relationship: None,
             relationship_target: None,
+            many_to_many: None,
+            many_to_many_target: None,
             immutable: false,
             clone_behavior: None,
             map_entities: None,
@@ -150,6 +156,12 @@ impl DeriveComponent {
             } else if attr.path().is_ident(RELATIONSHIP_TARGET) {
                 let relationship_target = attr.parse_args::<RelationshipTarget>()?;
                 attrs.relationship_target = Some(relationship_target);
+            } else if attr.path().is_ident(MANY_TO_MANY) {
+                let many_to_many = attr.parse_args::<ManyToMany>()?;
+                attrs.many_to_many = Some(many_to_many);
+            } else if attr.path().is_ident(MANY_TO_MANY_TARGET) {
+                let many_to_many_target = attr.parse_args::<ManyToManyTarget>()?;
+                attrs.many_to_many_target = Some(many_to_many_target);
             }
         }
 
@@ -160,6 +172,22 @@ impl DeriveComponent {
             ));
         }
 
+        if attrs.many_to_many_target.is_some() && attrs.clone_behavior.is_some() {
+            return Err(syn::Error::new(
+                attrs.clone_behavior.span(),
+                "A ManyToManyTarget is populated by its ManyToMany sources and is never cloned, please remove `clone_behavior = ...`",
+            ));
+        }
+
+        if (attrs.relationship.is_some() || attrs.relationship_target.is_some())
+            && (attrs.many_to_many.is_some() || attrs.many_to_many_target.is_some())
+        {
+            return Err(syn::Error::new(
+                ast.span(),
+                "A component cannot be both a one-to-many and a many-to-many relationship",
+            ));
+        }
+
         Ok(attrs)
     }
 
@@ -195,13 +223,21 @@ impl DeriveComponent {
             Ok(value) => value,
             Err(err) => Some(err.into_compile_error()),
         };
+        let many_to_many = match self.derive_many_to_many(ast, bevy_ecs) {
+            Ok(value) => value,
+            Err(err) => Some(err.into_compile_error()),
+        };
+        let many_to_many_target = match self.derive_many_to_many_target(ast, bevy_ecs) {
+            Ok(value) => value,
+            Err(err) => Some(err.into_compile_error()),
+        };
 
         let map_entities = map_entities(
             &ast.data,
             bevy_ecs,
             Ident::new("this", Span::call_site()),
-            relationship.is_some(),
-            relationship_target.is_some(),
+            relationship.is_some() || many_to_many.is_some(),
+            relationship_target.is_some() || many_to_many_target.is_some(),
             self.map_entities,
         )
         .map(|map_entities_impl| {
@@ -242,6 +278,18 @@ impl DeriveComponent {
                 );
             }
         }
+        if many_to_many.is_some() {
+            on_insert_path.push(quote!(<Self as #bevy_ecs::relationship::ManyToMany>::on_insert));
+            on_discard_path.push(quote!(<Self as #bevy_ecs::relationship::ManyToMany>::on_discard));
+        }
+        if let Some(target) = &self.many_to_many_target {
+            on_discard_path
+                .push(quote!(<Self as #bevy_ecs::relationship::ManyToManyTarget>::on_discard));
+            if target.linked_despawn.is_some() {
+                on_despawn_path
+                    .push(quote!(<Self as #bevy_ecs::relationship::ManyToManyTarget>::on_despawn));
+            }
+        }
 
         let on_add = hook_register_function_call(bevy_ecs, quote! {on_add}, &on_add_path);
         let on_insert = hook_register_function_call(bevy_ecs, quote! {on_insert}, &on_insert_path);
@@ -285,7 +333,7 @@ impl DeriveComponent {
             }
         });
 
-        let mutable_type = (self.immutable || relationship.is_some())
+        let mutable_type = (self.immutable || relationship.is_some() || many_to_many.is_some())
             .then_some(quote! { #bevy_ecs::component::Immutable })
             .unwrap_or(quote! { #bevy_ecs::component::Mutable });
 
@@ -297,6 +345,8 @@ impl DeriveComponent {
                     };
                 (&&&&&&&#bevy_ecs::relationship::RelationshipCloneBehaviorSpecialization::<Self>::default()).default_clone_behavior()
             )
+        } else if many_to_many_target.is_some() {
+            quote!(#bevy_ecs::component::ComponentCloneBehavior::Ignore)
         } else if let Some(behavior) = self.clone_behavior {
             quote!(#bevy_ecs::component::ComponentCloneBehavior::#behavior)
         } else {
@@ -367,6 +417,10 @@ impl DeriveComponent {
             #relationship
 
             #relationship_target
+
+            #many_to_many
+
+            #many_to_many_target
         })
     }
     fn derive_relationship(
@@ -492,6 +546,112 @@ impl DeriveComponent {
             }
         }))
     }
+
+    fn derive_many_to_many(
+        &self,
+        ast: &DeriveInput,
+        bevy_ecs: &Path,
+    ) -> Result<Option<TokenStream>> {
+        let Some(many_to_many) = &self.many_to_many else {
+            return Ok(None);
+        };
+        let (collection, collection_impl) = derive_many_to_many_collection(ast, "ManyToMany")?;
+
+        let struct_name = &ast.ident;
+        let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+        let target = &many_to_many.target;
+        let allow_self_referential = many_to_many.allow_self_referential;
+
+        Ok(Some(quote! {
+            impl #impl_generics #bevy_ecs::relationship::ManyToMany for #struct_name #type_generics #where_clause {
+                type Target = #target;
+                type Collection = #collection;
+                const ALLOW_SELF_REFERENTIAL: bool = #allow_self_referential;
+
+                #collection_impl
+            }
+        }))
+    }
+
+    fn derive_many_to_many_target(
+        &self,
+        ast: &DeriveInput,
+        bevy_ecs: &Path,
+    ) -> Result<Option<TokenStream>> {
+        let Some(many_to_many_target) = &self.many_to_many_target else {
+            return Ok(None);
+        };
+        let (collection, collection_impl) =
+            derive_many_to_many_collection(ast, "ManyToManyTarget")?;
+
+        let struct_name = &ast.ident;
+        let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+        let relationship = &many_to_many_target.relationship;
+        let linked_despawn = match &many_to_many_target.linked_despawn {
+            None => quote!(None),
+            Some(policy) => policy.to_token_stream(),
+        };
+
+        Ok(Some(quote! {
+            impl #impl_generics #bevy_ecs::relationship::ManyToManyTarget for #struct_name #type_generics #where_clause {
+                const LINKED_DESPAWN: #bevy_ecs::relationship::LinkedDespawn = #bevy_ecs::relationship::LinkedDespawn::#linked_despawn;
+                type Relationship = #relationship;
+                type Collection = #collection;
+
+                #collection_impl
+            }
+        }))
+    }
+}
+
+/// Returns the collection type of a many-to-many relationship component, along with the
+/// implementation of the methods giving access to it.
+fn derive_many_to_many_collection(
+    ast: &DeriveInput,
+    derive: &'static str,
+) -> Result<(Type, TokenStream)> {
+    let Data::Struct(DataStruct {
+        fields,
+        struct_token,
+        ..
+    }) = &ast.data
+    else {
+        return Err(syn::Error::new(
+            ast.span(),
+            format!("{derive} can only be derived for structs."),
+        ));
+    };
+    let field = relationship_field(fields, derive, struct_token.span())?;
+
+    if field.vis != Visibility::Inherited {
+        return Err(syn::Error::new(field.span(), format!("The collection in {derive} must be private to prevent users from directly mutating it, which could invalidate the correctness of relationships.")));
+    }
+    let relationship_member = field.ident.clone().map_or(Member::from(0), Member::Named);
+    let members = fields
+        .members()
+        .filter(|member| member != &relationship_member);
+    let fqdefault = FQDefault.into_token_stream();
+
+    let collection_impl = quote! {
+        #[inline]
+        fn collection(&self) -> &Self::Collection {
+            &self.#relationship_member
+        }
+
+        #[inline]
+        fn collection_mut_risky(&mut self) -> &mut Self::Collection {
+            &mut self.#relationship_member
+        }
+
+        #[inline]
+        fn from_collection_risky(collection: Self::Collection) -> Self {
+            Self {
+                #(#members: #fqdefault::default(),)*
+                #relationship_member: collection
+            }
+        }
+    };
+    Ok((field.ty.clone(), collection_impl))
 }
 
 const COMPONENT: &str = "component";
@@ -500,6 +660,8 @@ const STORAGE: &str = "storage";
 const REQUIRE: &str = "require";
 const RELATIONSHIP: &str = "relationship";
 const RELATIONSHIP_TARGET: &str = "relationship_target";
+const MANY_TO_MANY: &str = "many_to_many";
+const MANY_TO_MANY_TARGET: &str = "many_to_many_target";
 
 const ON_ADD: &str = "on_add";
 const ON_INSERT: &str = "on_insert";
@@ -597,6 +759,19 @@ pub struct RelationshipTarget {
     linked_spawn: bool,
 }
 
+/// Derived `#[many_to_many]` attribute information.
+pub struct ManyToMany {
+    target: Type,
+    allow_self_referential: bool,
+}
+
+/// Derived `#[many_to_many_target]` attribute information.
+pub struct ManyToManyTarget {
+    relationship: Type,
+    /// The `LinkedDespawn` variant, if the sources are despawned with the target.
+    linked_despawn: Option<Ident>,
+}
+
 // values for `storage` attribute
 const TABLE: &str = "Table";
 const SPARSE_SET: &str = "SparseSet";
@@ -702,6 +877,10 @@ mod kw {
     syn::custom_keyword!(relationship);
     syn::custom_keyword!(linked_spawn);
     syn::custom_keyword!(allow_self_referential);
+    syn::custom_keyword!(target);
+    syn::custom_keyword!(linked_despawn);
+    syn::custom_keyword!(all);
+    syn::custom_keyword!(orphans);
 }
 
 impl Parse for Relationship {
@@ -764,6 +943,76 @@ impl Parse for RelationshipTarget {
     }
 }
 
+impl Parse for ManyToMany {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+        let mut target: Option<Type> = None;
+        let mut allow_self_referential: bool = false;
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(kw::allow_self_referential) {
+                input.parse::<kw::allow_self_referential>()?;
+                allow_self_referential = true;
+            } else if lookahead.peek(kw::target) {
+                input.parse::<kw::target>()?;
+                input.parse::<Token![=]>()?;
+                target = Some(input.parse()?);
+            } else {
+                return Err(lookahead.error());
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(ManyToMany {
+            target: target
+                .ok_or_else(|| syn::Error::new(input.span(), "Missing `target = X` attribute"))?,
+            allow_self_referential,
+        })
+    }
+}
+
+impl Parse for ManyToManyTarget {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+        let mut relationship: Option<Type> = None;
+        let mut linked_despawn: Option<Ident> = None;
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(kw::linked_despawn) {
+                input.parse::<kw::linked_despawn>()?;
+                input.parse::<Token![=]>()?;
+                let lookahead = input.lookahead1();
+                let span = input.span();
+                linked_despawn = Some(if lookahead.peek(kw::all) {
+                    input.parse::<kw::all>()?;
+                    Ident::new("All", span)
+                } else if lookahead.peek(kw::orphans) {
+                    input.parse::<kw::orphans>()?;
+                    Ident::new("Orphans", span)
+                } else {
+                    return Err(lookahead.error());
+                });
+            } else if lookahead.peek(kw::relationship) {
+                input.parse::<kw::relationship>()?;
+                input.parse::<Token![=]>()?;
+                relationship = Some(input.parse()?);
+            } else {
+                return Err(lookahead.error());
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(ManyToManyTarget {
+            relationship: relationship.ok_or_else(|| {
+                syn::Error::new(input.span(), "Missing `relationship = X` attribute")
+            })?,
+            linked_despawn,
+        })
+    }
+}
+
 /// Returns the field with the `#[relationship]` attribute, the only field if unnamed,
 /// or the only field in a [`Fields::Named`] with one field, otherwise `Err`.
 pub(crate) fn relationship_field<'a>(
@@ -799,6 +799,19 @@ pub fn derive_settings_group(input: TokenStream) -> TokenStream {
 /// #[relationship(relationship_target = PeopleILike, allow_self_referential)]
 /// pub struct LikedBy(pub Entity);
 /// ```
+/// Many-to-many relationships, where each source can relate to any number of targets:
+/// ```ignore
+/// #[derive(Component)]
+/// #[many_to_many(target = FactionMembers)]
+/// pub struct MemberOf(Vec<Entity>);
+///
+/// #[derive(Component)]
+/// #[many_to_many_target(relationship = MemberOf, linked_despawn = orphans)]
+/// pub struct FactionMembers(Vec<Entity>);
+/// ```
+/// where `linked_despawn` is either `all`, to despawn every source when the target is despawned,
+/// or `orphans`, to only despawn the sources which have no other target left.
+///
 /// ## Warning
 ///
 /// When `allow_self_referential` is enabled, be careful when using recursive traversal methods
@@ -824,7 +837,15 @@ pub fn derive_settings_group(input: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro_derive(
     Component,
-    attributes(component, require, relationship, relationship_target, entities)
+    attributes(
+        component,
+        require,
+        relationship,
+        relationship_target,
+        many_to_many,
+        many_to_many_target,
+        entities
+    )
 )]
 pub fn derive_component(input: TokenStream) -> TokenStream {
     let mut ast = parse_macro_input!(input as DeriveInput);
@@ -0,0 +1,554 @@
+//! Async tasks that access the [`World`] across several frames.
+//!
+//! Workflows spanning several frames, like a cutscene or a network handshake, usually end up as a
+//! state machine stored in a component or resource, advanced a little by a system every frame.
+//! An async task lets them read as straight-line code instead: spawn one with
+//! [`World::spawn_async`] or [`Commands::spawn_async`], and `await` the [`AsyncWorld`] it is given
+//! whenever it needs to access the world or wait for something to happen.
+//!
+//! Tasks don't run on their own. They are polled by the [`run_async_tasks`] exclusive system,
+//! wherever it is added to a schedule (`bevy_app`'s `AsyncTasksPlugin` adds it to `Update`).
+//! Each time it runs, every task runs until it waits for the next frame, or for a future that
+//! isn't ready yet. Tasks take turns cooperatively, so a task that never waits for the next
+//! frame blocks the schedule.
+//!
+//! ```
+//! # use bevy_ecs::{prelude::*, async_world::run_async_tasks};
+//! #[derive(Message, Clone)]
+//! struct Skip;
+//!
+//! #[derive(Resource, Default)]
+//! struct Subtitle(&'static str);
+//!
+//! let mut world = World::new();
+//! world.init_resource::<Subtitle>();
+//! world.init_resource::<Messages<Skip>>();
+//! world.spawn_async(|world| async move {
+//!     world.with(|world| world.resource_mut::<Subtitle>().0 = "Welcome!").await;
+//!     world.frames(60).await;
+//!     world.with(|world| world.resource_mut::<Subtitle>().0 = "Press any key").await;
+//!     world.next_message::<Skip>().await;
+//!     world.with(|world| world.resource_mut::<Subtitle>().0 = "").await;
+//! });
+//!
+//! let mut schedule = Schedule::default();
+//! schedule.add_systems(run_async_tasks);
+//! schedule.run(&mut world);
+//! assert_eq!(world.resource::<Subtitle>().0, "Welcome!");
+//! ```
+//!
+//! Any future can be awaited, including the [`Task`](bevy_tasks::Task)s of a task pool. As tasks
+//! are polled once per frame rather than when they are woken, the task continues on the frame
+//! after the future completes.
+//!
+//! An [`AsyncWorld`] can also be cloned into other futures, like a task pool task. The world
+//! accesses they request run the next time the async task the [`AsyncWorld`] was given to is
+//! polled.
+
+use alloc::{boxed::Box, vec::Vec};
+use bevy_platform::{
+    cell::SyncCell,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+};
+use core::{
+    future::{poll_fn, Future},
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use crate::{
+    message::{Message, Messages},
+    resource::Resource,
+    system::Commands,
+    world::World,
+};
+
+type WorldRequest = Box<dyn FnOnce(&mut World) + Send>;
+
+/// The access to the [`World`] given to an async task.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Clone)]
+pub struct AsyncWorld {
+    shared: Arc<AsyncShared>,
+    requests: Arc<Mutex<TaskRequests>>,
+}
+
+#[derive(Default)]
+struct AsyncShared {
+    /// Incremented every time [`run_async_tasks`] runs.
+    frame: AtomicU32,
+    /// The wakers of futures outside of async tasks waiting for the next frame.
+    frame_wakers: Mutex<Vec<Waker>>,
+}
+
+/// The world accesses requested through the [`AsyncWorld`] of a single async task.
+#[derive(Default)]
+struct TaskRequests {
+    requests: Vec<WorldRequest>,
+    /// The wakers of futures outside of the task waiting for their requests.
+    wakers: Vec<Waker>,
+    /// Set once the task has finished or been cancelled, after which no requests will run.
+    closed: bool,
+}
+
+/// The result of a world access requested with [`AsyncWorld::with`].
+struct RequestResult<R> {
+    value: Option<R>,
+    waker: Option<Waker>,
+}
+
+/// Returns `true` if `waker` belongs to a future polled outside of [`run_async_tasks`], which
+/// needs to be woken to make progress.
+fn is_external(waker: &Waker) -> bool {
+    !waker.will_wake(Waker::noop())
+}
+
+impl AsyncWorld {
+    /// Runs `f` with exclusive access to the [`World`], and returns its result.
+    ///
+    /// Within the async task, `f` runs during the same run of [`run_async_tasks`] as the code
+    /// preceding it in the task. Elsewhere, `f` runs the next time the task is polled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the async task this was given to has finished or been cancelled before `f` ran.
+    pub async fn with<R: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut World) -> R + Send + 'static,
+    ) -> R {
+        let result = Arc::new(Mutex::new(RequestResult {
+            value: None,
+            waker: None,
+        }));
+        let slot = result.clone();
+        {
+            let mut requests = self.requests.lock().unwrap();
+            assert!(
+                !requests.closed,
+                "`AsyncWorld::with` was called after its async task finished or was cancelled"
+            );
+            requests.requests.push(Box::new(move |world| {
+                let value = f(world);
+                let mut slot = slot.lock().unwrap();
+                slot.value = Some(value);
+                if let Some(waker) = slot.waker.take() {
+                    waker.wake();
+                }
+            }));
+        }
+        poll_fn(|cx| {
+            let mut result = result.lock().unwrap();
+            if let Some(value) = result.value.take() {
+                return Poll::Ready(value);
+            }
+            if is_external(cx.waker()) {
+                let mut requests = self.requests.lock().unwrap();
+                assert!(
+                    !requests.closed,
+                    "the async task of an `AsyncWorld` finished or was cancelled before running `AsyncWorld::with`"
+                );
+                requests.wakers.push(cx.waker().clone());
+                result.waker = Some(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Waits until the next time [`run_async_tasks`] runs, usually the next frame.
+    pub async fn next_frame(&self) {
+        let frame = self.shared.frame.load(Ordering::Relaxed);
+        poll_fn(|cx| {
+            if self.shared.frame.load(Ordering::Relaxed) != frame {
+                return Poll::Ready(());
+            }
+            if is_external(cx.waker()) {
+                self.shared
+                    .frame_wakers
+                    .lock()
+                    .unwrap()
+                    .push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await;
+    }
+
+    /// Waits for the given number of frames, as with [`next_frame`](Self::next_frame).
+    pub async fn frames(&self, count: u32) {
+        for _ in 0..count {
+            self.next_frame().await;
+        }
+    }
+
+    /// Runs `f` with exclusive access to the [`World`] once per frame, until it returns `Some`.
+    ///
+    /// This can wait for any condition on the world, like an asset being loaded:
+    ///
+    /// ```ignore
+    /// let handle = world.with(|world| world.resource::<AssetServer>().load("level.glb")).await;
+    /// world
+    ///     .until(move |world| world.resource::<AssetServer>().is_loaded(&handle).then_some(()))
+    ///     .await;
+    /// ```
+    pub async fn until<R: Send + 'static>(
+        &self,
+        mut f: impl FnMut(&mut World) -> Option<R> + Send + 'static,
+    ) -> R {
+        loop {
+            let (returned, result) = self
+                .with(move |world| {
+                    let result = f(world);
+                    (f, result)
+                })
+                .await;
+            if let Some(result) = result {
+                return result;
+            }
+            f = returned;
+            self.next_frame().await;
+        }
+    }
+
+    /// Waits for the next message of type `M` written after this is first polled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`Messages<M>`] resource doesn't exist.
+    pub async fn next_message<M: Message + Clone>(&self) -> M {
+        let mut cursor = self
+            .with(|world| world.resource::<Messages<M>>().get_cursor_current())
+            .await;
+        self.until(move |world| cursor.read(world.resource::<Messages<M>>()).next().cloned())
+            .await
+    }
+}
+
+/// Identifies an async task spawned with [`World::spawn_async`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AsyncTaskId(u64);
+
+struct AsyncTask {
+    id: AsyncTaskId,
+    future: SyncCell<Pin<Box<dyn Future<Output = ()> + Send>>>,
+    requests: Arc<Mutex<TaskRequests>>,
+    finished: bool,
+}
+
+impl AsyncTask {
+    /// Stops accepting world accesses from the task's [`AsyncWorld`], dropping the pending ones.
+    fn close(&self) {
+        let mut requests = self.requests.lock().unwrap();
+        requests.closed = true;
+        requests.requests.clear();
+        // Futures outside of the task waiting for a dropped request panic once woken.
+        for waker in requests.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+/// The async tasks of a [`World`], run by [`run_async_tasks`].
+#[derive(Resource, Default)]
+pub struct AsyncTasks {
+    shared: Arc<AsyncShared>,
+    tasks: Vec<AsyncTask>,
+    cancelled: Vec<AsyncTaskId>,
+    next_id: u64,
+}
+
+impl AsyncTasks {
+    /// Returns `true` if the task with the given `id` hasn't finished or been cancelled.
+    ///
+    /// This returns `false` for tasks that haven't finished, if [`run_async_tasks`] is currently
+    /// running.
+    pub fn contains(&self, id: AsyncTaskId) -> bool {
+        self.tasks.iter().any(|task| task.id == id)
+    }
+
+    /// Returns the number of tasks that haven't finished or been cancelled.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if there are no tasks left to run.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Cancels the task with the given `id`, dropping its future without polling it again.
+    pub fn cancel(&mut self, id: AsyncTaskId) {
+        // While the tasks are being run, they are not stored here.
+        self.tasks.retain(|task| {
+            if task.id == id {
+                task.close();
+            }
+            task.id != id
+        });
+        self.cancelled.push(id);
+    }
+}
+
+impl World {
+    /// Spawns an async task, which will start running the next time [`run_async_tasks`] runs.
+    ///
+    /// See the [`async_world` module-level documentation](crate::async_world) for more information.
+    pub fn spawn_async<F, Fut>(&mut self, task: F) -> AsyncTaskId
+    where
+        F: FnOnce(AsyncWorld) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let mut tasks = self.get_resource_or_init::<AsyncTasks>();
+        let id = AsyncTaskId(tasks.next_id);
+        tasks.next_id += 1;
+        let requests = Arc::new(Mutex::new(TaskRequests::default()));
+        let world = AsyncWorld {
+            shared: tasks.shared.clone(),
+            requests: requests.clone(),
+        };
+        tasks.tasks.push(AsyncTask {
+            id,
+            future: SyncCell::new(Box::pin(task(world))),
+            requests,
+            finished: false,
+        });
+        id
+    }
+}
+
+impl Commands<'_, '_> {
+    /// Spawns an async task, which will start running the next time [`run_async_tasks`] runs.
+    ///
+    /// See [`World::spawn_async`] for more information.
+    pub fn spawn_async<F, Fut>(&mut self, task: F)
+    where
+        F: FnOnce(AsyncWorld) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.queue(move |world: &mut World| {
+            world.spawn_async(task);
+        });
+    }
+}
+
+/// Runs the async tasks of the [`World`], until each of them waits for the next frame or for a
+/// future that isn't ready yet.
+///
+/// The world accesses requested by a task run as soon as the task can't make further progress,
+/// in the order they were requested, after which the task is polled again. Tasks spawned while
+/// this system runs start running right away.
+pub fn run_async_tasks(world: &mut World) {
+    let Some(mut resource) = world.get_resource_mut::<AsyncTasks>() else {
+        return;
+    };
+    let shared = resource.shared.clone();
+    shared.frame.fetch_add(1, Ordering::Relaxed);
+    for waker in core::mem::take(&mut *shared.frame_wakers.lock().unwrap()) {
+        waker.wake();
+    }
+    let mut tasks = core::mem::take(&mut resource.tasks);
+    resource.cancelled.clear();
+
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut ready: Vec<usize> = (0..tasks.len()).collect();
+    while !ready.is_empty() {
+        let mut requests = Vec::new();
+        let mut polled = Vec::new();
+        for index in ready.drain(..) {
+            let task = &mut tasks[index];
+            if task.finished {
+                continue;
+            }
+            let finished = task.future.get().as_mut().poll(&mut cx).is_ready();
+            let mut task_requests = {
+                let mut task_requests = task.requests.lock().unwrap();
+                task_requests.wakers.clear();
+                core::mem::take(&mut task_requests.requests)
+            };
+            if finished {
+                // Requests made from outside of the task still run.
+                task.finished = true;
+                task.close();
+            } else if !task_requests.is_empty() {
+                polled.push(index);
+            }
+            requests.append(&mut task_requests);
+        }
+
+        for request in requests {
+            request(world);
+        }
+        ready = polled;
+
+        let mut resource = world.resource_mut::<AsyncTasks>();
+        if !resource.cancelled.is_empty() {
+            let cancelled = core::mem::take(&mut resource.cancelled);
+            for task in tasks.iter_mut() {
+                if cancelled.contains(&task.id) {
+                    task.finished = true;
+                    task.close();
+                }
+            }
+        }
+        let start = tasks.len();
+        tasks.append(&mut resource.tasks);
+        ready.extend(start..tasks.len());
+    }
+
+    // Finished tasks are only dropped now, so that indices stay valid while polling.
+    tasks.retain(|task| !task.finished);
+    world.resource_mut::<AsyncTasks>().tasks = tasks;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use alloc::{vec, vec::Vec};
+    use bevy_tasks::{block_on, poll_once};
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<&'static str>);
+
+    #[derive(Message, Clone)]
+    struct Ping(u32);
+
+    async fn log(world: &AsyncWorld, entry: &'static str) {
+        world
+            .with(move |world| world.resource_mut::<Log>().0.push(entry))
+            .await;
+    }
+
+    #[test]
+    fn tasks_run_until_next_frame() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.spawn_async(|world| async move {
+            log(&world, "a1").await;
+            log(&world, "a2").await;
+            world.next_frame().await;
+            log(&world, "a3").await;
+        });
+        world.spawn_async(|world| async move {
+            log(&world, "b1").await;
+            world.frames(2).await;
+            log(&world, "b2").await;
+        });
+
+        world.run_system_cached(run_async_tasks).unwrap();
+        assert_eq!(world.resource::<Log>().0, vec!["a1", "b1", "a2"]);
+        world.run_system_cached(run_async_tasks).unwrap();
+        assert_eq!(world.resource::<Log>().0, vec!["a1", "b1", "a2", "a3"]);
+        assert_eq!(world.resource::<AsyncTasks>().len(), 1);
+        world.run_system_cached(run_async_tasks).unwrap();
+        assert_eq!(
+            world.resource::<Log>().0,
+            vec!["a1", "b1", "a2", "a3", "b2"]
+        );
+        assert!(world.resource::<AsyncTasks>().is_empty());
+    }
+
+    #[test]
+    fn tasks_wait_for_messages() {
+        let mut world = World::new();
+        world.init_resource::<Messages<Ping>>();
+        world.insert_resource(Log::default());
+        world.spawn_async(|world| async move {
+            let Ping(value) = world.next_message::<Ping>().await;
+            world
+                .with(move |world| {
+                    world
+                        .resource_mut::<Log>()
+                        .0
+                        .push(if value == 2 { "2" } else { "?" });
+                })
+                .await;
+        });
+
+        world.run_system_cached(run_async_tasks).unwrap();
+        world.run_system_cached(run_async_tasks).unwrap();
+        assert!(world.resource::<Log>().0.is_empty());
+        world.write_message(Ping(2));
+        world.run_system_cached(run_async_tasks).unwrap();
+        assert_eq!(world.resource::<Log>().0, vec!["2"]);
+        assert!(world.resource::<AsyncTasks>().is_empty());
+    }
+
+    #[test]
+    fn cancel_task() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let id = world.spawn_async(|world| async move {
+            loop {
+                log(&world, "tick").await;
+                world.next_frame().await;
+            }
+        });
+        // Tasks can be cancelled and spawned from other tasks.
+        world.spawn_async(move |world| async move {
+            world.next_frame().await;
+            world
+                .with(move |world| {
+                    world.resource_mut::<AsyncTasks>().cancel(id);
+                    world.spawn_async(|world| async move {
+                        log(&world, "spawned").await;
+                    });
+                })
+                .await;
+        });
+
+        world.run_system_cached(run_async_tasks).unwrap();
+        world.run_system_cached(run_async_tasks).unwrap();
+        world.run_system_cached(run_async_tasks).unwrap();
+        assert_eq!(world.resource::<Log>().0, vec!["tick", "tick", "spawned"]);
+        assert!(!world.resource::<AsyncTasks>().contains(id));
+        assert!(world.resource::<AsyncTasks>().is_empty());
+    }
+
+    #[test]
+    fn requests_from_outside_the_task() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let shared = Arc::new(Mutex::new(None));
+        let sender = shared.clone();
+        world.spawn_async(move |world| async move {
+            *sender.lock().unwrap() = Some(world.clone());
+            log(&world, "task").await;
+            world.frames(3).await;
+        });
+        world.run_system_cached(run_async_tasks).unwrap();
+        let async_world: AsyncWorld = shared.lock().unwrap().take().unwrap();
+
+        // A future polled elsewhere gets its own results, which don't interfere with the task's.
+        let mut outside = Box::pin(async move {
+            log(&async_world, "outside").await;
+            async_world
+                .with(|world| world.resource::<Log>().0.len())
+                .await
+        });
+        assert!(block_on(poll_once(&mut outside)).is_none());
+        world.run_system_cached(run_async_tasks).unwrap();
+        assert_eq!(world.resource::<Log>().0, vec!["task", "outside"]);
+        assert!(block_on(poll_once(&mut outside)).is_none());
+        world.run_system_cached(run_async_tasks).unwrap();
+        assert_eq!(block_on(outside), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn requests_after_the_task_finished() {
+        let mut world = World::new();
+        let shared = Arc::new(Mutex::new(None));
+        let sender = shared.clone();
+        world.spawn_async(move |world| async move {
+            *sender.lock().unwrap() = Some(world);
+        });
+        world.run_system_cached(run_async_tasks).unwrap();
+        let async_world: AsyncWorld = shared.lock().unwrap().take().unwrap();
+        block_on(async_world.with(|_| ()));
+    }
+}
@@ -233,6 +233,7 @@ impl<'w> BundleInserter<'w> {
                 let new_location = unsafe { new_archetype.allocate(entity, result.table_row) };
                 // SAFETY: Entity and therefore index already exists, location was just allocated
                 unsafe { entities.update_existing_location(entity.index(), Some(new_location)) };
+                entities.record_archetype_move();
 
                 (&*new_archetype, new_location, sparse_sets, table)
             }
@@ -292,6 +293,7 @@ impl<'w> BundleInserter<'w> {
                 let new_location = unsafe { new_archetype.allocate(entity, move_result.new_row) };
                 // SAFETY: Entity exists & location was just allocated
                 unsafe { entities.update_existing_location(entity.index(), Some(new_location)) };
+                entities.record_archetype_move();
 
                 // If an entity was moved into this entity's table spot, update its table row.
                 if let Some(swapped_entity) = move_result.swapped_entity {
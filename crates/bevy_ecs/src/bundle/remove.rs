@@ -334,6 +334,7 @@ impl<'w> BundleRemover<'w> {
                 .entities
                 .update_existing_location(entity.index(), Some(new_location));
         }
+        world.entities.record_archetype_move();
 
         (new_location, pre_remove_result)
     }
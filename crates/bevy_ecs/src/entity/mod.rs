@@ -826,11 +826,41 @@ unsafe impl EntitySetIterator for AllocEntitiesIterator<'_> {}
 #[derive(Debug, Clone)]
 pub struct Entities {
     meta: Vec<EntityMeta>,
+    #[cfg(feature = "track_archetype_moves")]
+    archetype_moves: u64,
 }
 
 impl Entities {
     pub(crate) const fn new() -> Self {
-        Self { meta: Vec::new() }
+        Self {
+            meta: Vec::new(),
+            #[cfg(feature = "track_archetype_moves")]
+            archetype_moves: 0,
+        }
+    }
+
+    /// Returns the number of times an entity moved to another archetype, because a component was
+    /// inserted or removed.
+    ///
+    /// Each move copies the entity's components, so a count growing quickly may point to
+    /// components being inserted and removed in a loop.
+    ///
+    /// Returns `None` unless the `track_archetype_moves` feature is enabled.
+    #[inline]
+    pub fn archetype_moves(&self) -> Option<u64> {
+        #[cfg(feature = "track_archetype_moves")]
+        return Some(self.archetype_moves);
+        #[cfg(not(feature = "track_archetype_moves"))]
+        None
+    }
+
+    /// Records that an entity moved to another archetype.
+    #[inline]
+    pub(crate) fn record_archetype_move(&mut self) {
+        #[cfg(feature = "track_archetype_moves")]
+        {
+            self.archetype_moves = self.archetype_moves.wrapping_add(1);
+        }
     }
 
     /// Clears all entity information
@@ -0,0 +1,453 @@
+//! Indexes from the value of a component to the entities holding that value.
+//!
+//! Finding the entity with a given network id, or all the entities in a given grid cell, would
+//! otherwise require iterating over every entity with the component. A [`ValueIndex`] keeps a map
+//! from each value of a component to the entities that hold it, so that these lookups take
+//! constant time.
+//!
+//! Indexes are opt-in: register one with [`World::register_index`], then look entities up with
+//! the [`ValueIndex`] resource directly, or with the [`QueryByIndex`] system parameter.
+//!
+//! The index is kept up to date by the component's [`on_insert`] and [`on_discard`] hooks.
+//! Hooks don't run when a mutable component, such as [`Name`], is changed in place, so those
+//! changes are picked up by the [`ValueIndex::update`] system instead. `App::register_index` runs
+//! it at the start of every frame; lookups made between an in-place change and the next run of
+//! that system still find the entity under its previous value. Indexes of [immutable] components
+//! are always up to date.
+//!
+//! ```
+//! # use bevy_ecs::{prelude::*, index::QueryByIndex};
+//! #[derive(Component, Clone, PartialEq, Eq, Hash)]
+//! #[component(immutable)]
+//! struct GridCell(i32, i32);
+//!
+//! #[derive(Component)]
+//! struct Health(u32);
+//!
+//! fn damage_cell(mut cells: QueryByIndex<GridCell, &mut Health>) {
+//!     for mut health in cells.iter_mut(&GridCell(2, 3)) {
+//!         health.0 = health.0.saturating_sub(10);
+//!     }
+//! }
+//!
+//! let mut world = World::new();
+//! world.register_index::<GridCell>();
+//! let entity = world.spawn((GridCell(2, 3), Health(100))).id();
+//! world.spawn((GridCell(0, 0), Health(100)));
+//!
+//! world.run_system_once(damage_cell).unwrap();
+//! assert_eq!(world.get::<Health>(entity).unwrap().0, 90);
+//! # use bevy_ecs::system::RunSystemOnce;
+//! ```
+//!
+//! [`on_insert`]: crate::lifecycle::ComponentHooks::on_insert
+//! [`on_discard`]: crate::lifecycle::ComponentHooks::on_discard
+//! [`Name`]: crate::name::Name
+//! [immutable]: crate::component::Immutable
+
+use crate::{
+    component::Component,
+    entity::{Entity, EntityHashMap, EntityHashSet},
+    lifecycle::HookContext,
+    query::{Changed, IterQueryData, QueryData, QueryFilter, QuerySingleError, ROQueryItem},
+    resource::Resource,
+    system::{Query, Res, ResMut, SystemParam},
+    world::{DeferredWorld, World},
+};
+use bevy_platform::collections::HashMap;
+use bevy_utils::prelude::DebugName;
+use core::hash::Hash;
+use thiserror::Error;
+
+/// A [`Component`] which can be indexed by value with a [`ValueIndex`].
+///
+/// This is implemented for every component which can be hashed and cloned.
+pub trait IndexableComponent: Component + Eq + Hash + Clone {}
+
+impl<C: Component + Eq + Hash + Clone> IndexableComponent for C {}
+
+static NO_ENTITIES: EntityHashSet = EntityHashSet::new();
+
+/// A [`Resource`] mapping each value of the component `C` to the entities which hold it.
+///
+/// Added by [`World::register_index`]. See the [module docs](self) for more information.
+#[derive(Resource)]
+pub struct ValueIndex<C: IndexableComponent> {
+    entities: HashMap<C, EntityHashSet>,
+    /// The value each entity is indexed under, which differs from its current value until an
+    /// in-place change is picked up by [`ValueIndex::update`].
+    values: EntityHashMap<C>,
+}
+
+impl<C: IndexableComponent> Default for ValueIndex<C> {
+    fn default() -> Self {
+        Self {
+            entities: HashMap::default(),
+            values: EntityHashMap::default(),
+        }
+    }
+}
+
+impl<C: IndexableComponent> ValueIndex<C> {
+    /// Returns the entities whose `C` component is equal to `value`.
+    pub fn get(&self, value: &C) -> &EntityHashSet {
+        self.entities.get(value).unwrap_or(&NO_ENTITIES)
+    }
+
+    /// Returns `true` if any entity has a `C` component equal to `value`.
+    pub fn contains(&self, value: &C) -> bool {
+        self.entities.contains_key(value)
+    }
+
+    /// Returns an iterator over the indexed values, along with the entities holding each of them.
+    pub fn iter(&self) -> impl Iterator<Item = (&C, &EntityHashSet)> {
+        self.entities.iter()
+    }
+
+    /// Returns the number of distinct values of `C` in the world.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entity has a `C` component.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// A system which re-indexes the entities whose `C` component was changed in place since it
+    /// last ran.
+    ///
+    /// This is only needed for mutable components, see the [module docs](self).
+    pub fn update(mut index: ResMut<Self>, changed: Query<(Entity, &C), Changed<C>>) {
+        for (entity, value) in &changed {
+            if index.values.get(&entity) != Some(value) {
+                index.insert(entity, value.clone());
+            }
+        }
+    }
+
+    fn insert(&mut self, entity: Entity, value: C) {
+        self.remove(entity);
+        self.entities
+            .entry(value.clone())
+            .or_default()
+            .insert(entity);
+        self.values.insert(entity, value);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        let Some(value) = self.values.remove(&entity) else {
+            return;
+        };
+        if let Some(entities) = self.entities.get_mut(&value) {
+            entities.remove(&entity);
+            if entities.is_empty() {
+                self.entities.remove(&value);
+            }
+        }
+    }
+
+    fn on_insert(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let Some(value) = world.get::<C>(entity).cloned() else {
+            return;
+        };
+        if let Some(mut index) = world.get_resource_mut::<Self>() {
+            index.insert(entity, value);
+        }
+    }
+
+    fn on_discard(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        // The value is looked up in the index rather than on the entity, as a mutable component
+        // may have been changed in place since it was indexed.
+        if let Some(mut index) = world.get_resource_mut::<Self>() {
+            index.remove(entity);
+        }
+    }
+}
+
+/// An error returned when [`World::try_register_index`] fails.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum IndexRegistrationError {
+    /// The component already exists in an archetype, so its hooks can no longer be set.
+    #[error("Cannot index {0}, as it already exists in an archetype")]
+    ArchetypeExists(DebugName),
+    /// The component already has an `on_insert` or `on_discard` hook.
+    #[error("Cannot index {0}, as it already has an on_insert or on_discard hook")]
+    ConflictingHooks(DebugName),
+}
+
+impl World {
+    /// Starts maintaining a [`ValueIndex`] of the component `C`.
+    ///
+    /// The index is kept up to date with the `on_insert` and `on_discard` hooks of `C`. If `C`
+    /// is mutable, the [`ValueIndex::update`] system must also be run to pick up in-place changes.
+    /// Registering the index of a component more than once has no effect.
+    ///
+    /// For the non-panicking version, see [`World::try_register_index`].
+    ///
+    /// # Panics
+    ///
+    /// Like [`World::register_component_hooks`], panics if `C` already exists in an archetype,
+    /// so indexes must be registered before any entity holds the component.
+    /// Also panics if `C` already has an `on_insert` or `on_discard` hook.
+    pub fn register_index<C: IndexableComponent>(&mut self) -> &mut Self {
+        if let Err(error) = self.try_register_index::<C>() {
+            panic!("{error}");
+        }
+        self
+    }
+
+    /// Tries to start maintaining a [`ValueIndex`] of the component `C`.
+    ///
+    /// For the panicking version, see [`World::register_index`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`IndexRegistrationError`] if `C` already exists in an archetype, or if `C`
+    /// already has an `on_insert` or `on_discard` hook.
+    pub fn try_register_index<C: IndexableComponent>(
+        &mut self,
+    ) -> Result<(), IndexRegistrationError> {
+        if self.contains_resource::<ValueIndex<C>>() {
+            return Ok(());
+        }
+        let id = self.register_component::<C>();
+        if self
+            .archetypes()
+            .iter()
+            .any(|archetype| archetype.contains(id))
+        {
+            return Err(IndexRegistrationError::ArchetypeExists(
+                DebugName::type_name::<C>(),
+            ));
+        }
+        let hooks = self.register_component_hooks::<C>();
+        if hooks.on_insert.is_some() || hooks.on_discard.is_some() {
+            return Err(IndexRegistrationError::ConflictingHooks(
+                DebugName::type_name::<C>(),
+            ));
+        }
+        hooks
+            .on_insert(ValueIndex::<C>::on_insert)
+            .on_discard(ValueIndex::<C>::on_discard);
+        self.init_resource::<ValueIndex<C>>();
+        Ok(())
+    }
+}
+
+/// A [`SystemParam`] which looks up the entities of a [`Query`] by the value of their `C`
+/// component, using the [`ValueIndex`] of `C`.
+///
+/// Lookups only visit the entities holding the requested value, rather than every entity
+/// matching the query. The index must have been registered with [`World::register_index`],
+/// otherwise the system will fail validation like for any missing [`Res`].
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, index::QueryByIndex};
+/// #[derive(Component, Clone, PartialEq, Eq, Hash)]
+/// #[component(immutable)]
+/// struct NetworkId(u64);
+///
+/// fn find_player(players: QueryByIndex<NetworkId, &Name>) {
+///     if let Ok(name) = players.single(&NetworkId(42)) {
+///         println!("Player 42 is {name}");
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(find_player);
+/// ```
+#[derive(SystemParam)]
+pub struct QueryByIndex<
+    'w,
+    's,
+    C: IndexableComponent,
+    D: QueryData + 'static,
+    F: QueryFilter + 'static = (),
+> {
+    index: Res<'w, ValueIndex<C>>,
+    query: Query<'w, 's, D, F>,
+}
+
+impl<'w, 's, C: IndexableComponent, D: QueryData, F: QueryFilter> QueryByIndex<'w, 's, C, D, F> {
+    /// Returns the index used for lookups.
+    pub fn index(&self) -> &ValueIndex<C> {
+        &self.index
+    }
+
+    /// Returns the underlying query.
+    pub fn query(&self) -> &Query<'w, 's, D, F> {
+        &self.query
+    }
+
+    /// Returns the underlying query mutably.
+    pub fn query_mut(&mut self) -> &mut Query<'w, 's, D, F> {
+        &mut self.query
+    }
+
+    /// Returns an iterator over the read-only query items of the entities whose `C` component is
+    /// equal to `value`.
+    ///
+    /// Entities which hold the value but don't match the query are skipped.
+    pub fn iter(&self, value: &C) -> impl Iterator<Item = ROQueryItem<'_, 's, D>> {
+        self.query.iter_many_unique(self.index.get(value))
+    }
+
+    /// Returns an iterator over the query items of the entities whose `C` component is equal to
+    /// `value`.
+    ///
+    /// Entities which hold the value but don't match the query are skipped.
+    pub fn iter_mut(&mut self, value: &C) -> impl Iterator<Item = D::Item<'_, 's>>
+    where
+        D: IterQueryData,
+    {
+        self.query.iter_many_unique_mut(self.index.get(value))
+    }
+
+    /// Returns the read-only query item of the only entity whose `C` component is equal to
+    /// `value`.
+    ///
+    /// Returns a [`QuerySingleError`] if no entity or more than one entity matches.
+    pub fn single(&self, value: &C) -> Result<ROQueryItem<'_, 's, D>, QuerySingleError> {
+        Self::only(self.iter(value))
+    }
+
+    /// Returns the query item of the only entity whose `C` component is equal to `value`.
+    ///
+    /// Returns a [`QuerySingleError`] if no entity or more than one entity matches.
+    pub fn single_mut(&mut self, value: &C) -> Result<D::Item<'_, 's>, QuerySingleError>
+    where
+        D: IterQueryData,
+    {
+        Self::only(self.iter_mut(value))
+    }
+
+    fn only<T>(mut items: impl Iterator<Item = T>) -> Result<T, QuerySingleError> {
+        match (items.next(), items.next().is_some()) {
+            (Some(item), false) => Ok(item),
+            (None, _) => Err(QuerySingleError::NoEntities(DebugName::type_name::<Self>())),
+            (Some(_), true) => Err(QuerySingleError::MultipleEntities(DebugName::type_name::<
+                Self,
+            >())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, system::RunSystemOnce};
+    use alloc::vec::Vec;
+
+    #[derive(Component, Clone, PartialEq, Eq, Hash, Debug)]
+    #[component(immutable)]
+    struct Cell(i32);
+
+    #[derive(Component, Clone, PartialEq, Eq, Hash)]
+    struct Marker;
+
+    fn entities(world: &World, cell: i32) -> EntityHashSet {
+        world
+            .resource::<ValueIndex<Cell>>()
+            .get(&Cell(cell))
+            .clone()
+    }
+
+    #[test]
+    fn index_follows_inserts_replacements_and_removals() {
+        let mut world = World::new();
+        world.register_index::<Cell>();
+
+        let a = world.spawn(Cell(0)).id();
+        let b = world.spawn(Cell(0)).id();
+        let c = world.spawn(Cell(1)).id();
+        assert_eq!(entities(&world, 0), EntityHashSet::from_iter([a, b]));
+        assert_eq!(entities(&world, 1), EntityHashSet::from_iter([c]));
+
+        world.entity_mut(a).insert(Cell(1));
+        assert_eq!(entities(&world, 0), EntityHashSet::from_iter([b]));
+        assert_eq!(entities(&world, 1), EntityHashSet::from_iter([a, c]));
+
+        world.entity_mut(b).remove::<Cell>();
+        world.despawn(c);
+        assert_eq!(entities(&world, 1), EntityHashSet::from_iter([a]));
+        let index = world.resource::<ValueIndex<Cell>>();
+        assert!(!index.contains(&Cell(0)));
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn query_by_index() {
+        let mut world = World::new();
+        world.register_index::<Cell>().register_index::<Cell>();
+
+        let a = world.spawn((Cell(0), Marker)).id();
+        world.spawn(Cell(0));
+        world.spawn((Cell(1), Marker));
+
+        world
+            .run_system_once(move |query: QueryByIndex<Cell, Entity, With<Marker>>| {
+                assert_eq!(query.iter(&Cell(0)).collect::<Vec<_>>(), [a]);
+                assert_eq!(query.single(&Cell(0)).unwrap(), a);
+                assert!(matches!(
+                    query.single(&Cell(2)),
+                    Err(QuerySingleError::NoEntities(_))
+                ));
+            })
+            .unwrap();
+
+        world
+            .run_system_once(|query: QueryByIndex<Cell, Entity>| {
+                assert!(matches!(
+                    query.single(&Cell(0)),
+                    Err(QuerySingleError::MultipleEntities(_))
+                ));
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn mutable_components_are_reindexed_by_update() {
+        let mut world = World::new();
+        world.register_index::<Name>();
+
+        let a = world.spawn(Name::new("a")).id();
+        world.get_mut::<Name>(a).unwrap().set("b");
+        world.run_system_once(ValueIndex::<Name>::update).unwrap();
+
+        let index = world.resource::<ValueIndex<Name>>();
+        assert!(!index.contains(&Name::new("a")));
+        assert_eq!(index.get(&Name::new("b")), &EntityHashSet::from_iter([a]));
+
+        // Removing a component which was changed in place, but not re-indexed yet, removes it
+        // from the index under its previous value.
+        world.get_mut::<Name>(a).unwrap().set("c");
+        world.entity_mut(a).remove::<Name>();
+        assert!(world.resource::<ValueIndex<Name>>().is_empty());
+    }
+
+    #[test]
+    fn try_register_index_reports_errors() {
+        let mut world = World::new();
+        world.spawn(Cell(0));
+        assert!(matches!(
+            world.try_register_index::<Cell>(),
+            Err(IndexRegistrationError::ArchetypeExists(_))
+        ));
+
+        world
+            .register_component_hooks::<Marker>()
+            .on_insert(|_, _| {});
+        assert!(matches!(
+            world.try_register_index::<Marker>(),
+            Err(IndexRegistrationError::ConflictingHooks(_))
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_must_be_registered_before_use() {
+        let mut world = World::new();
+        world.spawn(Cell(0));
+        world.register_index::<Cell>();
+    }
+}
@@ -27,6 +27,7 @@ extern crate alloc;
 extern crate self as bevy_ecs;
 
 pub mod archetype;
+pub mod async_world;
 pub mod batching;
 pub mod bundle;
 pub mod change_detection;
@@ -36,6 +37,7 @@ pub mod entity_disabling;
 pub mod error;
 pub mod event;
 pub mod hierarchy;
+pub mod index;
 pub mod intern;
 pub mod label;
 pub mod lifecycle;
@@ -8,12 +8,20 @@
 //! - [`CachedObservers`] contains maps of [`ObserverRunner`]s, which are the actual functions that will be run when the observer is triggered.
 //!     - These are split by target type, in order to allow for different lookup strategies.
 //!     - [`CachedComponentObservers`] is one of these maps, which contains observers that are specifically targeted at a component.
+//! - [`CachedObservers`] also tracks the ordering constraints of its observers, and keeps each map sorted in the order
+//!   the observers should run.
 
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
 use bevy_platform::collections::HashMap;
+use core::cmp::Reverse;
 
 use crate::{
-    archetype::ArchetypeFlags, component::ComponentId, entity::EntityHashMap, event::EventKey,
+    archetype::ArchetypeFlags,
+    component::ComponentId,
+    entity::{Entity, EntityHashMap, EntityIndexMap},
+    event::EventKey,
     observer::ObserverRunner,
+    schedule::InternedSystemSet,
 };
 
 /// An internal lookup table tracking all of the observers in the world.
@@ -124,6 +132,14 @@ pub struct CachedObservers {
     pub(super) component_observers: HashMap<ComponentId, CachedComponentObservers>,
     /// Observers watching for triggers of events for a specific entity
     pub(super) entity_observers: EntityHashMap<ObserverMap>,
+    /// The ordering of every observer of this event, in registration order
+    ordering: EntityIndexMap<ObserverOrder>,
+    /// Observers whose ordering constraints could not be satisfied
+    order_conflicts: Vec<Entity>,
+    /// Every observer of this event, in the order they run
+    run_order: Vec<Entity>,
+    /// Whether the maps are currently sorted according to ordering constraints
+    constrained: bool,
 }
 
 impl CachedObservers {
@@ -142,10 +158,171 @@ impl CachedObservers {
     pub fn entity_observers(&self) -> &EntityHashMap<ObserverMap> {
         &self.entity_observers
     }
+
+    /// Returns the observers whose [`before`](crate::observer::Observer::before) and
+    /// [`after`](crate::observer::Observer::after) constraints form a cycle, or depend on one.
+    ///
+    /// These observers run after all the others, in registration order.
+    pub fn order_conflicts(&self) -> &[Entity] {
+        &self.order_conflicts
+    }
+
+    /// Tracks the ordering of a newly registered `observer`, and sorts the observers accordingly.
+    ///
+    /// The `observer` must already be inserted in the maps it runs from.
+    pub(super) fn insert_order(&mut self, observer: Entity, order: &ObserverOrder) {
+        self.ordering.insert(observer, order.clone());
+        if !self.constrained && order.is_unconstrained() {
+            // Observers are inserted at the end of their maps, which already is registration order.
+            self.run_order.push(observer);
+            return;
+        }
+
+        let run_order = self.compute_run_order();
+        let others_unchanged = run_order
+            .iter()
+            .filter(|&&entity| entity != observer)
+            .eq(&self.run_order);
+        self.run_order = run_order;
+        let ranks = self.ranks();
+        if others_unchanged {
+            // Only the maps the observer was inserted into are out of order.
+            let rank = ranks[&observer];
+            self.for_each_map(|map| {
+                if let Some(index) = map.get_index_of(&observer) {
+                    let position = map.keys().filter(|entity| ranks[*entity] < rank).count();
+                    map.move_index(index, position);
+                }
+            });
+        } else {
+            self.for_each_map(|map| map.sort_by(|a, _, b, _| ranks[a].cmp(&ranks[b])));
+        }
+    }
+
+    /// Stops tracking the ordering of a removed `observer`, and sorts the observers accordingly.
+    ///
+    /// The `observer` must already be removed from the maps it ran from.
+    pub(super) fn remove_order(&mut self, observer: Entity) {
+        if self.ordering.shift_remove(&observer).is_none() {
+            return;
+        }
+        self.run_order.retain(|&entity| entity != observer);
+        if !self.constrained {
+            return;
+        }
+
+        // Removing an observer may lift constraints on others, even if it has none itself.
+        let run_order = self.compute_run_order();
+        if run_order != self.run_order {
+            self.run_order = run_order;
+            let ranks = self.ranks();
+            self.for_each_map(|map| map.sort_by(|a, _, b, _| ranks[a].cmp(&ranks[b])));
+        }
+    }
+
+    /// Returns the position of each observer in [`Self::run_order`].
+    fn ranks(&self) -> EntityHashMap<usize> {
+        self.run_order
+            .iter()
+            .enumerate()
+            .map(|(rank, &entity)| (entity, rank))
+            .collect()
+    }
+
+    /// Runs `f` on every map of observers.
+    fn for_each_map(&mut self, mut f: impl FnMut(&mut ObserverMap)) {
+        f(&mut self.global_observers);
+        self.entity_observers.values_mut().for_each(&mut f);
+        for observers in self.component_observers.values_mut() {
+            f(&mut observers.global_observers);
+            observers
+                .entity_component_observers
+                .values_mut()
+                .for_each(&mut f);
+        }
+    }
+
+    /// Computes an order of the observers that satisfies their constraints.
+    ///
+    /// Observers that are not ordered relative to each other run in registration order.
+    fn compute_run_order(&mut self) -> Vec<Entity> {
+        self.constrained = self
+            .ordering
+            .values()
+            .any(|order| !order.is_unconstrained());
+
+        let mut members = HashMap::<InternedSystemSet, Vec<usize>>::default();
+        for (index, order) in self.ordering.values().enumerate() {
+            for set in &order.sets {
+                members.entry(*set).or_default().push(index);
+            }
+        }
+
+        let count = self.ordering.len();
+        let mut successors = vec![Vec::new(); count];
+        let mut in_degree = vec![0; count];
+        for (index, order) in self.ordering.values().enumerate() {
+            for set in &order.before {
+                for &other in members.get(set).into_iter().flatten() {
+                    if other != index {
+                        successors[index].push(other);
+                        in_degree[other] += 1;
+                    }
+                }
+            }
+            for set in &order.after {
+                for &other in members.get(set).into_iter().flatten() {
+                    if other != index {
+                        successors[other].push(index);
+                        in_degree[index] += 1;
+                    }
+                }
+            }
+        }
+
+        // Kahn's algorithm, always picking the earliest registered observer that is ready.
+        let mut ready: BinaryHeap<_> = (0..count)
+            .filter(|&index| in_degree[index] == 0)
+            .map(Reverse)
+            .collect();
+        let mut sorted = Vec::with_capacity(count);
+        while let Some(Reverse(index)) = ready.pop() {
+            sorted.push(index);
+            for &next in &successors[index] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.push(Reverse(next));
+                }
+            }
+        }
+
+        let entity = |index: usize| *self.ordering.get_index(index).unwrap().0;
+        self.order_conflicts = (0..count)
+            .filter(|&index| in_degree[index] > 0)
+            .map(entity)
+            .collect();
+        sorted.extend((0..count).filter(|&index| in_degree[index] > 0));
+        sorted.into_iter().map(entity).collect()
+    }
 }
 
-/// Map between an observer entity and its [`ObserverRunner`]
-pub type ObserverMap = EntityHashMap<ObserverRunner>;
+/// Map between an observer entity and its [`ObserverRunner`], in the order the observers run.
+pub type ObserverMap = EntityIndexMap<ObserverRunner>;
+
+/// The system sets an [`Observer`](crate::observer::Observer) belongs to, and the sets it must run
+/// before or after.
+#[derive(Default, Clone, Debug)]
+pub(super) struct ObserverOrder {
+    pub(super) sets: Vec<InternedSystemSet>,
+    pub(super) before: Vec<InternedSystemSet>,
+    pub(super) after: Vec<InternedSystemSet>,
+}
+
+impl ObserverOrder {
+    fn is_unconstrained(&self) -> bool {
+        self.before.is_empty() && self.after.is_empty()
+    }
+}
 
 /// Collection of [`ObserverRunner`] for [`Observer`](crate::observer::Observer) registered to a particular event targeted at a specific component.
 ///
@@ -20,9 +20,10 @@ use crate::{
     lifecycle::{ComponentHook, HookContext},
     observer::{
         condition::{ObserverCondition, ObserverWithCondition, ObserverWithConditionMarker},
-        observer_system_runner, ObserverRunner,
+        observer_system_runner, ObserverOrder, ObserverRunner,
     },
     prelude::*,
+    schedule::{InternedSystemSet, IntoSystemSet},
     system::{IntoObserverSystem, ObserverSystem},
     world::DeferredWorld,
 };
@@ -352,6 +353,37 @@ impl Observer {
         self
     }
 
+    /// Adds this observer to the given system `set`, so that other observers of the same [`Event`]
+    /// can be ordered relative to it with [`before`](Self::before) and [`after`](Self::after).
+    /// Note that if this is called _after_ an [`Observer`] is spawned, it will produce no effects.
+    pub fn in_set(mut self, set: impl SystemSet) -> Self {
+        self.descriptor.order.sets.push(set.intern());
+        self
+    }
+
+    /// Runs this observer before the observers of the same [`Event`] that are in `set`.
+    ///
+    /// Ordering only applies between observers that run at the same step of a trigger: observers
+    /// watching the event regardless of its target always run before the ones watching specific
+    /// entities or components. If constraints form a cycle, the observers involved run after all
+    /// the others and a warning is logged.
+    /// Note that if this is called _after_ an [`Observer`] is spawned, it will produce no effects.
+    pub fn before<M>(mut self, set: impl IntoSystemSet<M>) -> Self {
+        let set = set.into_system_set();
+        self.descriptor.order.before.push(set.intern());
+        self
+    }
+
+    /// Runs this observer after the observers of the same [`Event`] that are in `set`.
+    ///
+    /// See [`before`](Self::before) for the caveats of observer ordering.
+    /// Note that if this is called _after_ an [`Observer`] is spawned, it will produce no effects.
+    pub fn after<M>(mut self, set: impl IntoSystemSet<M>) -> Self {
+        let set = set.into_system_set();
+        self.descriptor.order.after.push(set.intern());
+        self
+    }
+
     /// Returns the [`ObserverDescriptor`] for this [`Observer`].
     pub fn descriptor(&self) -> &ObserverDescriptor {
         &self.descriptor
@@ -405,6 +437,9 @@ pub struct ObserverDescriptor {
 
     /// The entities the observer is watching.
     pub(super) entities: Vec<Entity>,
+
+    /// The system sets the observer belongs to, and the sets it runs before or after.
+    pub(super) order: ObserverOrder,
 }
 
 impl ObserverDescriptor {
@@ -443,6 +478,21 @@ impl ObserverDescriptor {
     pub fn entities(&self) -> &[Entity] {
         &self.entities
     }
+
+    /// Returns the system sets that the observer belongs to.
+    pub fn sets(&self) -> &[InternedSystemSet] {
+        &self.order.sets
+    }
+
+    /// Returns the system sets that the observer runs before.
+    pub fn before(&self) -> &[InternedSystemSet] {
+        &self.order.before
+    }
+
+    /// Returns the system sets that the observer runs after.
+    pub fn after(&self) -> &[InternedSystemSet] {
+        &self.order.after
+    }
 }
 
 /// A [`ComponentHook`] used by [`Observer`] to handle its [`on-add`](`crate::lifecycle::ComponentHooks::on_add`).
@@ -22,6 +22,7 @@ use crate::{
     prelude::*,
     world::{DeferredWorld, *},
 };
+use log::warn;
 
 impl World {
     /// Spawns a "global" [`Observer`] which will watch for the given event.
@@ -375,6 +376,15 @@ impl World {
                     }
                 }
             }
+
+            cache.insert_order(observer_entity, &descriptor.order);
+            if cache.order_conflicts().contains(&observer_entity) {
+                warn!(
+                    "The ordering constraints of observer `{}` ({observer_entity}) form a cycle. \
+                    It will run after the other observers, in registration order.",
+                    observer_state.system_name()
+                );
+            }
         }
     }
 
@@ -406,14 +416,14 @@ impl World {
         for &event_key in &descriptor.event_keys {
             let cache = observers.get_observers_mut(event_key);
             if descriptor.components.is_empty() && descriptor.entities.is_empty() {
-                cache.global_observers.remove(&entity);
+                cache.global_observers.shift_remove(&entity);
             } else if descriptor.components.is_empty() {
                 for watched_entity in &descriptor.entities {
                     // This check should be unnecessary since this observer hasn't been unregistered yet
                     let Some(observers) = cache.entity_observers.get_mut(watched_entity) else {
                         continue;
                     };
-                    observers.remove(&entity);
+                    observers.shift_remove(&entity);
                     if observers.is_empty() {
                         cache.entity_observers.remove(watched_entity);
                     }
@@ -424,7 +434,7 @@ impl World {
                         continue;
                     };
                     if descriptor.entities.is_empty() {
-                        observers.global_observers.remove(&entity);
+                        observers.global_observers.shift_remove(&entity);
                     } else {
                         for watched_entity in &descriptor.entities {
                             let Some(map) =
@@ -432,7 +442,7 @@ impl World {
                             else {
                                 continue;
                             };
-                            map.remove(&entity);
+                            map.shift_remove(&entity);
                             if map.is_empty() {
                                 observers.entity_component_observers.remove(watched_entity);
                             }
@@ -462,6 +472,8 @@ impl World {
                     }
                 }
             }
+
+            cache.remove_order(entity);
         }
     }
 }
@@ -666,7 +678,7 @@ mod tests {
         world.add_observer(|_: On<Add, A>, mut res: ResMut<Order>| res.observed("add_2"));
 
         world.spawn(A).flush();
-        assert_eq!(vec!["add_2", "add_1"], world.resource::<Order>().0);
+        assert_eq!(vec!["add_1", "add_2"], world.resource::<Order>().0);
         // we have one A entity and two observers
         assert_eq!(world.query::<&A>().query(&world).count(), 1);
         assert_eq!(world.query::<&Observer>().query(&world).count(), 2);
@@ -1600,6 +1612,110 @@ mod tests {
             .contains_key(&a));
     }
 
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    enum ObserverSets {
+        Ui,
+        Gameplay,
+    }
+
+    #[test]
+    fn observer_order_constraints() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        world.spawn(
+            Observer::new(|_: On<EventA>, mut res: ResMut<Order>| res.observed("ui"))
+                .in_set(ObserverSets::Ui)
+                .after(ObserverSets::Gameplay),
+        );
+        world.add_observer(|_: On<EventA>, mut res: ResMut<Order>| res.observed("unordered"));
+        let gameplay = world
+            .spawn(
+                Observer::new(|_: On<EventA>, mut res: ResMut<Order>| res.observed("gameplay"))
+                    .in_set(ObserverSets::Gameplay),
+            )
+            .id();
+        world.spawn(
+            Observer::new(|_: On<EventA>, mut res: ResMut<Order>| res.observed("first"))
+                .before(ObserverSets::Gameplay),
+        );
+        world.trigger(EventA);
+        assert_eq!(
+            vec!["unordered", "first", "gameplay", "ui"],
+            world.resource::<Order>().0
+        );
+
+        // Without gameplay observers, observers go back to registration order.
+        world.despawn(gameplay);
+        world.resource_mut::<Order>().0.clear();
+        world.trigger(EventA);
+        assert_eq!(
+            vec!["ui", "unordered", "first"],
+            world.resource::<Order>().0
+        );
+    }
+
+    #[test]
+    fn observer_order_constraints_across_maps() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        world.spawn(
+            Observer::new(|_: On<EventA>, mut res: ResMut<Order>| res.observed("gameplay"))
+                .in_set(ObserverSets::Gameplay),
+        );
+        world.spawn(
+            Observer::new(|_: On<EventA>, mut res: ResMut<Order>| res.observed("ui"))
+                .in_set(ObserverSets::Ui),
+        );
+        world.trigger(EventA);
+        assert_eq!(vec!["gameplay", "ui"], world.resource::<Order>().0);
+
+        // An observer watching an entity is only inserted into that entity's map, but it still
+        // orders the global observers.
+        let watched = world.spawn_empty().id();
+        world.spawn(
+            Observer::new(|_: On<EventA>| {})
+                .with_entity(watched)
+                .after(ObserverSets::Ui)
+                .before(ObserverSets::Gameplay),
+        );
+        world.resource_mut::<Order>().0.clear();
+        world.trigger(EventA);
+        assert_eq!(vec!["ui", "gameplay"], world.resource::<Order>().0);
+    }
+
+    #[test]
+    fn observer_order_cycle() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let ui = world
+            .spawn(
+                Observer::new(|_: On<EventA>, mut res: ResMut<Order>| res.observed("ui"))
+                    .in_set(ObserverSets::Ui)
+                    .before(ObserverSets::Gameplay),
+            )
+            .id();
+        let gameplay = world
+            .spawn(
+                Observer::new(|_: On<EventA>, mut res: ResMut<Order>| res.observed("gameplay"))
+                    .in_set(ObserverSets::Gameplay)
+                    .before(ObserverSets::Ui),
+            )
+            .id();
+        world.add_observer(|_: On<EventA>, mut res: ResMut<Order>| res.observed("unordered"));
+        world.trigger(EventA);
+        assert_eq!(
+            vec!["unordered", "ui", "gameplay"],
+            world.resource::<Order>().0
+        );
+
+        let event_key = world.event_key::<EventA>().unwrap();
+        let observers = world.observers().try_get_observers(event_key).unwrap();
+        assert_eq!(observers.order_conflicts(), &[ui, gameplay]);
+    }
+
     #[derive(Resource)]
     struct RunConditionFlag(bool);
 
@@ -1,6 +1,15 @@
 use crate::world::unsafe_world_cell::UnsafeWorldCell;
-use crate::{component::ComponentId, resource::IS_RESOURCE};
-use alloc::{format, string::String, vec, vec::Vec};
+use crate::{
+    component::{ComponentId, Components},
+    resource::IS_RESOURCE,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use bevy_utils::prelude::DebugName;
 use core::iter::FusedIterator;
 use core::{fmt, fmt::Debug};
 use derive_more::From;
@@ -689,6 +698,129 @@ impl From<Vec<ComponentId>> for AccessConflicts {
     }
 }
 
+/// Whether an access reads or writes a component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AccessMode {
+    /// Shared access, such as `&Foo`.
+    Read,
+    /// Exclusive access, such as `&mut Foo`.
+    Write,
+}
+
+/// A `With` or `Without` filter which would make two conflicting accesses disjoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DisjointFilter {
+    /// A [`With`](super::With) filter on the component, which the other access excludes.
+    With(ComponentId),
+    /// A [`Without`](super::Without) filter on the component, which the other access requires.
+    Without(ComponentId),
+}
+
+/// One side of an [`AccessConflictExplanation`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConflictingAccess {
+    /// The type of the system parameter the access comes from, if it was recorded with
+    /// [`FilteredAccessSet::add_with_source`].
+    pub source: Option<DebugName>,
+    /// Whether the access reads or writes the conflicting component.
+    pub mode: AccessMode,
+    /// A filter which, added to this access, would make it disjoint from the other one.
+    pub disjoint_filter: Option<DisjointFilter>,
+}
+
+/// Explains why two [`FilteredAccess`]es conflict: which component they both access, how each of
+/// them accesses it, and which filter would make them disjoint.
+///
+/// Returned by [`FilteredAccess::explain_conflicts`] and [`FilteredAccessSet::explain_conflicts`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccessConflictExplanation {
+    /// The component both accesses conflict on, or `None` if one of them accesses all components.
+    pub component: Option<ComponentId>,
+    /// The access which was added first, such as a previous system parameter.
+    pub first: ConflictingAccess,
+    /// The access which was added second.
+    pub second: ConflictingAccess,
+}
+
+impl AccessConflictExplanation {
+    /// Returns a human-readable description of the conflict, naming components with `components`.
+    pub fn display<'a>(&'a self, components: &'a Components) -> impl fmt::Display + 'a {
+        AccessConflictDisplay {
+            explanation: self,
+            components,
+        }
+    }
+}
+
+/// Formats each explanation on its own line, to be appended to a conflict panic message.
+pub(crate) fn format_explanations(
+    explanations: &[AccessConflictExplanation],
+    components: &Components,
+) -> String {
+    explanations
+        .iter()
+        .map(|explanation| format!("\n - {}", explanation.display(components)))
+        .collect()
+}
+
+struct AccessConflictDisplay<'a> {
+    explanation: &'a AccessConflictExplanation,
+    components: &'a Components,
+}
+
+impl fmt::Display for AccessConflictDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = |id: ComponentId| {
+            self.components
+                .get_name(id)
+                .map(|name| name.shortname().to_string())
+                .unwrap_or_else(|| format!("{id:?}"))
+        };
+        let source = |access: &ConflictingAccess, default: &str| {
+            access
+                .source
+                .as_ref()
+                .map(|source| format!("`{}`", source.shortname()))
+                .unwrap_or_else(|| String::from(default))
+        };
+        let verb = |mode| match mode {
+            AccessMode::Read => "reads",
+            AccessMode::Write => "writes",
+        };
+        let AccessConflictExplanation {
+            component,
+            first,
+            second,
+        } = self.explanation;
+        let first_source = source(first, "a previous access");
+        let second_source = source(second, "this access");
+        let component = component
+            .map(|id| format!("`{}`", name(id)))
+            .unwrap_or_else(|| String::from("all components"));
+        write!(
+            f,
+            "{second_source} {} {component}, which {first_source} {}",
+            verb(second.mode),
+            verb(first.mode)
+        )?;
+
+        let filters = [(second, &second_source), (first, &first_source)]
+            .into_iter()
+            .filter_map(|(access, source)| {
+                let filter = match access.disjoint_filter? {
+                    DisjointFilter::With(id) => format!("`With<{}>`", name(id)),
+                    DisjointFilter::Without(id) => format!("`Without<{}>`", name(id)),
+                };
+                Some(format!("adding {filter} to {source}"))
+            })
+            .collect::<Vec<_>>();
+        if !filters.is_empty() {
+            write!(f, "; {} would make them disjoint", filters.join(" or "))?;
+        }
+        Ok(())
+    }
+}
+
 impl FilteredAccess {
     /// Returns a `FilteredAccess` which has no access and matches everything.
     /// This is the equivalent of a `TRUE` logic atom.
@@ -804,6 +936,72 @@ impl FilteredAccess {
         AccessConflicts::empty()
     }
 
+    /// Explains each conflict between this and `other`, with `self` as the first access.
+    ///
+    /// Returns an empty list if they are compatible.
+    pub fn explain_conflicts(&self, other: &FilteredAccess) -> Vec<AccessConflictExplanation> {
+        if self.is_compatible(other) {
+            return Vec::new();
+        }
+        let explain = |component: Option<ComponentId>| {
+            let side = |access: &FilteredAccess, other: &FilteredAccess| ConflictingAccess {
+                source: None,
+                mode: match component {
+                    Some(id) if access.access.has_write(id) => AccessMode::Write,
+                    None if access.access.has_any_write() => AccessMode::Write,
+                    _ => AccessMode::Read,
+                },
+                disjoint_filter: access.disjoint_filter(other),
+            };
+            AccessConflictExplanation {
+                component,
+                first: side(self, other),
+                second: side(other, self),
+            }
+        };
+        match self.access.get_conflicts(&other.access) {
+            AccessConflicts::All => vec![explain(None)],
+            AccessConflicts::Individual(conflicts) => {
+                conflicts.iter().map(|id| explain(Some(id))).collect()
+            }
+        }
+    }
+
+    /// Returns a filter which, added to `self`, would rule out every filter set of `other`.
+    ///
+    /// Filters on components accessed by `self`, or which contradict its own filters, are not
+    /// suggested.
+    fn disjoint_filter(&self, other: &FilteredAccess) -> Option<DisjointFilter> {
+        let always = |filters: fn(&AccessFilters) -> &ComponentIdSet| {
+            let (first, rest) = other.filter_sets.split_first()?;
+            let mut always = filters(first).clone();
+            for filter_set in rest {
+                always.intersect_with(filters(filter_set));
+            }
+            Some(always)
+        };
+        let in_any = |filters: fn(&AccessFilters) -> &ComponentIdSet, id| {
+            self.filter_sets
+                .iter()
+                .any(|filter_set| filters(filter_set).contains(id))
+        };
+
+        let without = always(AccessFilters::with)?
+            .iter()
+            .find(|&id| {
+                !self.access.has_read(id)
+                    && !self.access.has_archetypal(id)
+                    && !in_any(AccessFilters::with, id)
+            })
+            .map(DisjointFilter::Without);
+        without.or_else(|| {
+            always(AccessFilters::without)?
+                .iter()
+                .find(|&id| !in_any(AccessFilters::without, id))
+                .map(DisjointFilter::With)
+        })
+    }
+
     /// Adds all access and filters from `other`.
     ///
     /// Corresponds to a conjunction operation (AND) for filters.
@@ -947,10 +1145,13 @@ impl AccessFilters {
 /// It stores multiple sets of accesses.
 /// - A "combined" set, which is the access of all filters in this set combined.
 /// - The set of access of each individual filters in this set.
-#[derive(Debug, PartialEq, Eq, Default)]
+#[derive(Debug, Eq, Default)]
 pub struct FilteredAccessSet {
     combined_access: Access,
     filtered_accesses: Vec<FilteredAccess>,
+    /// The system parameter each filtered access comes from, if known. Only used to explain
+    /// conflicts, so it is ignored when comparing sets.
+    sources: Vec<Option<DebugName>>,
 }
 
 // This is needed since `#[derive(Clone)]` does not generate optimized `clone_from`.
@@ -959,12 +1160,21 @@ impl Clone for FilteredAccessSet {
         Self {
             combined_access: self.combined_access.clone(),
             filtered_accesses: self.filtered_accesses.clone(),
+            sources: self.sources.clone(),
         }
     }
 
     fn clone_from(&mut self, source: &Self) {
         self.combined_access.clone_from(&source.combined_access);
         self.filtered_accesses.clone_from(&source.filtered_accesses);
+        self.sources.clone_from(&source.sources);
+    }
+}
+
+impl PartialEq for FilteredAccessSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.combined_access == other.combined_access
+            && self.filtered_accesses == other.filtered_accesses
     }
 }
 
@@ -974,6 +1184,7 @@ impl FilteredAccessSet {
         Self {
             combined_access: Access::new(),
             filtered_accesses: Vec::new(),
+            sources: Vec::new(),
         }
     }
 
@@ -1041,10 +1252,53 @@ impl FilteredAccessSet {
         conflicts
     }
 
+    /// Explains each conflict between this set and `other`, with the accesses of this set first.
+    pub fn explain_conflicts(&self, other: &FilteredAccessSet) -> Vec<AccessConflictExplanation> {
+        let mut explanations = Vec::new();
+        if self.combined_access.is_compatible(other.combined_access()) {
+            return explanations;
+        }
+        for (filtered, source) in self.filtered_accesses.iter().zip(&self.sources) {
+            for (other_filtered, other_source) in other.filtered_accesses.iter().zip(&other.sources)
+            {
+                explanations.extend(filtered.explain_conflicts(other_filtered).into_iter().map(
+                    |mut explanation| {
+                        explanation.first.source.clone_from(source);
+                        explanation.second.source.clone_from(other_source);
+                        explanation
+                    },
+                ));
+            }
+        }
+        explanations
+    }
+
+    /// Explains each conflict between this set and `filtered_access`, which would be added with
+    /// the given `source`.
+    pub fn explain_conflicts_single(
+        &self,
+        filtered_access: &FilteredAccess,
+        source: Option<DebugName>,
+    ) -> Vec<AccessConflictExplanation> {
+        let mut set = FilteredAccessSet::new();
+        set.filtered_accesses.push(filtered_access.clone());
+        set.sources.push(source);
+        set.combined_access.extend(&filtered_access.access);
+        self.explain_conflicts(&set)
+    }
+
     /// Adds the filtered access to the set.
     pub fn add(&mut self, filtered_access: FilteredAccess) {
         self.combined_access.extend(&filtered_access.access);
         self.filtered_accesses.push(filtered_access);
+        self.sources.push(None);
+    }
+
+    /// Adds the filtered access to the set, recording the system parameter it comes from to
+    /// explain conflicts with it.
+    pub fn add_with_source(&mut self, filtered_access: FilteredAccess, source: DebugName) {
+        self.add(filtered_access);
+        *self.sources.last_mut().unwrap() = Some(source);
     }
 
     /// Adds a read access to a resource to the set.
@@ -1097,6 +1351,7 @@ impl FilteredAccessSet {
             .extend(&filtered_access_set.combined_access);
         self.filtered_accesses
             .extend(filtered_access_set.filtered_accesses);
+        self.sources.extend(filtered_access_set.sources);
     }
 
     /// Marks the set as reading all possible indices of type T.
@@ -1117,6 +1372,7 @@ impl FilteredAccessSet {
     pub fn clear(&mut self) {
         self.combined_access.clear();
         self.filtered_accesses.clear();
+        self.sources.clear();
     }
 }
 
@@ -1338,11 +1594,13 @@ mod tests {
     use crate::{
         component::ComponentId,
         query::{
-            access::AccessFilters, Access, AccessConflicts, ComponentAccessKind, ComponentIdSet,
-            FilteredAccess, FilteredAccessSet, UnboundedAccessError,
+            access::AccessFilters, Access, AccessConflictExplanation, AccessConflicts, AccessMode,
+            ComponentAccessKind, ComponentIdSet, ConflictingAccess, DisjointFilter, FilteredAccess,
+            FilteredAccessSet, UnboundedAccessError,
         },
     };
     use alloc::{vec, vec::Vec};
+    use bevy_utils::prelude::DebugName;
     use fixedbitset::FixedBitSet;
 
     fn create_sample_access() -> Access {
@@ -1879,4 +2137,86 @@ mod tests {
         s.difference_from(&set_13);
         assert!(s.iter().eq([1].map(ComponentId::new)));
     }
+
+    #[test]
+    fn explain_conflicts() {
+        let [a, b, c] = [0, 1, 2].map(ComponentId::new);
+
+        // `Query<(&mut A, &B), Without<C>>`
+        let mut first = FilteredAccess::default();
+        first.add_write(a);
+        first.add_read(b);
+        first.and_without(c);
+        // `Query<&A>`
+        let mut second = FilteredAccess::default();
+        second.add_read(a);
+
+        assert_eq!(
+            first.explain_conflicts(&second),
+            [AccessConflictExplanation {
+                component: Some(a),
+                first: ConflictingAccess {
+                    source: None,
+                    mode: AccessMode::Write,
+                    disjoint_filter: None,
+                },
+                second: ConflictingAccess {
+                    source: None,
+                    mode: AccessMode::Read,
+                    disjoint_filter: Some(DisjointFilter::Without(b)),
+                },
+            }]
+        );
+
+        // Filters contradicting the access' own filters aren't suggested: `With<C>` would make
+        // `first` disjoint from `Query<&A, Without<C>>`, but it already has `Without<C>`.
+        second.and_without(c);
+        let explanation = &second.explain_conflicts(&first)[0];
+        assert_eq!(
+            explanation.first.disjoint_filter,
+            Some(DisjointFilter::Without(b))
+        );
+        assert_eq!(explanation.second.disjoint_filter, None);
+
+        second.and_without(b);
+        assert!(first.explain_conflicts(&second).is_empty());
+    }
+
+    #[test]
+    fn explain_conflicts_with_sources() {
+        let [a, b] = [0, 1].map(ComponentId::new);
+        let mut set = FilteredAccessSet::new();
+        set.add_unfiltered_read_all_components();
+        let mut write_a = FilteredAccess::default();
+        write_a.add_write(a);
+        set.add_with_source(write_a.clone(), DebugName::borrowed("WriteA"));
+
+        let mut read_ab = FilteredAccess::default();
+        read_ab.add_read(a);
+        read_ab.add_read(b);
+        let explanations =
+            set.explain_conflicts_single(&read_ab, Some(DebugName::borrowed("ReadAB")));
+        assert_eq!(explanations.len(), 1);
+        assert_eq!(explanations[0].component, Some(a));
+        assert_eq!(
+            explanations[0].first.source,
+            Some(DebugName::borrowed("WriteA"))
+        );
+        assert_eq!(
+            explanations[0].second.source,
+            Some(DebugName::borrowed("ReadAB"))
+        );
+
+        // Sources are ignored when comparing sets.
+        let mut other = FilteredAccessSet::new();
+        other.add_unfiltered_read_all_components();
+        other.add(write_a);
+        assert_eq!(set, other);
+
+        let explanations = FilteredAccessSet::from(read_ab).explain_conflicts(&other);
+        assert_eq!(explanations.len(), 1);
+        assert_eq!(explanations[0].first.mode, AccessMode::Read);
+        assert_eq!(explanations[0].second.mode, AccessMode::Write);
+        assert_eq!(explanations[0].second.source, None);
+    }
 }
@@ -6,9 +6,9 @@ use crate::{
     entity_disabling::DefaultQueryFilters,
     prelude::FromWorld,
     query::{
-        ArchetypeFilter, ContiguousQueryData, FilteredAccess, FilteredAccessSet, IterQueryData,
-        QueryCombinationIter, QueryContiguousIter, QueryIter, QueryNotDenseError, QueryParIter,
-        SingleEntityQueryData, WorldQuery,
+        format_explanations, ArchetypeFilter, ContiguousQueryData, FilteredAccess,
+        FilteredAccessSet, IterQueryData, QueryCombinationIter, QueryContiguousIter, QueryIter,
+        QueryNotDenseError, QueryParIter, SingleEntityQueryData, WorldQuery,
     },
     storage::TableId,
     system::Query,
@@ -201,6 +201,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         component_access_set: &mut FilteredAccessSet,
         world: UnsafeWorldCell,
     ) {
+        let source = DebugName::type_name::<Query<D, F>>();
         let conflicts = component_access_set.get_conflicts_single(&self.component_access);
         if !conflicts.is_empty() {
             let mut accesses = conflicts.format_conflict_list(world);
@@ -208,15 +209,17 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             if !accesses.is_empty() {
                 accesses.push(' ');
             }
-            let type_name = DebugName::type_name::<Query<D, F>>();
-            let type_name = type_name.shortname();
+            let explanations = component_access_set
+                .explain_conflicts_single(&self.component_access, Some(source.clone()));
+            let explanations = format_explanations(&explanations, world.components());
+            let type_name = source.shortname();
             let system = system_name
                 .map(|name| format!(" in system {name}"))
                 .unwrap_or_default();
-            panic!("error[B0001]: {type_name}{system} accesses component(s) {accesses}in a way that conflicts with a previous system parameter. Consider using `Without<T>` to create disjoint Queries or merging conflicting Queries into a `ParamSet`. See: https://bevy.org/learn/errors/b0001",);
+            panic!("error[B0001]: {type_name}{system} accesses component(s) {accesses}in a way that conflicts with a previous system parameter. Consider using `Without<T>` to create disjoint Queries or merging conflicting Queries into a `ParamSet`. See: https://bevy.org/learn/errors/b0001{explanations}",);
         }
 
-        component_access_set.add(self.component_access.clone());
+        component_access_set.add_with_source(self.component_access.clone(), source);
         D::init_nested_access(&self.fetch_state, system_name, component_access_set, world);
         F::init_nested_access(&self.filter_state, system_name, component_access_set, world);
     }
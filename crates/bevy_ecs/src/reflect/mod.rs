@@ -18,6 +18,7 @@ mod event;
 mod from_world;
 mod map_entities;
 mod message;
+mod recorded_command;
 mod resource;
 
 use bevy_utils::prelude::DebugName;
@@ -28,6 +29,11 @@ pub use event::{ReflectEvent, ReflectEventFns};
 pub use from_world::{ReflectFromWorld, ReflectFromWorldFns};
 pub use map_entities::ReflectMapEntities;
 pub use message::{ReflectMessage, ReflectMessageFns};
+pub use recorded_command::{
+    CommandRecording, RecordCommandsExt, RecordEntityCommandsExt, RecordedCommand,
+};
+#[cfg(feature = "serialize")]
+pub use recorded_command::{CommandRecordingDeserializer, CommandRecordingSerializer};
 pub use resource::ReflectResource;
 
 /// A [`Resource`] storing [`TypeRegistry`] for
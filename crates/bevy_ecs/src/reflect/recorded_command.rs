@@ -0,0 +1,898 @@
+//! Structural [`World`] mutations which can be recorded from [`Commands`], serialized, and
+//! re-applied to another [`World`] using reflection.
+//!
+//! A [`CommandQueue`](crate::world::CommandQueue) is opaque once commands are pushed to it.
+//! [`RecordedCommand`] instead describes the common structural mutations as data: spawning and
+//! despawning entities, inserting and removing components, and inserting and removing resources.
+//!
+//! Recorded commands are queued like any other command, with the methods of
+//! [`RecordCommandsExt`] and [`RecordEntityCommandsExt`]. When they are applied, and a
+//! [`CommandRecording`] resource exists, they are appended to it in the order they were applied.
+//! The recording can then be replayed on another world with [`CommandRecording::apply`],
+//! which is useful for undo/redo or replicating mutations across the network.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::reflect::{AppTypeRegistry, CommandRecording, RecordCommandsExt};
+//! # use bevy_ecs::entity::EntityHashMap;
+//! # use bevy_reflect::Reflect;
+//! #[derive(Component, Reflect, PartialEq, Debug)]
+//! #[reflect(Component)]
+//! struct Health(u32);
+//!
+//! let registry = AppTypeRegistry::default();
+//! registry.write().register::<Health>();
+//!
+//! let mut world = World::new();
+//! world.insert_resource(registry.clone());
+//! world.init_resource::<CommandRecording>();
+//!
+//! let mut commands = world.commands();
+//! commands.spawn_recorded(vec![Box::new(Health(10))]);
+//! world.flush();
+//!
+//! // Replay the recorded mutations on another world.
+//! let recording = world.remove_resource::<CommandRecording>().unwrap();
+//! let mut replica = World::new();
+//! replica.insert_resource(registry);
+//! recording.apply(&mut replica, &mut EntityHashMap::default());
+//!
+//! let mut query = replica.query::<&Health>();
+//! assert_eq!(query.single(&replica).unwrap(), &Health(10));
+//! ```
+
+use crate::{
+    entity::{Entity, EntityHashMap},
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    relationship::RelationshipHookMode,
+    resource::Resource,
+    system::{Command, Commands, EntityCommands},
+    world::World,
+};
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+use bevy_reflect::{PartialReflect, TypeRegistration, TypeRegistry};
+use log::warn;
+
+/// A structural [`World`] mutation which can be recorded, serialized and re-applied.
+///
+/// Components and resources are stored as reflected values, so their types must be registered in
+/// the [`AppTypeRegistry`] with [`ReflectComponent`] or [`ReflectResource`] type data.
+///
+/// Entities are those of the world the command was recorded in. [`RecordedCommand::apply`]
+/// maps them to the entities of the world the command is applied to.
+#[derive(Debug)]
+pub enum RecordedCommand {
+    /// Spawns an entity with the given components.
+    Spawn {
+        /// The spawned entity.
+        entity: Entity,
+        /// The components of the spawned entity.
+        components: Vec<Box<dyn PartialReflect>>,
+    },
+    /// Inserts components into an entity.
+    ///
+    /// Components which the entity already has are updated in place with
+    /// [`PartialReflect::apply`].
+    Insert {
+        /// The entity to insert the components into.
+        entity: Entity,
+        /// The inserted components.
+        components: Vec<Box<dyn PartialReflect>>,
+    },
+    /// Removes components from an entity.
+    Remove {
+        /// The entity to remove the components from.
+        entity: Entity,
+        /// The type paths of the removed components.
+        components: Vec<Cow<'static, str>>,
+    },
+    /// Despawns an entity.
+    Despawn {
+        /// The despawned entity.
+        entity: Entity,
+    },
+    /// Inserts a resource, replacing the previous value if any.
+    InsertResource {
+        /// The inserted resource.
+        resource: Box<dyn PartialReflect>,
+    },
+    /// Removes a resource.
+    RemoveResource {
+        /// The type path of the removed resource.
+        resource: Cow<'static, str>,
+    },
+}
+
+impl Clone for RecordedCommand {
+    fn clone(&self) -> Self {
+        let clone_all = |values: &Vec<Box<dyn PartialReflect>>| {
+            values.iter().map(|value| value.to_dynamic()).collect()
+        };
+        match self {
+            Self::Spawn { entity, components } => Self::Spawn {
+                entity: *entity,
+                components: clone_all(components),
+            },
+            Self::Insert { entity, components } => Self::Insert {
+                entity: *entity,
+                components: clone_all(components),
+            },
+            Self::Remove { entity, components } => Self::Remove {
+                entity: *entity,
+                components: components.clone(),
+            },
+            Self::Despawn { entity } => Self::Despawn { entity: *entity },
+            Self::InsertResource { resource } => Self::InsertResource {
+                resource: resource.to_dynamic(),
+            },
+            Self::RemoveResource { resource } => Self::RemoveResource {
+                resource: resource.clone(),
+            },
+        }
+    }
+}
+
+impl RecordedCommand {
+    /// Applies this command to `world`, using the [`AppTypeRegistry`] of the world.
+    ///
+    /// Recorded entities are looked up in `entity_map`, and entities which aren't in it are used
+    /// as is. [`RecordedCommand::Spawn`] spawns a new entity if the recorded one isn't in
+    /// `entity_map`, and adds it to the map. Entities referenced by components are mapped too.
+    ///
+    /// Commands targeting entities which don't exist are skipped with a warning, as are removals
+    /// of component or resource types which aren't registered, since their type paths may come
+    /// from another build of the app.
+    ///
+    /// # Panics
+    ///
+    /// - If [`AppTypeRegistry`] is not present in `world`.
+    /// - If an inserted component or resource type isn't registered with [`ReflectComponent`] or
+    ///   [`ReflectResource`] type data.
+    pub fn apply(&self, world: &mut World, entity_map: &mut EntityHashMap<Entity>) {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        self.apply_with_registry(world, &registry.read(), entity_map);
+    }
+
+    /// Like [`RecordedCommand::apply`], but uses the given `registry`.
+    pub fn apply_with_registry(
+        &self,
+        world: &mut World,
+        registry: &TypeRegistry,
+        entity_map: &mut EntityHashMap<Entity>,
+    ) {
+        match self {
+            Self::Spawn { entity, components } => {
+                let target = match entity_map.get(entity) {
+                    Some(&target) => target,
+                    None => {
+                        let target = world.spawn_empty().id();
+                        entity_map.insert(*entity, target);
+                        target
+                    }
+                };
+                insert_components(world, registry, entity_map, target, components);
+            }
+            Self::Insert { entity, components } => {
+                let target = entity_map.get(entity).copied().unwrap_or(*entity);
+                insert_components(world, registry, entity_map, target, components);
+            }
+            Self::Remove { entity, components } => {
+                let target = entity_map.get(entity).copied().unwrap_or(*entity);
+                let Ok(mut target) = world.get_entity_mut(target) else {
+                    warn!("Could not remove components from {entity}, as it does not exist");
+                    return;
+                };
+                for type_path in components {
+                    let Some(reflect_component) = registry
+                        .get_with_type_path(type_path)
+                        .and_then(|registration| registration.data::<ReflectComponent>())
+                    else {
+                        warn!(
+                            "Could not remove `{type_path}` from {entity}, as it isn't registered \
+                            with #[reflect(Component)]"
+                        );
+                        continue;
+                    };
+                    reflect_component.remove(&mut target);
+                }
+            }
+            Self::Despawn { entity } => {
+                let target = entity_map.get(entity).copied().unwrap_or(*entity);
+                world.despawn(target);
+            }
+            Self::InsertResource { resource } => {
+                let registration = registration(registry, resource.as_ref());
+                let reflect_component = resource_registration(registration);
+                let component_id = reflect_component.register_component(world);
+                let target = match world.resource_entities().get(component_id) {
+                    Some(target) if world.get_entity(target).is_ok() => target,
+                    _ => world.spawn_empty().id(),
+                };
+                reflect_component.insert(
+                    &mut world.entity_mut(target),
+                    resource.as_ref(),
+                    registry,
+                );
+            }
+            Self::RemoveResource { resource } => {
+                let Some(reflect_component) = registry
+                    .get_with_type_path(resource)
+                    .filter(|registration| registration.data::<ReflectResource>().is_some())
+                    .and_then(|registration| registration.data::<ReflectComponent>())
+                else {
+                    warn!(
+                        "Could not remove `{resource}`, as it isn't registered with \
+                        #[reflect(Resource)]"
+                    );
+                    return;
+                };
+                let component_id = reflect_component.register_component(world);
+                world.remove_resource_by_id(component_id);
+            }
+        }
+    }
+}
+
+/// Applies the command to the world it was recorded in, then appends it to the
+/// [`CommandRecording`] if there is one.
+impl Command for RecordedCommand {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        // The recorded entities belong to this world, including the spawned one,
+        // which was reserved when the command was queued.
+        let mut entity_map = EntityHashMap::default();
+        if let Self::Spawn { entity, .. } = &self {
+            entity_map.insert(*entity, *entity);
+        }
+        RecordedCommand::apply(&self, world, &mut entity_map);
+        if let Some(mut recording) = world.get_resource_mut::<CommandRecording>() {
+            recording.commands.push(self);
+        }
+    }
+}
+
+fn insert_components(
+    world: &mut World,
+    registry: &TypeRegistry,
+    entity_map: &mut EntityHashMap<Entity>,
+    target: Entity,
+    components: &[Box<dyn PartialReflect>],
+) {
+    let Ok(mut target) = world.get_entity_mut(target) else {
+        warn!("Could not insert components into {target}, as it does not exist");
+        return;
+    };
+    for component in components {
+        let registration = registration(registry, component.as_ref());
+        let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+            panic!(
+                "`{}` should have #[reflect(Component)]",
+                registration.type_info().type_path()
+            );
+        };
+        reflect_component.apply_or_insert_mapped(
+            &mut target,
+            component.as_ref(),
+            registry,
+            entity_map,
+            RelationshipHookMode::Run,
+        );
+    }
+}
+
+fn registration<'a>(
+    registry: &'a TypeRegistry,
+    value: &dyn PartialReflect,
+) -> &'a TypeRegistration {
+    let type_info = value
+        .get_represented_type_info()
+        .expect("recorded values should represent a type");
+    let Some(registration) = registry.get(type_info.type_id()) else {
+        let type_path = type_info.type_path();
+        panic!("`{type_path}` should be registered in the type registry");
+    };
+    registration
+}
+
+fn resource_registration(registration: &TypeRegistration) -> &ReflectComponent {
+    // `ReflectResource` implies `ReflectComponent`, which is used to access the resource.
+    match (
+        registration.data::<ReflectResource>(),
+        registration.data::<ReflectComponent>(),
+    ) {
+        (Some(_), Some(reflect_component)) => reflect_component,
+        _ => panic!(
+            "`{}` should have #[reflect(Resource)]",
+            registration.type_info().type_path()
+        ),
+    }
+}
+
+/// A [`Resource`] which records every [`RecordedCommand`] applied to the world, in order.
+///
+/// Recording starts when the resource is inserted, and stops when it is removed.
+/// See the [module docs](self) for more information.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct CommandRecording {
+    commands: Vec<RecordedCommand>,
+}
+
+impl CommandRecording {
+    /// Creates a recording of the given commands.
+    pub fn new(commands: Vec<RecordedCommand>) -> Self {
+        Self { commands }
+    }
+
+    /// Returns the recorded commands, in the order they were applied.
+    pub fn commands(&self) -> &[RecordedCommand] {
+        &self.commands
+    }
+
+    /// Removes and returns the recorded commands, leaving the recording empty.
+    pub fn take(&mut self) -> Vec<RecordedCommand> {
+        core::mem::take(&mut self.commands)
+    }
+
+    /// Returns the number of recorded commands.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns `true` if no command was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Applies every recorded command to `world`, in order.
+    ///
+    /// See [`RecordedCommand::apply`] for how entities are mapped.
+    pub fn apply(&self, world: &mut World, entity_map: &mut EntityHashMap<Entity>) {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        for command in &self.commands {
+            command.apply_with_registry(world, &registry, entity_map);
+        }
+    }
+}
+
+/// An extension trait for [`Commands`] to queue [`RecordedCommand`]s.
+pub trait RecordCommandsExt {
+    /// Spawns an entity with the given reflected components, and records it.
+    fn spawn_recorded(&mut self, components: Vec<Box<dyn PartialReflect>>) -> EntityCommands<'_>;
+
+    /// Inserts the given reflected resource, and records it.
+    fn insert_resource_recorded(&mut self, resource: Box<dyn PartialReflect>);
+
+    /// Removes the resource with the given type path, and records it.
+    fn remove_resource_recorded(&mut self, resource: impl Into<Cow<'static, str>>);
+}
+
+impl RecordCommandsExt for Commands<'_, '_> {
+    fn spawn_recorded(&mut self, components: Vec<Box<dyn PartialReflect>>) -> EntityCommands<'_> {
+        let entity = self.spawn_empty().id();
+        self.queue(RecordedCommand::Spawn { entity, components });
+        self.entity(entity)
+    }
+
+    fn insert_resource_recorded(&mut self, resource: Box<dyn PartialReflect>) {
+        self.queue(RecordedCommand::InsertResource { resource });
+    }
+
+    fn remove_resource_recorded(&mut self, resource: impl Into<Cow<'static, str>>) {
+        self.queue(RecordedCommand::RemoveResource {
+            resource: resource.into(),
+        });
+    }
+}
+
+/// An extension trait for [`EntityCommands`] to queue [`RecordedCommand`]s.
+pub trait RecordEntityCommandsExt {
+    /// Inserts the given reflected components into the entity, and records it.
+    fn insert_recorded(&mut self, components: Vec<Box<dyn PartialReflect>>) -> &mut Self;
+
+    /// Removes the components with the given type paths from the entity, and records it.
+    fn remove_recorded(&mut self, components: Vec<Cow<'static, str>>) -> &mut Self;
+
+    /// Despawns the entity, and records it.
+    fn despawn_recorded(&mut self);
+}
+
+impl RecordEntityCommandsExt for EntityCommands<'_> {
+    fn insert_recorded(&mut self, components: Vec<Box<dyn PartialReflect>>) -> &mut Self {
+        let entity = self.id();
+        self.commands()
+            .queue(RecordedCommand::Insert { entity, components });
+        self
+    }
+
+    fn remove_recorded(&mut self, components: Vec<Cow<'static, str>>) -> &mut Self {
+        let entity = self.id();
+        self.commands()
+            .queue(RecordedCommand::Remove { entity, components });
+        self
+    }
+
+    fn despawn_recorded(&mut self) {
+        let entity = self.id();
+        self.commands().queue(RecordedCommand::Despawn { entity });
+    }
+}
+
+#[cfg(feature = "serialize")]
+mod serde {
+    use super::{CommandRecording, RecordedCommand};
+    use crate::entity::Entity;
+    use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
+    use bevy_reflect::{
+        serde::{ReflectDeserializer, ReflectSerializer},
+        PartialReflect, TypeRegistry,
+    };
+    use core::fmt;
+    use serde::{
+        de::{DeserializeSeed, EnumAccess, Error, SeqAccess, VariantAccess, Visitor},
+        ser::SerializeSeq,
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    const RECORDED_COMMAND: &str = "RecordedCommand";
+    const VARIANTS: &[&str] = &[
+        "Spawn",
+        "Insert",
+        "Remove",
+        "Despawn",
+        "InsertResource",
+        "RemoveResource",
+    ];
+
+    impl CommandRecording {
+        /// Returns a [`Serialize`] implementation for the recording, which uses `registry` to
+        /// serialize the reflected components and resources.
+        ///
+        /// The recording can be deserialized with [`CommandRecordingDeserializer`].
+        pub fn serializer<'a>(
+            &'a self,
+            registry: &'a TypeRegistry,
+        ) -> CommandRecordingSerializer<'a> {
+            CommandRecordingSerializer {
+                recording: self,
+                registry,
+            }
+        }
+    }
+
+    /// Serializes a [`CommandRecording`]. Created by [`CommandRecording::serializer`].
+    pub struct CommandRecordingSerializer<'a> {
+        recording: &'a CommandRecording,
+        registry: &'a TypeRegistry,
+    }
+
+    impl Serialize for CommandRecordingSerializer<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(self.recording.len()))?;
+            for command in self.recording.commands() {
+                seq.serialize_element(&RecordedCommandSerializer {
+                    command,
+                    registry: self.registry,
+                })?;
+            }
+            seq.end()
+        }
+    }
+
+    struct RecordedCommandSerializer<'a> {
+        command: &'a RecordedCommand,
+        registry: &'a TypeRegistry,
+    }
+
+    impl Serialize for RecordedCommandSerializer<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let registry = self.registry;
+            match self.command {
+                RecordedCommand::Spawn { entity, components } => serializer
+                    .serialize_newtype_variant(
+                        RECORDED_COMMAND,
+                        0,
+                        VARIANTS[0],
+                        &(entity, ValuesSerializer(components, registry)),
+                    ),
+                RecordedCommand::Insert { entity, components } => serializer
+                    .serialize_newtype_variant(
+                        RECORDED_COMMAND,
+                        1,
+                        VARIANTS[1],
+                        &(entity, ValuesSerializer(components, registry)),
+                    ),
+                RecordedCommand::Remove { entity, components } => serializer
+                    .serialize_newtype_variant(
+                        RECORDED_COMMAND,
+                        2,
+                        VARIANTS[2],
+                        &(entity, components),
+                    ),
+                RecordedCommand::Despawn { entity } => {
+                    serializer.serialize_newtype_variant(RECORDED_COMMAND, 3, VARIANTS[3], entity)
+                }
+                RecordedCommand::InsertResource { resource } => serializer
+                    .serialize_newtype_variant(
+                        RECORDED_COMMAND,
+                        4,
+                        VARIANTS[4],
+                        &ReflectSerializer::new(resource.as_ref(), registry),
+                    ),
+                RecordedCommand::RemoveResource { resource } => {
+                    serializer.serialize_newtype_variant(RECORDED_COMMAND, 5, VARIANTS[5], resource)
+                }
+            }
+        }
+    }
+
+    struct ValuesSerializer<'a>(&'a [Box<dyn PartialReflect>], &'a TypeRegistry);
+
+    impl Serialize for ValuesSerializer<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+            for value in self.0 {
+                seq.serialize_element(&ReflectSerializer::new(value.as_ref(), self.1))?;
+            }
+            seq.end()
+        }
+    }
+
+    /// Deserializes a [`CommandRecording`] serialized with [`CommandRecording::serializer`],
+    /// using `registry` to deserialize the reflected components and resources.
+    pub struct CommandRecordingDeserializer<'a> {
+        /// The registry of the reflected components and resources.
+        pub registry: &'a TypeRegistry,
+    }
+
+    impl<'a> CommandRecordingDeserializer<'a> {
+        /// Creates a deserializer which uses the given `registry`.
+        pub fn new(registry: &'a TypeRegistry) -> Self {
+            Self { registry }
+        }
+    }
+
+    impl<'de> DeserializeSeed<'de> for CommandRecordingDeserializer<'_> {
+        type Value = CommandRecording;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer
+                .deserialize_seq(SeqVisitor {
+                    registry: self.registry,
+                    element: RecordedCommandDeserializer,
+                })
+                .map(CommandRecording::new)
+        }
+    }
+
+    /// A seed which deserializes an element of a sequence with access to the registry.
+    trait ElementSeed: Copy {
+        type Value;
+
+        fn deserialize<'de, D: Deserializer<'de>>(
+            self,
+            registry: &TypeRegistry,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error>;
+    }
+
+    #[derive(Clone, Copy)]
+    struct RecordedCommandDeserializer;
+
+    impl ElementSeed for RecordedCommandDeserializer {
+        type Value = RecordedCommand;
+
+        fn deserialize<'de, D: Deserializer<'de>>(
+            self,
+            registry: &TypeRegistry,
+            deserializer: D,
+        ) -> Result<RecordedCommand, D::Error> {
+            deserializer.deserialize_enum(
+                RECORDED_COMMAND,
+                VARIANTS,
+                RecordedCommandVisitor { registry },
+            )
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    struct ValueDeserializer;
+
+    impl ElementSeed for ValueDeserializer {
+        type Value = Box<dyn PartialReflect>;
+
+        fn deserialize<'de, D: Deserializer<'de>>(
+            self,
+            registry: &TypeRegistry,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            ReflectDeserializer::new(registry).deserialize(deserializer)
+        }
+    }
+
+    struct WithRegistry<'a, T> {
+        registry: &'a TypeRegistry,
+        element: T,
+    }
+
+    impl<'de, T: ElementSeed> DeserializeSeed<'de> for WithRegistry<'_, T> {
+        type Value = T::Value;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            self.element.deserialize(self.registry, deserializer)
+        }
+    }
+
+    struct SeqVisitor<'a, T> {
+        registry: &'a TypeRegistry,
+        element: T,
+    }
+
+    impl<'de, T: ElementSeed> Visitor<'de> for SeqVisitor<'_, T> {
+        type Value = Vec<T::Value>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a sequence")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut values = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+            while let Some(value) = seq.next_element_seed(WithRegistry {
+                registry: self.registry,
+                element: self.element,
+            })? {
+                values.push(value);
+            }
+            Ok(values)
+        }
+    }
+
+    impl<'de, T: ElementSeed> DeserializeSeed<'de> for SeqVisitor<'_, T> {
+        type Value = Vec<T::Value>;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_seq(self)
+        }
+    }
+
+    /// Deserializes an `(Entity, Vec<Box<dyn PartialReflect>>)` tuple.
+    struct EntityValuesVisitor<'a> {
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'de> Visitor<'de> for EntityValuesVisitor<'_> {
+        type Value = (Entity, Vec<Box<dyn PartialReflect>>);
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an entity and its reflected components")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let entity = seq
+                .next_element()?
+                .ok_or_else(|| Error::invalid_length(0, &self))?;
+            let values = seq
+                .next_element_seed(SeqVisitor {
+                    registry: self.registry,
+                    element: ValueDeserializer,
+                })?
+                .ok_or_else(|| Error::invalid_length(1, &self))?;
+            Ok((entity, values))
+        }
+    }
+
+    impl<'de> DeserializeSeed<'de> for EntityValuesVisitor<'_> {
+        type Value = (Entity, Vec<Box<dyn PartialReflect>>);
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_tuple(2, self)
+        }
+    }
+
+    #[derive(Deserialize)]
+    enum Variant {
+        Spawn,
+        Insert,
+        Remove,
+        Despawn,
+        InsertResource,
+        RemoveResource,
+    }
+
+    struct RecordedCommandVisitor<'a> {
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'de> Visitor<'de> for RecordedCommandVisitor<'_> {
+        type Value = RecordedCommand;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a recorded command")
+        }
+
+        fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+            let registry = self.registry;
+            let (variant, access) = data.variant::<Variant>()?;
+            Ok(match variant {
+                Variant::Spawn => {
+                    let (entity, components) =
+                        access.newtype_variant_seed(EntityValuesVisitor { registry })?;
+                    RecordedCommand::Spawn { entity, components }
+                }
+                Variant::Insert => {
+                    let (entity, components) =
+                        access.newtype_variant_seed(EntityValuesVisitor { registry })?;
+                    RecordedCommand::Insert { entity, components }
+                }
+                Variant::Remove => {
+                    let (entity, components): (Entity, Vec<String>) = access.newtype_variant()?;
+                    RecordedCommand::Remove {
+                        entity,
+                        components: components.into_iter().map(Cow::Owned).collect(),
+                    }
+                }
+                Variant::Despawn => RecordedCommand::Despawn {
+                    entity: access.newtype_variant()?,
+                },
+                Variant::InsertResource => RecordedCommand::InsertResource {
+                    resource: access.newtype_variant_seed(ReflectDeserializer::new(registry))?,
+                },
+                Variant::RemoveResource => RecordedCommand::RemoveResource {
+                    resource: Cow::Owned(access.newtype_variant::<String>()?),
+                },
+            })
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+pub use self::serde::{CommandRecordingDeserializer, CommandRecordingSerializer};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, reflect::ReflectResource};
+    use alloc::vec;
+    use bevy_reflect::{Reflect, TypePath};
+
+    #[derive(Component, Reflect, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Component, Reflect, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Target(Entity);
+
+    #[derive(Resource, Reflect, PartialEq, Debug)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    fn world() -> World {
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Health>();
+            registry.register::<Target>();
+            registry.register::<Score>();
+        }
+        let mut world = World::new();
+        world.insert_resource(registry);
+        world
+    }
+
+    fn record(world: &mut World) -> CommandRecording {
+        world.init_resource::<CommandRecording>();
+
+        let mut commands = world.commands();
+        let a = commands.spawn_recorded(vec![Box::new(Health(10))]).id();
+        let b = commands.spawn_recorded(vec![Box::new(Health(20))]).id();
+        commands
+            .entity(a)
+            .insert_recorded(vec![Box::new(Health(5)), Box::new(Target(b))]);
+        commands.entity(b).despawn_recorded();
+        commands.insert_resource_recorded(Box::new(Score(1)));
+        commands.insert_resource_recorded(Box::new(Score(2)));
+        world.flush();
+
+        world.remove_resource::<CommandRecording>().unwrap()
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let mut world = world();
+        let recording = record(&mut world);
+        assert_eq!(recording.len(), 6);
+        assert_eq!(world.resource::<Score>(), &Score(2));
+
+        let mut replica = self::world();
+        // Offset the entities of the replica, so they differ from the recorded ones.
+        replica.spawn_empty();
+        let mut entity_map = EntityHashMap::default();
+        recording.apply(&mut replica, &mut entity_map);
+
+        let mut query = replica.query::<(&Health, &Target)>();
+        let (health, &Target(target)) = query.single(&replica).unwrap();
+        assert_eq!(health, &Health(5));
+        assert!(replica.get_entity(target).is_err());
+        assert_eq!(entity_map.len(), 2);
+        assert!(entity_map.values().any(|&entity| entity == target));
+        assert_eq!(replica.resource::<Score>(), &Score(2));
+
+        let a = *entity_map
+            .values()
+            .find(|&&entity| entity != target)
+            .unwrap();
+        let mut commands = replica.commands();
+        commands
+            .entity(a)
+            .remove_recorded(vec![Cow::Borrowed(Health::type_path())]);
+        commands.remove_resource_recorded(Score::type_path());
+        replica.flush();
+        assert!(!replica.entity(a).contains::<Health>());
+        assert!(!replica.contains_resource::<Score>());
+    }
+
+    #[test]
+    fn unregistered_removals_are_skipped() {
+        let mut world = world();
+        let entity = world.spawn(Health(1)).id();
+        world.insert_resource(Score(1));
+
+        let recording = CommandRecording::new(vec![
+            RecordedCommand::Remove {
+                entity,
+                components: vec![
+                    Cow::Borrowed("unknown::Component"),
+                    Cow::Borrowed(Health::type_path()),
+                ],
+            },
+            RecordedCommand::RemoveResource {
+                resource: Cow::Borrowed("unknown::Resource"),
+            },
+        ]);
+        recording.apply(&mut world, &mut EntityHashMap::default());
+        assert!(!world.entity(entity).contains::<Health>());
+        assert_eq!(world.resource::<Score>(), &Score(1));
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn serialize_recording() {
+        use ::serde::de::DeserializeSeed;
+
+        let mut world = world();
+        let recording = record(&mut world);
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+
+        let serialized = ron::to_string(&recording.serializer(&registry)).unwrap();
+        let mut deserializer = ron::Deserializer::from_str(&serialized).unwrap();
+        let deserialized = CommandRecordingDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(deserialized.len(), recording.len());
+        assert_eq!(
+            ron::to_string(&deserialized.serializer(&registry)).unwrap(),
+            serialized
+        );
+
+        let mut replica = self::world();
+        deserialized.apply(&mut replica, &mut EntityHashMap::default());
+        let mut query = replica.query::<&Health>();
+        assert_eq!(query.single(&replica).unwrap(), &Health(5));
+        assert_eq!(replica.resource::<Score>(), &Score(2));
+    }
+}
@@ -0,0 +1,595 @@
+use alloc::{format, vec::Vec};
+use bevy_utils::prelude::DebugName;
+use log::warn;
+
+use crate::{
+    change_detection::MaybeLocation,
+    component::{Component, Immutable, Mutable},
+    entity::Entity,
+    lifecycle::HookContext,
+    relationship::{RelationshipHookMode, RelationshipSourceCollection},
+    system::EntityCommand,
+    world::{DeferredWorld, EntityWorldMut, World},
+};
+
+/// A [`Component`] on a "source" [`Entity`] that references any number of "target" entities, creating a many-to-many
+/// relationship between them. Every [`ManyToMany`] component has a corresponding [`ManyToManyTarget`] component (and
+/// vice-versa), which exists on each target entity and contains the list of all the source entities that relate to it.
+///
+/// Like [`Relationship`](super::Relationship), the [`ManyToMany`] component is the "source of truth", and the
+/// [`ManyToManyTarget`] components reflect it: when a [`ManyToMany`] component is inserted, replaced or removed, the
+/// source entity is immediately added to or removed from the [`ManyToManyTarget`] of each of its targets. This is done
+/// via component hooks, so [`ManyToMany`] components are always immutable. Removing a [`ManyToManyTarget`] component
+/// (or despawning its entity) removes the entity from the [`ManyToMany`] component of each of its sources.
+///
+/// [`ManyToMany`] and [`ManyToManyTarget`] should always be derived via the [`Component`] trait to ensure the hooks are
+/// set up properly. The collection field of both components must be private, since it must not contain duplicates;
+/// create the source component with [`ManyToMany::from_targets`], and add or remove links with the methods of
+/// [`EntityWorldMut`] and [`EntityCommands`](crate::system::EntityCommands) such as
+/// [`add_many_to_many_targets`](EntityWorldMut::add_many_to_many_targets).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::relationship::{ManyToMany, ManyToManyTarget};
+/// #[derive(Component)]
+/// #[many_to_many(target = FactionMembers)]
+/// pub struct MemberOf(Vec<Entity>);
+///
+/// #[derive(Component)]
+/// #[many_to_many_target(relationship = MemberOf)]
+/// pub struct FactionMembers(Vec<Entity>);
+///
+/// let mut world = World::new();
+/// let rebels = world.spawn_empty().id();
+/// let traders = world.spawn_empty().id();
+/// let smuggler = world.spawn(MemberOf::from_targets([rebels, traders])).id();
+///
+/// let members = world.get::<FactionMembers>(traders).unwrap();
+/// assert_eq!(members.collection(), &[smuggler]);
+///
+/// world.entity_mut(smuggler).remove_many_to_many_targets::<MemberOf>(&[rebels]);
+/// assert!(world.get::<FactionMembers>(rebels).is_none());
+/// ```
+///
+/// When deriving [`ManyToManyTarget`], the `linked_despawn` attribute selects a [`LinkedDespawn`] policy:
+///
+/// ```
+/// # use bevy_ecs::component::Component;
+/// # use bevy_ecs::entity::Entity;
+/// #[derive(Component)]
+/// #[many_to_many(target = Holds)]
+/// pub struct InInventory(Vec<Entity>);
+///
+/// // Items held by no other inventory are despawned with the inventory.
+/// #[derive(Component)]
+/// #[many_to_many_target(relationship = InInventory, linked_despawn = orphans)]
+/// pub struct Holds(Vec<Entity>);
+/// ```
+///
+/// By default, an entity cannot be its own target; self-referential links are dropped with a warning. Use
+/// `#[many_to_many(target = X, allow_self_referential)]` to allow them.
+pub trait ManyToMany: Component<Mutability = Immutable> + Sized {
+    /// The [`Component`] added to the target entities of this relationship, which contains the list of all the source
+    /// entities that relate to the target.
+    type Target: ManyToManyTarget<Relationship = Self>;
+
+    /// The collection type that stores the target entities of this component.
+    type Collection: RelationshipSourceCollection;
+
+    /// If `true`, an entity is allowed to be one of its own targets.
+    const ALLOW_SELF_REFERENTIAL: bool = false;
+
+    /// Returns a reference to the stored [`ManyToMany::Collection`].
+    fn collection(&self) -> &Self::Collection;
+
+    /// Returns a mutable reference to the stored [`ManyToMany::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as modifying the internal collection could invalidate the
+    /// relationship. The collection should not contain duplicates.
+    fn collection_mut_risky(&mut self) -> &mut Self::Collection;
+
+    /// Creates a new [`ManyToMany`] component from the given [`ManyToMany::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, prefer [`ManyToMany::from_targets`].
+    /// The collection should not contain duplicates.
+    fn from_collection_risky(collection: Self::Collection) -> Self;
+
+    /// Creates this component relating to the given `targets`, ignoring duplicates.
+    fn from_targets(targets: impl IntoIterator<Item = Entity>) -> Self {
+        let mut collection = Self::Collection::new();
+        for target in targets {
+            if !collection.iter().any(|entity| entity == target) {
+                collection.add(target);
+            }
+        }
+        Self::from_collection_risky(collection)
+    }
+
+    /// Iterates the target entities of this component.
+    #[inline]
+    fn iter(&self) -> <Self::Collection as RelationshipSourceCollection>::SourceIter<'_> {
+        self.collection().iter()
+    }
+
+    /// Returns the number of target entities.
+    #[inline]
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns true if this component has no target.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+
+    /// Returns true if `entity` is one of the targets of this component.
+    #[inline]
+    fn contains(&self, entity: Entity) -> bool {
+        self.iter().any(|target| target == entity)
+    }
+
+    /// The `on_insert` component hook that adds the source entity to the [`ManyToManyTarget`] of each target.
+    fn on_insert(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            caller,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        if let RelationshipHookMode::Skip = relationship_hook_mode {
+            return;
+        }
+        let targets: Vec<Entity> = world.entity(entity).get::<Self>().unwrap().iter().collect();
+        let mut invalid = Vec::new();
+        for target in targets {
+            if !is_valid_target::<Self>(&world, entity, target, caller) {
+                invalid.push(target);
+                continue;
+            }
+            // Deferring is necessary for batch mode
+            world
+                .commands()
+                .entity(target)
+                .entry::<Self::Target>()
+                .and_modify(move |mut many_to_many_target| {
+                    many_to_many_target.collection_mut_risky().add(entity);
+                })
+                .or_insert_with(move || {
+                    let mut many_to_many_target = Self::Target::with_capacity(1);
+                    many_to_many_target.collection_mut_risky().add(entity);
+                    many_to_many_target
+                });
+        }
+
+        if !invalid.is_empty() {
+            let command = move |mut source: EntityWorldMut| {
+                modify_targets_without_hooks::<Self>(&mut source, |targets| {
+                    for target in &invalid {
+                        targets.remove(*target);
+                    }
+                });
+            };
+            world.commands().queue_silenced(command.with_entity(entity));
+        }
+    }
+
+    /// The `on_discard` component hook that removes the source entity from the [`ManyToManyTarget`] of each target.
+    // note: think of this as "on_drop"
+    fn on_discard(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        if let RelationshipHookMode::Skip = relationship_hook_mode {
+            return;
+        }
+        let targets: Vec<Entity> = world.entity(entity).get::<Self>().unwrap().iter().collect();
+        for target in targets {
+            remove_source::<Self>(&mut world, target, entity);
+        }
+    }
+}
+
+/// A [`Component`] containing the collection of entities that relate to this [`Entity`] via the associated
+/// [`ManyToMany`] type. See the [`ManyToMany`] documentation for more information.
+pub trait ManyToManyTarget: Component<Mutability = Mutable> + Sized {
+    /// What happens to the source entities when this entity is despawned.
+    ///
+    /// This defaults to [`LinkedDespawn::None`] when derived.
+    const LINKED_DESPAWN: LinkedDespawn;
+
+    /// The [`ManyToMany`] component that populates this [`ManyToManyTarget`] collection.
+    type Relationship: ManyToMany<Target = Self>;
+
+    /// The collection type that stores the source entities of this component.
+    type Collection: RelationshipSourceCollection;
+
+    /// Returns a reference to the stored [`ManyToManyTarget::Collection`].
+    fn collection(&self) -> &Self::Collection;
+
+    /// Returns a mutable reference to the stored [`ManyToManyTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as modifying the internal collection could invalidate the
+    /// relationship. The collection should not contain duplicates.
+    fn collection_mut_risky(&mut self) -> &mut Self::Collection;
+
+    /// Creates a new [`ManyToManyTarget`] from the given [`ManyToManyTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as constructing the internal collection could invalidate
+    /// the relationship. The collection should not contain duplicates.
+    fn from_collection_risky(collection: Self::Collection) -> Self;
+
+    /// Creates this [`ManyToManyTarget`] with the given pre-allocated entity capacity.
+    fn with_capacity(capacity: usize) -> Self {
+        let collection =
+            <Self::Collection as RelationshipSourceCollection>::with_capacity(capacity);
+        Self::from_collection_risky(collection)
+    }
+
+    /// Iterates the source entities stored in this collection.
+    #[inline]
+    fn iter(&self) -> <Self::Collection as RelationshipSourceCollection>::SourceIter<'_> {
+        self.collection().iter()
+    }
+
+    /// Returns the number of source entities.
+    #[inline]
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns true if no entity relates to this one.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+
+    /// The `on_discard` component hook that removes this entity from the [`ManyToMany`] component of each source.
+    // note: think of this as "on_drop"
+    fn on_discard(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        match relationship_hook_mode {
+            RelationshipHookMode::Run => {}
+            RelationshipHookMode::Skip | RelationshipHookMode::RunIfNotLinked => return,
+        }
+        let (entities, mut commands) = world.entities_and_commands();
+        let many_to_many_target = entities.get(entity).unwrap().get::<Self>().unwrap();
+        for source in many_to_many_target.iter() {
+            let command = move |mut source: EntityWorldMut| {
+                modify_targets_without_hooks::<Self::Relationship>(&mut source, |targets| {
+                    targets.remove(entity);
+                });
+            };
+            commands.queue_silenced(command.with_entity(source));
+        }
+    }
+
+    /// The `on_despawn` component hook that applies the [`LinkedDespawn`] policy of this component.
+    fn on_despawn(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let (entities, mut commands) = world.entities_and_commands();
+        let many_to_many_target = entities.get(entity).unwrap().get::<Self>().unwrap();
+        for source in many_to_many_target.iter() {
+            let despawn = match Self::LINKED_DESPAWN {
+                LinkedDespawn::None => false,
+                LinkedDespawn::All => true,
+                LinkedDespawn::Orphans => entities
+                    .get(source)
+                    .ok()
+                    .and_then(|source| source.get::<Self::Relationship>())
+                    .is_some_and(|relationship| relationship.iter().all(|target| target == entity)),
+            };
+            if despawn {
+                commands.entity(source).try_despawn();
+            }
+        }
+    }
+}
+
+/// What happens to the source entities of a [`ManyToManyTarget`] when its entity is despawned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LinkedDespawn {
+    /// The sources are unlinked from the despawned entity, and are otherwise left untouched.
+    #[default]
+    None,
+    /// All the sources are despawned.
+    All,
+    /// The sources which have no other target are despawned, the others are unlinked.
+    Orphans,
+}
+
+/// Returns whether `source` can be linked to `target` with `R`, logging a warning if not.
+pub(super) fn is_valid_target<R: ManyToMany>(
+    world: &World,
+    source: Entity,
+    target: Entity,
+    caller: MaybeLocation,
+) -> bool {
+    if !R::ALLOW_SELF_REFERENTIAL && target == source {
+        warn!(
+            "{}The {} relationship on entity {source:?} points to itself. The invalid link has been removed.\nIf this is intended behavior self-referential relations can be enabled with the allow_self_referential attribute: #[many_to_many(allow_self_referential)]",
+            caller.map(|location| format!("{location}: ")).unwrap_or_default(),
+            DebugName::type_name::<R>(),
+        );
+        return false;
+    }
+    if !world.entities().contains(target) {
+        warn!(
+            "{}The {} relationship on entity {source:?} relates to {target:?}, which does not exist. The invalid link has been removed.",
+            caller.map(|location| format!("{location}: ")).unwrap_or_default(),
+            DebugName::type_name::<R>(),
+        );
+        return false;
+    }
+    true
+}
+
+/// Adds `source` to the [`ManyToManyTarget`] of `target`, inserting that component if needed.
+pub(super) fn add_source<R: ManyToMany>(world: &mut World, target: Entity, source: Entity) {
+    let mut target = world.entity_mut(target);
+    if let Some(mut many_to_many_target) = target.get_mut::<R::Target>() {
+        many_to_many_target.collection_mut_risky().add(source);
+    } else {
+        let mut many_to_many_target = R::Target::with_capacity(1);
+        many_to_many_target.collection_mut_risky().add(source);
+        target.insert(many_to_many_target);
+    }
+}
+
+/// Removes `source` from the [`ManyToManyTarget`] of `target`, and removes that component if it ends up empty.
+pub(super) fn remove_source<R: ManyToMany>(
+    world: &mut DeferredWorld,
+    target: Entity,
+    source: Entity,
+) {
+    if let Ok(mut target_entity_mut) = world.get_entity_mut(target)
+        && let Some(mut many_to_many_target) = target_entity_mut.get_mut::<R::Target>()
+    {
+        many_to_many_target.collection_mut_risky().remove(source);
+        if many_to_many_target.is_empty() {
+            let command = |mut entity: EntityWorldMut| {
+                // This must check emptiness again, as the source could be linked back before the command runs.
+                if entity
+                    .get::<R::Target>()
+                    .is_some_and(ManyToManyTarget::is_empty)
+                {
+                    entity.remove::<R::Target>();
+                }
+            };
+            world.commands().queue_silenced(command.with_entity(target));
+        }
+    }
+}
+
+/// Runs `f` on the [`ManyToMany`] component of `source` without running its hooks, so without updating the
+/// targets, and removes the component if it ends up empty.
+pub(super) fn modify_targets_without_hooks<R: ManyToMany>(
+    source: &mut EntityWorldMut,
+    f: impl FnOnce(&mut R::Collection),
+) {
+    let id = source.id();
+    let is_empty = source.world_scope(|world| {
+        let is_empty = DeferredWorld::from(&mut *world)
+            .modify_component_with_relationship_hook_mode::<R, _>(
+                id,
+                RelationshipHookMode::Skip,
+                |relationship| {
+                    f(relationship.collection_mut_risky());
+                    relationship.is_empty()
+                },
+            )
+            .expect("entity access must be valid");
+        world.flush();
+        is_empty
+    });
+    if is_empty == Some(true) {
+        source.remove::<R>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        relationship::{ManyToMany, ManyToManyTarget},
+        system::RunSystemOnce,
+    };
+    use alloc::{vec, vec::Vec};
+
+    #[derive(Component)]
+    #[many_to_many(target = Members)]
+    struct MemberOf(Vec<Entity>);
+
+    #[derive(Component)]
+    #[many_to_many_target(relationship = MemberOf)]
+    struct Members(Vec<Entity>);
+
+    #[derive(Component)]
+    #[many_to_many(target = Holds)]
+    struct HeldBy(Vec<Entity>);
+
+    #[derive(Component)]
+    #[many_to_many_target(relationship = HeldBy, linked_despawn = orphans)]
+    struct Holds(Vec<Entity>);
+
+    #[derive(Component)]
+    #[many_to_many(target = Tagged)]
+    struct TaggedWith(Vec<Entity>);
+
+    #[derive(Component)]
+    #[many_to_many_target(relationship = TaggedWith, linked_despawn = all)]
+    struct Tagged(Vec<Entity>);
+
+    fn members(world: &World, entity: Entity) -> Option<Vec<Entity>> {
+        world
+            .get::<Members>(entity)
+            .map(|members| members.iter().collect())
+    }
+
+    fn member_of(world: &World, entity: Entity) -> Option<Vec<Entity>> {
+        world
+            .get::<MemberOf>(entity)
+            .map(|member_of| member_of.iter().collect())
+    }
+
+    #[test]
+    fn many_to_many_bookkeeping() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let x = world.spawn(MemberOf::from_targets([a, b, a])).id();
+        let y = world.spawn(MemberOf::from_targets([a])).id();
+
+        assert_eq!(member_of(&world, x), Some(vec![a, b]));
+        assert_eq!(members(&world, a), Some(vec![x, y]));
+        assert_eq!(members(&world, b), Some(vec![x]));
+
+        // Replacing the source component relinks it.
+        world.entity_mut(x).insert(MemberOf::from_targets([b]));
+        assert_eq!(members(&world, a), Some(vec![y]));
+        assert_eq!(members(&world, b), Some(vec![x]));
+
+        world
+            .entity_mut(x)
+            .add_many_to_many_targets::<MemberOf>(&[a, b, a]);
+        assert_eq!(member_of(&world, x), Some(vec![b, a]));
+        assert_eq!(members(&world, a), Some(vec![y, x]));
+        assert_eq!(members(&world, b), Some(vec![x]));
+
+        world
+            .entity_mut(x)
+            .remove_many_to_many_targets::<MemberOf>(&[b]);
+        assert_eq!(member_of(&world, x), Some(vec![a]));
+        assert_eq!(members(&world, b), None);
+
+        // Removing the target component unlinks all its sources.
+        world.entity_mut(a).remove::<Members>();
+        assert_eq!(member_of(&world, x), None);
+        assert_eq!(member_of(&world, y), None);
+
+        world
+            .entity_mut(b)
+            .add_many_to_many_sources::<MemberOf>(&[x, y]);
+        assert_eq!(members(&world, b), Some(vec![x, y]));
+        world.despawn(x);
+        assert_eq!(members(&world, b), Some(vec![y]));
+        world.despawn(b);
+        assert_eq!(member_of(&world, y), None);
+    }
+
+    #[test]
+    fn many_to_many_invalid_links_are_dropped() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let despawned = world.spawn_empty().id();
+        world.despawn(despawned);
+
+        let x = world.spawn_empty().id();
+        world
+            .entity_mut(x)
+            .insert(MemberOf::from_targets([x, a, despawned]));
+        assert_eq!(member_of(&world, x), Some(vec![a]));
+        assert_eq!(members(&world, a), Some(vec![x]));
+
+        world
+            .entity_mut(x)
+            .add_many_to_many_targets::<MemberOf>(&[x, despawned]);
+        assert_eq!(member_of(&world, x), Some(vec![a]));
+        assert_eq!(members(&world, x), None);
+    }
+
+    #[test]
+    fn many_to_many_commands() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let x = world.spawn_empty().id();
+
+        world
+            .commands()
+            .entity(x)
+            .add_many_to_many_targets::<MemberOf>(&[a, b]);
+        world.flush();
+        assert_eq!(members(&world, a), Some(vec![x]));
+
+        world
+            .commands()
+            .entity(b)
+            .remove_many_to_many_sources::<MemberOf>(&[x]);
+        world.flush();
+        assert_eq!(member_of(&world, x), Some(vec![a]));
+        assert_eq!(members(&world, b), None);
+    }
+
+    #[test]
+    fn many_to_many_linked_despawn() {
+        let mut world = World::new();
+        let chest = world.spawn_empty().id();
+        let bag = world.spawn_empty().id();
+        let shared = world.spawn(HeldBy::from_targets([chest, bag])).id();
+        let only_in_chest = world.spawn(HeldBy::from_targets([chest])).id();
+
+        world.despawn(chest);
+        assert!(world.get_entity(only_in_chest).is_err());
+        assert_eq!(
+            world
+                .get::<HeldBy>(shared)
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            [bag]
+        );
+        world.despawn(bag);
+        assert!(world.get_entity(shared).is_err());
+
+        let tag = world.spawn_empty().id();
+        let other_tag = world.spawn_empty().id();
+        let tagged = world.spawn(TaggedWith::from_targets([tag, other_tag])).id();
+        world.despawn(tag);
+        assert!(world.get_entity(tagged).is_err());
+        assert!(world.get::<Tagged>(other_tag).is_none());
+    }
+
+    #[test]
+    fn many_to_many_query_traversal() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn(MemberOf::from_targets([a])).id();
+        let c = world.spawn(MemberOf::from_targets([a, b])).id();
+        // `a` closes a cycle through `c`.
+        world
+            .entity_mut(a)
+            .add_many_to_many_targets::<MemberOf>(&[c]);
+
+        world
+            .run_system_once(
+                move |member_of: Query<&MemberOf>, members: Query<&Members>| {
+                    assert_eq!(
+                        member_of.many_to_many_targets(c).collect::<Vec<_>>(),
+                        [a, b]
+                    );
+                    assert_eq!(members.many_to_many_sources(a).collect::<Vec<_>>(), [b, c]);
+                    assert_eq!(
+                        member_of.iter_many_to_many_reachable(b).collect::<Vec<_>>(),
+                        [a, c]
+                    );
+                },
+            )
+            .unwrap();
+    }
+}
@@ -1,6 +1,12 @@
 //! This module provides functionality to link entities to each other using specialized components called "relationships". See the [`Relationship`] trait for more info.
+//!
+//! Many-to-many relationships, where an entity can relate to any number of entities, are provided by the [`ManyToMany`] trait.
+//!
+//! Queries can follow a relationship to the related entities with [`Related`], [`AnyRelated`] and [`RelatedData`].
 
+mod many_to_many;
 mod related_methods;
+mod related_query;
 mod relationship_query;
 mod relationship_source_collection;
 
@@ -12,7 +18,9 @@ use core::{any::TypeId, marker::PhantomData};
 use alloc::format;
 
 use bevy_utils::prelude::DebugName;
+pub use many_to_many::{LinkedDespawn, ManyToMany, ManyToManyTarget};
 pub use related_methods::*;
+pub use related_query::{AnyRelated, Related, RelatedData};
 pub use relationship_query::*;
 pub use relationship_source_collection::*;
 
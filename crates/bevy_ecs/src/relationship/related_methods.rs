@@ -1,9 +1,11 @@
 use crate::{
     bundle::Bundle,
+    change_detection::MaybeLocation,
     entity::{hash_set::EntityHashSet, Entity},
     prelude::Children,
     relationship::{
-        Relationship, RelationshipHookMode, RelationshipSourceCollection, RelationshipTarget,
+        ManyToMany, Relationship, RelationshipHookMode, RelationshipSourceCollection,
+        RelationshipTarget,
     },
     system::{Commands, EntityCommands},
     world::{DeferredWorld, EntityWorldMut, World},
@@ -11,7 +13,10 @@ use crate::{
 use bevy_platform::prelude::{Box, Vec};
 use core::{marker::PhantomData, mem};
 
-use super::OrderedRelationshipSourceCollection;
+use super::{
+    many_to_many::{add_source, is_valid_target, modify_targets_without_hooks, remove_source},
+    OrderedRelationshipSourceCollection,
+};
 
 impl<'w> EntityWorldMut<'w> {
     /// Spawns an entity related to this entity (with the `R` relationship) by taking a bundle
@@ -372,6 +377,108 @@ impl<'w> EntityWorldMut<'w> {
         self
     }
 
+    /// Links this entity to the given `targets` with the [`ManyToMany`] relationship `R`.
+    ///
+    /// Targets this entity is already linked to are ignored.
+    #[track_caller]
+    pub fn add_many_to_many_targets<R: ManyToMany>(&mut self, targets: &[Entity]) -> &mut Self {
+        let Some(relationship) = self.get::<R>() else {
+            // The insert hook links the targets.
+            self.insert(R::from_targets(targets.iter().copied()));
+            return self;
+        };
+
+        let mut new_targets: Vec<Entity> = Vec::with_capacity(targets.len());
+        for &target in targets {
+            if !relationship.contains(target) && !new_targets.contains(&target) {
+                new_targets.push(target);
+            }
+        }
+        let id = self.id();
+        let caller = MaybeLocation::caller();
+        new_targets.retain(|&target| is_valid_target::<R>(self.world(), id, target, caller));
+        if new_targets.is_empty() {
+            return self;
+        }
+
+        // Re-inserting the component would unlink and relink every existing target,
+        // so the new targets are added without running its hooks and linked here instead.
+        modify_targets_without_hooks::<R>(self, |collection| {
+            for &target in &new_targets {
+                collection.add(target);
+            }
+        });
+        self.world_scope(|world| {
+            for target in new_targets {
+                add_source::<R>(world, target, id);
+            }
+        });
+        self
+    }
+
+    /// Unlinks this entity from the given `targets` of the [`ManyToMany`] relationship `R`.
+    ///
+    /// The `R` component is removed if no target is left.
+    pub fn remove_many_to_many_targets<R: ManyToMany>(&mut self, targets: &[Entity]) -> &mut Self {
+        let Some(relationship) = self.get::<R>() else {
+            return self;
+        };
+
+        let mut removed_targets: Vec<Entity> = Vec::with_capacity(targets.len());
+        for &target in targets {
+            if relationship.contains(target) && !removed_targets.contains(&target) {
+                removed_targets.push(target);
+            }
+        }
+        if removed_targets.is_empty() {
+            return self;
+        }
+
+        let id = self.id();
+        modify_targets_without_hooks::<R>(self, |collection| {
+            for &target in &removed_targets {
+                collection.remove(target);
+            }
+        });
+        self.world_scope(|world| {
+            let mut deferred_world = DeferredWorld::from(&mut *world);
+            for target in removed_targets {
+                remove_source::<R>(&mut deferred_world, target, id);
+            }
+            world.flush();
+        });
+        self
+    }
+
+    /// Links the given `sources` to this entity with the [`ManyToMany`] relationship `R`.
+    ///
+    /// Sources already linked to this entity are ignored.
+    #[track_caller]
+    pub fn add_many_to_many_sources<R: ManyToMany>(&mut self, sources: &[Entity]) -> &mut Self {
+        let id = self.id();
+        self.world_scope(|world| {
+            for &source in sources {
+                world
+                    .entity_mut(source)
+                    .add_many_to_many_targets::<R>(&[id]);
+            }
+        });
+        self
+    }
+
+    /// Unlinks the given `sources` from this entity for the [`ManyToMany`] relationship `R`.
+    pub fn remove_many_to_many_sources<R: ManyToMany>(&mut self, sources: &[Entity]) -> &mut Self {
+        let id = self.id();
+        self.world_scope(|world| {
+            for &source in sources {
+                if let Ok(mut source) = world.get_entity_mut(source) {
+                    source.remove_many_to_many_targets::<R>(&[id]);
+                }
+            }
+        });
+        self
+    }
+
     fn modify_or_insert_relation_with_relationship_hook_mode<R: Relationship>(
         &mut self,
         entity: Entity,
@@ -558,6 +665,48 @@ impl<'a> EntityCommands<'a> {
             entity.remove_recursive::<S, B>();
         })
     }
+
+    /// Links this entity to the given `targets` with the [`ManyToMany`] relationship `R`.
+    ///
+    /// Targets this entity is already linked to are ignored.
+    pub fn add_many_to_many_targets<R: ManyToMany>(&mut self, targets: &[Entity]) -> &mut Self {
+        let targets: Box<[Entity]> = targets.into();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.add_many_to_many_targets::<R>(&targets);
+        })
+    }
+
+    /// Unlinks this entity from the given `targets` of the [`ManyToMany`] relationship `R`.
+    ///
+    /// The `R` component is removed if no target is left.
+    pub fn remove_many_to_many_targets<R: ManyToMany>(&mut self, targets: &[Entity]) -> &mut Self {
+        let targets: Box<[Entity]> = targets.into();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.remove_many_to_many_targets::<R>(&targets);
+        })
+    }
+
+    /// Links the given `sources` to this entity with the [`ManyToMany`] relationship `R`.
+    ///
+    /// Sources already linked to this entity are ignored.
+    pub fn add_many_to_many_sources<R: ManyToMany>(&mut self, sources: &[Entity]) -> &mut Self {
+        let sources: Box<[Entity]> = sources.into();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.add_many_to_many_sources::<R>(&sources);
+        })
+    }
+
+    /// Unlinks the given `sources` from this entity for the [`ManyToMany`] relationship `R`.
+    pub fn remove_many_to_many_sources<R: ManyToMany>(&mut self, sources: &[Entity]) -> &mut Self {
+        let sources: Box<[Entity]> = sources.into();
+
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.remove_many_to_many_sources::<R>(&sources);
+        })
+    }
 }
 
 /// Directly spawns related "source" entities with the given [`Relationship`], targeting
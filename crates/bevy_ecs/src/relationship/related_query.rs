@@ -0,0 +1,388 @@
+//! [`QueryFilter`]s and [`QueryData`] which follow a relationship one hop, to join the data of
+//! related entities declaratively rather than with nested [`Query::get`](crate::system::Query::get) calls.
+
+use crate::{
+    archetype::Archetype,
+    change_detection::Tick,
+    component::{ComponentId, Components},
+    entity::Entity,
+    query::{
+        EcsAccessType, FilteredAccess, FilteredAccessSet, IterQueryData, NestedQuery, QueryData,
+        QueryFilter, ReadOnlyQueryData, ReleaseStateQueryData, WorldQuery,
+    },
+    relationship::{Relationship, RelationshipTarget},
+    storage::{Table, TableRow},
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+use core::marker::PhantomData;
+
+/// Filter that selects entities whose [`Relationship`] `R` targets an entity matching the filter `F`.
+///
+/// This is the declarative equivalent of reading `R` and calling [`Query::contains`] on the target.
+/// Entities without `R`, or whose target doesn't exist, are never selected.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::relationship::Related;
+/// #
+/// # #[derive(Component)]
+/// # struct Enemy;
+/// # #[derive(Component)]
+/// # struct Health(u32);
+/// #
+/// // Damages the children of enemies.
+/// fn damage_enemy_children(mut query: Query<&mut Health, Related<ChildOf, With<Enemy>>>) {
+///     for mut health in &mut query {
+///         health.0 = health.0.saturating_sub(1);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(damage_enemy_children);
+/// ```
+///
+/// [`Query::contains`]: crate::system::Query::contains
+pub struct Related<R: Relationship, F: QueryFilter + 'static = ()>(PhantomData<(R, F)>);
+
+type RelatedInner<R, F> = (&'static R, NestedQuery<(), F>);
+
+/// Filter that selects entities whose [`RelationshipTarget`] `S` contains at least one entity
+/// matching the filter `F`.
+///
+/// This is the counterpart of [`Related`] for the target side of a relationship.
+/// Entities without `S` are never selected.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::relationship::AnyRelated;
+/// #
+/// # #[derive(Component)]
+/// # struct Enemy;
+/// #
+/// // Finds the entities with at least one enemy among their children.
+/// fn find_enemy_parents(query: Query<Entity, AnyRelated<Children, With<Enemy>>>) {
+///     for entity in &query {
+///         println!("{entity} has an enemy child");
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(find_enemy_parents);
+/// ```
+pub struct AnyRelated<S: RelationshipTarget, F: QueryFilter + 'static = ()>(PhantomData<(S, F)>);
+
+type AnyRelatedInner<S, F> = (&'static S, NestedQuery<(), F>);
+
+/// [`QueryData`] which fetches `D` from the target of the entity's [`Relationship`] `R`.
+///
+/// Entities without `R`, or whose target doesn't match `D` and `F`, are skipped.
+/// Wrap this in an [`Option`] to include them anyway.
+///
+/// Only read-only data can be fetched from the target, as several entities may share it.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::relationship::RelatedData;
+/// #
+/// # #[derive(Component)]
+/// # struct Enemy;
+/// # #[derive(Component)]
+/// # struct Team(u32);
+/// #
+/// // Copies the team of each enemy to its children which aren't enemies themselves.
+/// // `Without<Enemy>` makes the mutable access to `Team` disjoint from the read-only access to
+/// // the `Team` of the parents.
+/// fn inherit_team(
+///     mut query: Query<(&mut Team, RelatedData<ChildOf, &Team, With<Enemy>>), Without<Enemy>>,
+/// ) {
+///     for (mut team, parent_team) in &mut query {
+///         team.0 = parent_team.0;
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(inherit_team);
+/// ```
+pub struct RelatedData<
+    R: Relationship,
+    D: ReadOnlyQueryData + 'static,
+    F: QueryFilter + 'static = (),
+>(PhantomData<(R, D, F)>);
+
+type RelatedDataInner<R, D, F> = (&'static R, NestedQuery<D, F>);
+
+/// Implements [`WorldQuery`] for a relationship query type by delegating to its inner tuple.
+macro_rules! impl_delegated_world_query {
+    ($ty:ty, $inner:ty, [$($generics:tt)*]) => {
+        // SAFETY: Every method delegates to the sound implementation of the inner tuple,
+        // including `init_nested_access`, which registers the access of the nested query.
+        unsafe impl<$($generics)*> WorldQuery for $ty {
+            type Fetch<'w> = <$inner as WorldQuery>::Fetch<'w>;
+            type State = <$inner as WorldQuery>::State;
+
+            fn shrink_fetch<'wlong: 'wshort, 'wshort>(
+                fetch: Self::Fetch<'wlong>,
+            ) -> Self::Fetch<'wshort> {
+                <$inner as WorldQuery>::shrink_fetch(fetch)
+            }
+
+            #[inline]
+            unsafe fn init_fetch<'w, 's>(
+                world: UnsafeWorldCell<'w>,
+                state: &'s Self::State,
+                last_run: Tick,
+                this_run: Tick,
+            ) -> Self::Fetch<'w> {
+                // SAFETY: The invariants are upheld by the caller.
+                unsafe { <$inner as WorldQuery>::init_fetch(world, state, last_run, this_run) }
+            }
+
+            const IS_DENSE: bool = <$inner as WorldQuery>::IS_DENSE;
+
+            #[inline]
+            unsafe fn set_archetype<'w, 's>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &'s Self::State,
+                archetype: &'w Archetype,
+                table: &'w Table,
+            ) {
+                // SAFETY: The invariants are upheld by the caller.
+                unsafe { <$inner as WorldQuery>::set_archetype(fetch, state, archetype, table) }
+            }
+
+            #[inline]
+            unsafe fn set_table<'w, 's>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &'s Self::State,
+                table: &'w Table,
+            ) {
+                // SAFETY: The invariants are upheld by the caller.
+                unsafe { <$inner as WorldQuery>::set_table(fetch, state, table) }
+            }
+
+            fn update_component_access(state: &Self::State, access: &mut FilteredAccess) {
+                <$inner as WorldQuery>::update_component_access(state, access);
+            }
+
+            fn init_nested_access(
+                state: &Self::State,
+                system_name: Option<&str>,
+                component_access_set: &mut FilteredAccessSet,
+                world: UnsafeWorldCell,
+            ) {
+                <$inner as WorldQuery>::init_nested_access(
+                    state,
+                    system_name,
+                    component_access_set,
+                    world,
+                );
+            }
+
+            fn init_state(world: &mut World) -> Self::State {
+                <$inner as WorldQuery>::init_state(world)
+            }
+
+            fn get_state(components: &Components) -> Option<Self::State> {
+                <$inner as WorldQuery>::get_state(components)
+            }
+
+            fn matches_component_set(
+                state: &Self::State,
+                set_contains_id: &impl Fn(ComponentId) -> bool,
+            ) -> bool {
+                <$inner as WorldQuery>::matches_component_set(state, set_contains_id)
+            }
+
+            fn update_archetypes(state: &mut Self::State, world: UnsafeWorldCell) {
+                <$inner as WorldQuery>::update_archetypes(state, world);
+            }
+        }
+    };
+}
+
+impl_delegated_world_query!(Related<R, F>, RelatedInner<R, F>, [R: Relationship, F: QueryFilter + 'static]);
+impl_delegated_world_query!(AnyRelated<S, F>, AnyRelatedInner<S, F>, [S: RelationshipTarget, F: QueryFilter + 'static]);
+impl_delegated_world_query!(
+    RelatedData<R, D, F>,
+    RelatedDataInner<R, D, F>,
+    [R: Relationship, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static]
+);
+
+// SAFETY: The inner tuple only reads `R` and performs read-only access through the nested query.
+unsafe impl<R: Relationship, F: QueryFilter + 'static> QueryFilter for Related<R, F> {
+    const IS_ARCHETYPAL: bool = false;
+
+    #[inline]
+    unsafe fn filter_fetch(
+        state: &Self::State,
+        fetch: &mut Self::Fetch<'_>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> bool {
+        // SAFETY: The invariants are upheld by the caller.
+        let Some((relationship, targets)) =
+            (unsafe { <RelatedInner<R, F> as QueryData>::fetch(state, fetch, entity, table_row) })
+        else {
+            return false;
+        };
+        targets.contains(relationship.get())
+    }
+}
+
+// SAFETY: The inner tuple only reads `S` and performs read-only access through the nested query.
+unsafe impl<S: RelationshipTarget, F: QueryFilter + 'static> QueryFilter for AnyRelated<S, F> {
+    const IS_ARCHETYPAL: bool = false;
+
+    #[inline]
+    unsafe fn filter_fetch(
+        state: &Self::State,
+        fetch: &mut Self::Fetch<'_>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> bool {
+        // SAFETY: The invariants are upheld by the caller.
+        let Some((relationship_target, sources)) = (unsafe {
+            <AnyRelatedInner<S, F> as QueryData>::fetch(state, fetch, entity, table_row)
+        }) else {
+            return false;
+        };
+        relationship_target
+            .iter()
+            .any(|source| sources.contains(source))
+    }
+}
+
+// SAFETY:
+// `Self::ReadOnly` is `Self`, and all access is read-only.
+// Everything but `fetch` delegates to the inner tuple, which has the same access.
+unsafe impl<R: Relationship, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static> QueryData
+    for RelatedData<R, D, F>
+{
+    const IS_READ_ONLY: bool = true;
+    // `fetch` returns `None` when the target doesn't match the nested query.
+    const IS_ARCHETYPAL: bool = false;
+    type ReadOnly = Self;
+    type Item<'w, 's> = D::Item<'w, 's>;
+
+    fn shrink<'wlong: 'wshort, 'wshort, 's>(
+        item: Self::Item<'wlong, 's>,
+    ) -> Self::Item<'wshort, 's> {
+        D::shrink(item)
+    }
+
+    #[inline]
+    unsafe fn fetch<'w, 's>(
+        state: &'s Self::State,
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Option<Self::Item<'w, 's>> {
+        // SAFETY: The invariants are upheld by the caller.
+        let (relationship, targets) = unsafe {
+            <RelatedDataInner<R, D, F> as QueryData>::fetch(state, fetch, entity, table_row)
+        }?;
+        // `get_inner` returns the item for the full `'w` lifetime.
+        targets.get_inner(relationship.get()).ok()
+    }
+
+    fn iter_access(state: &Self::State) -> impl Iterator<Item = EcsAccessType<'_>> {
+        <RelatedDataInner<R, D, F> as QueryData>::iter_access(state)
+    }
+}
+
+// SAFETY: All access is read-only.
+unsafe impl<R: Relationship, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static>
+    ReadOnlyQueryData for RelatedData<R, D, F>
+{
+}
+
+// SAFETY: All access to other entities is read-only, so it may alias.
+unsafe impl<R: Relationship, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static> IterQueryData
+    for RelatedData<R, D, F>
+{
+}
+
+impl<
+        R: Relationship,
+        D: ReadOnlyQueryData + ReleaseStateQueryData + 'static,
+        F: QueryFilter + 'static,
+    > ReleaseStateQueryData for RelatedData<R, D, F>
+{
+    fn release_state<'w>(item: Self::Item<'w, '_>) -> Self::Item<'w, 'static> {
+        D::release_state(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, system::RunSystemOnce};
+    use alloc::vec::Vec;
+
+    #[derive(Component)]
+    struct Enemy;
+
+    #[derive(Component, PartialEq, Debug)]
+    struct Team(u32);
+
+    #[test]
+    fn related_filters() {
+        let mut world = World::new();
+        let enemy = world.spawn((Enemy, Team(1))).id();
+        let friend = world.spawn(Team(2)).id();
+        let enemy_child = world.spawn(ChildOf(enemy)).id();
+        let friend_child = world.spawn(ChildOf(friend)).id();
+
+        let mut query = world.query_filtered::<Entity, Related<ChildOf, With<Enemy>>>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [enemy_child]);
+
+        let mut query = world.query_filtered::<Entity, AnyRelated<Children, Without<Enemy>>>();
+        assert_eq!(
+            query.iter(&world).collect::<Vec<_>>(),
+            [enemy, friend],
+            "both parents have a child without `Enemy`"
+        );
+
+        world.entity_mut(friend_child).insert(Enemy);
+        let mut query = world.query_filtered::<Entity, AnyRelated<Children, With<Enemy>>>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [friend]);
+    }
+
+    #[test]
+    fn related_data() {
+        let mut world = World::new();
+        let enemy = world.spawn((Enemy, Team(1))).id();
+        let friend = world.spawn(Team(2)).id();
+        let enemy_child = world.spawn((ChildOf(enemy), Team(0))).id();
+        let friend_child = world.spawn((ChildOf(friend), Team(0))).id();
+
+        world
+            .run_system_once(
+                |mut query: Query<
+                    (&mut Team, RelatedData<ChildOf, &Team, With<Enemy>>),
+                    Without<Enemy>,
+                >| {
+                    for (mut team, parent_team) in &mut query {
+                        team.0 = parent_team.0;
+                    }
+                },
+            )
+            .unwrap();
+        assert_eq!(world.get::<Team>(enemy_child), Some(&Team(1)));
+        assert_eq!(world.get::<Team>(friend_child), Some(&Team(0)));
+
+        let mut query = world.query::<Option<RelatedData<ChildOf, &Team>>>();
+        assert_eq!(query.get(&world, enemy).unwrap(), None);
+        assert_eq!(query.get(&world, enemy_child).unwrap(), Some(&Team(1)));
+        assert_eq!(query.get(&world, friend_child).unwrap(), Some(&Team(2)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn related_data_conflicts_with_mutable_access() {
+        let mut world = World::new();
+        world
+            .run_system_once(|_: Query<(&mut Team, RelatedData<ChildOf, &Team>)>| {})
+            .unwrap();
+    }
+}
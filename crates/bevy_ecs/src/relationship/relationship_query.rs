@@ -1,7 +1,7 @@
 use crate::{
-    entity::Entity,
+    entity::{Entity, EntityHashSet},
     query::{QueryData, QueryFilter},
-    relationship::{Relationship, RelationshipTarget},
+    relationship::{ManyToMany, ManyToManyTarget, Relationship, RelationshipTarget},
     system::Query,
 };
 use alloc::collections::VecDeque;
@@ -141,6 +141,47 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
     {
         AncestorIter::new(self, entity)
     }
+
+    /// If the given `entity` contains the `R` [`ManyToMany`] component, returns the target entities of that
+    /// relationship.
+    pub fn many_to_many_targets<R: ManyToMany>(
+        &'w self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + 'w
+    where
+        <D as QueryData>::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+    {
+        self.get(entity).into_iter().flat_map(ManyToMany::iter)
+    }
+
+    /// If the given `entity` contains the `T` [`ManyToManyTarget`] component, returns the source entities stored on
+    /// that component.
+    pub fn many_to_many_sources<T: ManyToManyTarget>(
+        &'w self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + 'w
+    where
+        <D as QueryData>::ReadOnly: QueryData<Item<'w, 's> = &'w T>,
+    {
+        self.get(entity)
+            .into_iter()
+            .flat_map(ManyToManyTarget::iter)
+    }
+
+    /// Iterates all entities reachable from the given `entity` by repeatedly following the targets of the `R`
+    /// [`ManyToMany`] relationship, in breadth-first order.
+    ///
+    /// Each entity is returned at most once, and `entity` itself is never returned, so this terminates even if
+    /// the relationship graph contains cycles.
+    pub fn iter_many_to_many_reachable<R: ManyToMany>(
+        &'w self,
+        entity: Entity,
+    ) -> ManyToManyReachableIter<'w, 's, D, F, R>
+    where
+        D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+    {
+        ManyToManyReachableIter::new(self, entity)
+    }
 }
 
 /// An [`Iterator`] of [`Entity`]s over the descendants of an [`Entity`].
@@ -270,3 +311,56 @@ where
         self.next
     }
 }
+
+/// An [`Iterator`] of [`Entity`]s over the entities reachable from an [`Entity`] through a [`ManyToMany`]
+/// relationship.
+///
+/// Traverses the relationship graph breadth-first, visiting each entity once.
+pub struct ManyToManyReachableIter<'w, 's, D: QueryData, F: QueryFilter, R: ManyToMany>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+{
+    query: &'w Query<'w, 's, D, F>,
+    visited: EntityHashSet,
+    vecdeque: VecDeque<Entity>,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, R: ManyToMany> ManyToManyReachableIter<'w, 's, D, F, R>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+{
+    /// Returns a new [`ManyToManyReachableIter`].
+    pub fn new(query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
+        let mut iter = ManyToManyReachableIter {
+            query,
+            visited: EntityHashSet::from_iter([entity]),
+            vecdeque: VecDeque::new(),
+        };
+        iter.visit_targets(entity);
+        iter
+    }
+
+    fn visit_targets(&mut self, entity: Entity) {
+        if let Ok(relationship) = self.query.get(entity) {
+            for target in relationship.iter() {
+                if self.visited.insert(target) {
+                    self.vecdeque.push_back(target);
+                }
+            }
+        }
+    }
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, R: ManyToMany> Iterator
+    for ManyToManyReachableIter<'w, 's, D, F, R>
+where
+    D::ReadOnly: QueryData<Item<'w, 's> = &'w R>,
+{
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entity = self.vecdeque.pop_front()?;
+        self.visit_targets(entity);
+        Some(entity)
+    }
+}
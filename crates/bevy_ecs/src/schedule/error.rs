@@ -10,8 +10,8 @@ use crate::{
             DagCrossDependencyError, DagOverlappingGroupError, DagRedundancyError,
             DiGraphToposortError, GraphNodeId,
         },
-        AmbiguousSystemConflictsWarning, ConflictingSystems, NodeId, ScheduleGraph, SystemKey,
-        SystemSetKey, SystemTypeSetAmbiguityError,
+        AmbiguousSystemConflictsWarning, ConflictingSystems, DeterministicOrderWarning, NodeId,
+        ScheduleGraph, SystemKey, SystemSetKey, SystemTypeSetAmbiguityError, SystemWithAccess,
     },
     world::World,
 };
@@ -72,6 +72,19 @@ pub enum ScheduleBuildWarning {
     /// [`LogLevel::Error`]: crate::schedule::LogLevel::Error
     #[error(transparent)]
     Ambiguity(#[from] AmbiguousSystemConflictsWarning),
+    /// Systems with conflicting access were ordered by their names, because
+    /// [`ScheduleBuildSettings::deterministic_order`] is set. Renaming one of these systems may
+    /// change their order.
+    ///
+    /// This warning is only reported if [`ScheduleBuildSettings::ambiguity_detection`] is not
+    /// [`LogLevel::Ignore`]. It is never upgraded to a [`ScheduleBuildError`], as the order of these
+    /// systems is deterministic.
+    ///
+    /// [`ScheduleBuildSettings::deterministic_order`]: crate::schedule::ScheduleBuildSettings::deterministic_order
+    /// [`ScheduleBuildSettings::ambiguity_detection`]: crate::schedule::ScheduleBuildSettings::ambiguity_detection
+    /// [`LogLevel::Ignore`]: crate::schedule::LogLevel::Ignore
+    #[error(transparent)]
+    DeterministicOrder(#[from] DeterministicOrderWarning),
 }
 
 impl ScheduleBuildError {
@@ -244,8 +257,10 @@ impl ScheduleBuildError {
             "{n_ambiguities} pairs of systems with conflicting data access have indeterminate execution order. \
             Consider adding `before`, `after`, or `ambiguous_with` relationships between these:\n",
         );
+        let explanations =
+            ambiguities.explain(|key| graph.systems.get(key).map(SystemWithAccess::access));
         let ambiguities = ambiguities.to_string(graph, components);
-        for (name_a, name_b, conflicts) in ambiguities {
+        for ((name_a, name_b, conflicts), explanations) in ambiguities.zip(explanations) {
             writeln!(message, " -- {name_a} and {name_b}").unwrap();
 
             if !conflicts.is_empty() {
@@ -255,7 +270,34 @@ impl ScheduleBuildError {
                 let world = core::any::type_name::<World>();
                 writeln!(message, "    conflict on: {world}").unwrap();
             }
+            for explanation in explanations {
+                writeln!(message, "      {}", explanation.display(components)).unwrap();
+            }
+        }
+        message
+    }
+
+    pub(crate) fn deterministic_order_to_string(
+        resolved: &ConflictingSystems,
+        graph: &ScheduleGraph,
+        components: &Components,
+    ) -> String {
+        let n_resolved = resolved.len();
+        let mut message = format!(
+            "{n_resolved} pairs of systems with conflicting data access were ordered by name. \
+            Renaming these systems may change their order:\n",
+        );
+        for (name_a, name_b, conflicts) in resolved.to_string(graph, components) {
+            writeln!(message, " -- {name_a} runs before {name_b}").unwrap();
+            if !conflicts.is_empty() {
+                writeln!(message, "    conflict on: {conflicts:?}").unwrap();
+            }
         }
+        #[cfg(not(feature = "debug"))]
+        message.push_str(
+            "System names are not available without the `debug` feature, so these systems were \
+            ordered by their `TypeId`, which may differ between builds and platforms.\n",
+        );
         message
     }
 
@@ -275,6 +317,13 @@ impl ScheduleBuildWarning {
             ScheduleBuildWarning::Ambiguity(AmbiguousSystemConflictsWarning(ambiguities)) => {
                 ScheduleBuildError::ambiguity_to_string(ambiguities, graph, world.components())
             }
+            ScheduleBuildWarning::DeterministicOrder(DeterministicOrderWarning(resolved)) => {
+                ScheduleBuildError::deterministic_order_to_string(
+                    resolved,
+                    graph,
+                    world.components(),
+                )
+            }
         }
     }
 }
@@ -1,12 +1,14 @@
 #[cfg(feature = "std")]
 mod multi_threaded;
 mod single_threaded;
+mod tracer;
 
 use alloc::{boxed::Box, vec, vec::Vec};
 use bevy_utils::prelude::DebugName;
 use core::any::TypeId;
 
 pub use self::single_threaded::SingleThreadedExecutor;
+pub use self::tracer::{RunningScheduleTracer, ScheduleTracer, ScheduleTracing, SystemSkipReason};
 
 #[cfg(feature = "std")]
 pub use self::multi_threaded::{MainThreadExecutor, MultiThreadedExecutor};
@@ -104,6 +106,8 @@ pub struct SystemSchedule {
     ///
     /// If a set doesn't run because of its conditions, this is used to skip all systems in it.
     pub(super) systems_in_sets_with_conditions: Vec<FixedBitSet>,
+    /// The tracer to report to while the schedule runs, if the world has [`ScheduleTracing`].
+    pub(super) tracer: Option<RunningScheduleTracer>,
 }
 
 impl SystemSchedule {
@@ -119,9 +123,17 @@ impl SystemSchedule {
             system_dependents: Vec::new(),
             sets_with_conditions_of_systems: Vec::new(),
             systems_in_sets_with_conditions: Vec::new(),
+            tracer: None,
         }
     }
 
+    /// Returns the tracer that executors should report to while running this schedule.
+    ///
+    /// This is only set while the schedule runs on a world with a [`ScheduleTracing`] resource.
+    pub fn tracer(&self) -> Option<&RunningScheduleTracer> {
+        self.tracer.as_ref()
+    }
+
     /// Accessor to allow running systems from a custom executor
     ///
     /// # Safety
@@ -326,9 +338,16 @@ mod __rust_begin_short_backtrace {
 
 #[cfg(test)]
 mod tests {
+    use alloc::{format, string::String, vec::Vec};
+    use bevy_platform::sync::{Arc, Mutex};
+    use bevy_utils::prelude::DebugName;
+
     use crate::{
         prelude::{Component, In, IntoSystem, Resource, Schedule},
-        schedule::{MultiThreadedExecutor, SingleThreadedExecutor},
+        schedule::{
+            InternedScheduleLabel, IntoScheduleConfigs, MultiThreadedExecutor, ScheduleTracer,
+            ScheduleTracing, SingleThreadedExecutor, SystemExecutor, SystemSet, SystemSkipReason,
+        },
         system::{Populated, Res, ResMut, Single},
         world::World,
     };
@@ -576,6 +595,90 @@ mod tests {
         let counter = world.resource::<Counter>();
         assert_eq!(counter.0, 0);
     }
+
+    #[derive(Default)]
+    struct RecordingTracer(Mutex<Vec<String>>);
+
+    impl ScheduleTracer for RecordingTracer {
+        fn schedule_started(&self, _schedule: InternedScheduleLabel) {
+            self.0.lock().unwrap().push("schedule started".into());
+        }
+
+        fn schedule_finished(&self, _schedule: InternedScheduleLabel) {
+            self.0.lock().unwrap().push("schedule finished".into());
+        }
+
+        fn system_started(
+            &self,
+            _schedule: InternedScheduleLabel,
+            system: usize,
+            _name: &DebugName,
+        ) {
+            self.0.lock().unwrap().push(format!("started {system}"));
+        }
+
+        fn system_finished(
+            &self,
+            _schedule: InternedScheduleLabel,
+            system: usize,
+            _name: &DebugName,
+        ) {
+            self.0.lock().unwrap().push(format!("finished {system}"));
+        }
+
+        fn system_skipped(
+            &self,
+            _schedule: InternedScheduleLabel,
+            system: usize,
+            _name: &DebugName,
+            reason: SystemSkipReason,
+        ) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("skipped {system} {}", reason.as_str()));
+        }
+    }
+
+    #[test]
+    fn tracer_receives_system_events_single_threaded() {
+        tracer_receives_system_events(SingleThreadedExecutor::new());
+    }
+
+    #[test]
+    fn tracer_receives_system_events_multi_threaded() {
+        tracer_receives_system_events(MultiThreadedExecutor::new());
+    }
+
+    fn tracer_receives_system_events(executor: impl SystemExecutor + 'static) {
+        #[derive(SystemSet, Hash, PartialEq, Eq, Debug, Clone)]
+        struct Disabled;
+
+        let mut world = World::new();
+        let tracer = Arc::new(RecordingTracer::default());
+        world.insert_resource(ScheduleTracing::from_arc(tracer.clone()));
+
+        let mut schedule = Schedule::default();
+        schedule.set_executor(executor);
+        schedule.configure_sets(Disabled.run_if(|| false));
+        schedule.add_systems(
+            (|| {}, (|| {}).run_if(|| false), (|| {}).in_set(Disabled)).chain_ignore_deferred(),
+        );
+        schedule.run(&mut world);
+
+        let events = tracer.0.lock().unwrap().clone();
+        assert_eq!(
+            events,
+            [
+                "schedule started",
+                "started 0",
+                "finished 0",
+                "skipped 1 system_conditions",
+                "skipped 2 set_conditions",
+                "schedule finished",
+            ]
+        );
+    }
 }
 
 #[cfg(test)]
@@ -22,7 +22,8 @@ use crate::{
     },
     prelude::Resource,
     schedule::{
-        is_apply_deferred, ConditionWithAccess, SystemExecutor, SystemSchedule, SystemWithAccess,
+        is_apply_deferred, ConditionWithAccess, RunningScheduleTracer, SystemExecutor,
+        SystemSchedule, SystemSkipReason, SystemWithAccess,
     },
     system::{BoxedSystem, RunSystemError, ScheduleSystem},
     world::{unsafe_world_cell::UnsafeWorldCell, World},
@@ -38,6 +39,7 @@ struct Environment<'env, 'sys> {
     systems: &'sys [SyncUnsafeCell<SystemWithAccess>],
     conditions: SyncUnsafeCell<Conditions<'sys>>,
     world_cell: UnsafeWorldCell<'env>,
+    tracer: Option<RunningScheduleTracer>,
 }
 
 struct Conditions<'a> {
@@ -55,6 +57,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
     ) -> Self {
         Environment {
             executor,
+            tracer: schedule.tracer.clone(),
             systems: SyncUnsafeCell::from_mut(schedule.systems.as_mut_slice()).as_slice_of_cells(),
             conditions: SyncUnsafeCell::new(Conditions {
                 system_conditions: &mut schedule.system_conditions,
@@ -267,6 +270,10 @@ impl SystemExecutor for MultiThreadedExecutor {
             for system_index in skipped_systems.ones() {
                 state.signal_dependents(system_index);
                 state.ready_systems.remove(system_index);
+                if let Some(tracer) = &schedule.tracer {
+                    let name = schedule.systems[system_index].system.name();
+                    tracer.system_skipped(system_index, &name, SystemSkipReason::Stepping);
+                }
             }
         }
 
@@ -503,8 +510,8 @@ impl ExecutorState {
 
                 // SAFETY: `can_run` returned true, which means that:
                 // - There can be no systems running whose accesses would conflict with any conditions.
-                if unsafe {
-                    !self.should_run(
+                if let Err(reason) = unsafe {
+                    self.should_run(
                         system_index,
                         system,
                         conditions,
@@ -512,6 +519,9 @@ impl ExecutorState {
                         context.error_handler,
                     )
                 } {
+                    if let Some(tracer) = &context.environment.tracer {
+                        tracer.system_skipped(system_index, &system.name(), reason);
+                    }
                     self.skip_system_and_signal_dependents(system_index);
                     // signal_dependents may have set more systems to ready.
                     check_for_new_ready_systems = true;
@@ -583,6 +593,9 @@ impl ExecutorState {
         true
     }
 
+    /// Evaluates the conditions of the system and of its sets, and returns why the system
+    /// should be skipped if it should not run.
+    ///
     /// # Safety
     /// * `world` must have permission to read any world data required by
     ///   the system's conditions: this includes conditions for the system
@@ -594,8 +607,8 @@ impl ExecutorState {
         conditions: &mut Conditions,
         world: UnsafeWorldCell,
         error_handler: ErrorHandler,
-    ) -> bool {
-        let mut should_run = !self.skipped_systems.contains(system_index);
+    ) -> Result<(), SystemSkipReason> {
+        let mut set_conditions_met = !self.skipped_systems.contains(system_index);
 
         for set_idx in conditions.sets_with_conditions_of_systems[system_index].ones() {
             if self.evaluated_sets.contains(set_idx) {
@@ -606,7 +619,7 @@ impl ExecutorState {
             // SAFETY:
             // - The caller ensures that `world` has permission to read any data
             //   required by the conditions.
-            let set_conditions_met = unsafe {
+            let conditions_met = unsafe {
                 evaluate_and_fold_conditions(
                     &mut conditions.set_conditions[set_idx],
                     world,
@@ -616,12 +629,12 @@ impl ExecutorState {
                 )
             };
 
-            if !set_conditions_met {
+            if !conditions_met {
                 self.skipped_systems
                     .union_with(&conditions.systems_in_sets_with_conditions[set_idx]);
             }
 
-            should_run &= set_conditions_met;
+            set_conditions_met &= conditions_met;
             self.evaluated_sets.insert(set_idx);
         }
 
@@ -643,9 +656,13 @@ impl ExecutorState {
             self.skipped_systems.insert(system_index);
         }
 
-        should_run &= system_conditions_met;
-
-        should_run
+        if !set_conditions_met {
+            Err(SystemSkipReason::SetConditions)
+        } else if !system_conditions_met {
+            Err(SystemSkipReason::SystemConditions)
+        } else {
+            Ok(())
+        }
     }
 
     /// # Safety
@@ -662,6 +679,11 @@ impl ExecutorState {
         let system_meta = &self.system_task_metadata[system_index];
 
         let task = async move {
+            let name = context.environment.tracer.as_ref().map(|tracer| {
+                let name = system.name();
+                tracer.system_started(system_index, &name);
+                name
+            });
             let res = handle_errors(
                 |system| {
                     // SAFETY:
@@ -679,6 +701,9 @@ impl ExecutorState {
                 context.error_handler,
                 "System panicked",
             );
+            if let (Some(tracer), Some(name)) = (&context.environment.tracer, &name) {
+                tracer.system_finished(system_index, name);
+            }
             context.system_completed(system_index, res, system);
         };
 
@@ -703,6 +728,11 @@ impl ExecutorState {
             let unapplied_systems = self.unapplied_systems.clone();
             self.unapplied_systems.clear();
             let task = async move {
+                let name = context.environment.tracer.as_ref().map(|tracer| {
+                    let name = system.name();
+                    tracer.system_started(system_index, &name);
+                    name
+                });
                 // SAFETY: `can_run` returned true for this system, which means
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
@@ -712,12 +742,20 @@ impl ExecutorState {
                     world,
                     context.error_handler,
                 );
+                if let (Some(tracer), Some(name)) = (&context.environment.tracer, &name) {
+                    tracer.system_finished(system_index, name);
+                }
                 context.system_completed(system_index, res, system);
             };
 
             context.scope.spawn_on_scope(task);
         } else {
             let task = async move {
+                let name = context.environment.tracer.as_ref().map(|tracer| {
+                    let name = system.name();
+                    tracer.system_started(system_index, &name);
+                    name
+                });
                 // SAFETY: `can_run` returned true for this system, which means
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
@@ -727,6 +765,9 @@ impl ExecutorState {
                     context.error_handler,
                     "Exclusive system panicked",
                 );
+                if let (Some(tracer), Some(name)) = (&context.environment.tracer, &name) {
+                    tracer.system_finished(system_index, name);
+                }
                 context.system_completed(system_index, res, system);
             };
 
@@ -19,6 +19,7 @@ use crate::{
     error::{ErrorContext, ErrorHandler},
     schedule::{
         is_apply_deferred, BoxedCondition, ConditionWithAccess, SystemExecutor, SystemSchedule,
+        SystemSkipReason,
     },
     system::{RunSystemError, ScheduleSystem},
     world::World,
@@ -69,6 +70,7 @@ impl SystemExecutor for SingleThreadedExecutor {
             // mark skipped systems as completed
             self.completed_systems |= skipped_systems;
         }
+        let tracer = schedule.tracer.clone();
 
         #[cfg(feature = "hotpatching")]
         let hotpatch_tick = world
@@ -84,14 +86,16 @@ impl SystemExecutor for SingleThreadedExecutor {
             #[cfg(feature = "trace")]
             let should_run_span = info_span!("check_conditions", name = name.to_string()).entered();
 
-            let mut should_run = !self.completed_systems.contains(system_index);
+            let skipped_before = self.completed_systems.contains(system_index);
+            let mut should_run = !skipped_before;
+            let mut set_conditions_met = true;
             for set_idx in schedule.sets_with_conditions_of_systems[system_index].ones() {
                 if self.evaluated_sets.contains(set_idx) {
                     continue;
                 }
 
                 // evaluate system set's conditions
-                let set_conditions_met = evaluate_and_fold_conditions(
+                let conditions_met = evaluate_and_fold_conditions(
                     &mut schedule.set_conditions[set_idx],
                     world,
                     error_handler,
@@ -99,12 +103,12 @@ impl SystemExecutor for SingleThreadedExecutor {
                     true,
                 );
 
-                if !set_conditions_met {
+                if !conditions_met {
                     self.completed_systems
                         .union_with(&schedule.systems_in_sets_with_conditions[set_idx]);
                 }
 
-                should_run &= set_conditions_met;
+                set_conditions_met &= conditions_met;
                 self.evaluated_sets.insert(set_idx);
             }
 
@@ -117,7 +121,7 @@ impl SystemExecutor for SingleThreadedExecutor {
                 false,
             );
 
-            should_run &= system_conditions_met;
+            should_run &= set_conditions_met && system_conditions_met;
 
             #[cfg(feature = "trace")]
             should_run_span.exit();
@@ -131,11 +135,34 @@ impl SystemExecutor for SingleThreadedExecutor {
             self.completed_systems.insert(system_index);
 
             if !should_run {
+                if let Some(tracer) = &tracer {
+                    #[cfg(feature = "bevy_debug_stepping")]
+                    let stepped = _skip_systems.is_some_and(|skip| skip.contains(system_index));
+                    #[cfg(not(feature = "bevy_debug_stepping"))]
+                    let stepped = false;
+                    let reason = if stepped {
+                        SystemSkipReason::Stepping
+                    } else if skipped_before || !set_conditions_met {
+                        SystemSkipReason::SetConditions
+                    } else {
+                        SystemSkipReason::SystemConditions
+                    };
+                    tracer.system_skipped(system_index, &system.name(), reason);
+                }
                 continue;
             }
 
+            let traced_name = tracer.as_ref().map(|tracer| {
+                let name = system.name();
+                tracer.system_started(system_index, &name);
+                name
+            });
+
             if is_apply_deferred(&**system) {
                 self.apply_deferred(schedule, world, error_handler);
+                if let (Some(tracer), Some(name)) = (&tracer, &traced_name) {
+                    tracer.system_finished(system_index, name);
+                }
                 continue;
             }
 
@@ -164,6 +191,10 @@ impl SystemExecutor for SingleThreadedExecutor {
                 (f)(system);
             }
 
+            if let (Some(tracer), Some(name)) = (&tracer, &traced_name) {
+                tracer.system_finished(system_index, name);
+            }
+
             self.unapplied_systems.insert(system_index);
         }
 
@@ -0,0 +1,126 @@
+use alloc::boxed::Box;
+use bevy_platform::sync::Arc;
+use bevy_utils::prelude::DebugName;
+
+use crate::{resource::Resource, schedule::InternedScheduleLabel};
+
+/// Why an executor did not run a system.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SystemSkipReason {
+    /// The system was skipped by [`Stepping`](crate::schedule::Stepping).
+    Stepping,
+    /// The conditions of one of the system's sets were not met.
+    SetConditions,
+    /// The system's own conditions were not met.
+    SystemConditions,
+}
+
+impl SystemSkipReason {
+    /// Returns a short, stable name for this reason.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Stepping => "stepping",
+            Self::SetConditions => "set_conditions",
+            Self::SystemConditions => "system_conditions",
+        }
+    }
+}
+
+/// Receives events from the [`SystemExecutor`](super::SystemExecutor) while schedules run.
+///
+/// Insert a [`ScheduleTracing`] resource to receive the events of every schedule run on a
+/// [`World`](crate::world::World). Systems are identified by their index in the
+/// [`SystemSchedule`](super::SystemSchedule), which is stable until the schedule is rebuilt.
+///
+/// The methods are called from the thread running the system, and while the system runs, so
+/// implementations should be cheap and must not block on other systems.
+pub trait ScheduleTracer: Send + Sync + 'static {
+    /// Called before the executor starts running the systems of `schedule`.
+    fn schedule_started(&self, schedule: InternedScheduleLabel);
+
+    /// Called after all systems of `schedule` have run or been skipped.
+    fn schedule_finished(&self, schedule: InternedScheduleLabel);
+
+    /// Called on the system's thread right before it runs.
+    fn system_started(&self, schedule: InternedScheduleLabel, system: usize, name: &DebugName);
+
+    /// Called on the system's thread right after it ran.
+    fn system_finished(&self, schedule: InternedScheduleLabel, system: usize, name: &DebugName);
+
+    /// Called when the executor decides not to run a system.
+    fn system_skipped(
+        &self,
+        schedule: InternedScheduleLabel,
+        system: usize,
+        name: &DebugName,
+        reason: SystemSkipReason,
+    );
+}
+
+/// A [`Resource`] which makes every schedule run on the world report to a [`ScheduleTracer`].
+#[derive(Resource, Clone)]
+pub struct ScheduleTracing(Arc<dyn ScheduleTracer>);
+
+impl ScheduleTracing {
+    /// Creates the resource from a tracer.
+    pub fn new(tracer: impl ScheduleTracer) -> Self {
+        // `portable-atomic-util` `Arc` can't coerce to an unsized type, but can be created
+        // from a `Box`.
+        let tracer: Box<dyn ScheduleTracer> = Box::new(tracer);
+        Self(Arc::from(tracer))
+    }
+
+    /// Creates the resource from a tracer that is shared with other code.
+    pub fn from_arc(tracer: Arc<dyn ScheduleTracer>) -> Self {
+        Self(tracer)
+    }
+
+    pub(crate) fn for_schedule(&self, schedule: InternedScheduleLabel) -> RunningScheduleTracer {
+        RunningScheduleTracer {
+            schedule,
+            tracer: self.0.clone(),
+        }
+    }
+}
+
+/// The [`ScheduleTracer`] of a schedule that is currently running.
+///
+/// Executors access it through [`SystemSchedule::tracer`](super::SystemSchedule::tracer).
+#[derive(Clone)]
+pub struct RunningScheduleTracer {
+    schedule: InternedScheduleLabel,
+    tracer: Arc<dyn ScheduleTracer>,
+}
+
+impl RunningScheduleTracer {
+    /// The label of the running schedule.
+    pub fn schedule(&self) -> InternedScheduleLabel {
+        self.schedule
+    }
+
+    /// Reports that the schedule started running.
+    pub fn schedule_started(&self) {
+        self.tracer.schedule_started(self.schedule);
+    }
+
+    /// Reports that the schedule finished running.
+    pub fn schedule_finished(&self) {
+        self.tracer.schedule_finished(self.schedule);
+    }
+
+    /// Reports that the system at index `system` is about to run.
+    pub fn system_started(&self, system: usize, name: &DebugName) {
+        self.tracer.system_started(self.schedule, system, name);
+    }
+
+    /// Reports that the system at index `system` finished running.
+    pub fn system_finished(&self, system: usize, name: &DebugName) {
+        self.tracer.system_finished(self.schedule, system, name);
+    }
+
+    /// Reports that the system at index `system` was skipped.
+    pub fn system_skipped(&self, system: usize, name: &DebugName, reason: SystemSkipReason) {
+        self.tracer
+            .system_skipped(self.schedule, system, name, reason);
+    }
+}
@@ -1,5 +1,6 @@
-use alloc::vec::Vec;
+use alloc::{collections::BinaryHeap, vec::Vec};
 use core::{
+    cmp::Reverse,
     fmt::{self, Debug},
     hash::{BuildHasher, Hash},
     ops::{Deref, DerefMut},
@@ -108,6 +109,64 @@ impl<N: GraphNodeId, S: BuildHasher> Dag<N, S> {
         Ok(&self.toposort)
     }
 
+    /// Recomputes the topological ordering so that nodes whose relative order
+    /// is not constrained by the graph are sorted by `key`, and returns it.
+    ///
+    /// Nodes with equal keys keep their order from [`Dag::toposort`]. The
+    /// result is the smallest topological ordering of the graph when comparing
+    /// nodes by their keys, and so only depends on the edges and the keys of
+    /// the graph, not on the order they were added in.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DiGraphToposortError`] if the DAG is dirty and cannot be
+    /// topologically sorted.
+    pub fn toposort_by_key<K: Ord>(
+        &mut self,
+        mut key: impl FnMut(N) -> K,
+    ) -> Result<&[N], DiGraphToposortError<N>> {
+        self.ensure_toposorted()?;
+
+        let positions = self
+            .toposort
+            .iter()
+            .enumerate()
+            .map(|(position, &node)| (node, position))
+            .collect::<HashMap<_, _>>();
+        let keys = self
+            .toposort
+            .iter()
+            .map(|&node| key(node))
+            .collect::<Vec<_>>();
+        let mut dependencies = self
+            .toposort
+            .iter()
+            .map(|&node| self.graph.neighbors_directed(node, Incoming).count())
+            .collect::<Vec<_>>();
+
+        let mut ready = dependencies
+            .iter()
+            .enumerate()
+            .filter(|&(_, &count)| count == 0)
+            .map(|(position, _)| Reverse((&keys[position], position)))
+            .collect::<BinaryHeap<_>>();
+        let mut sorted = Vec::with_capacity(self.toposort.len());
+        while let Some(Reverse((_, position))) = ready.pop() {
+            let node = self.toposort[position];
+            sorted.push(node);
+            for dependent in self.graph.neighbors_directed(node, Outgoing) {
+                let dependent = positions[&dependent];
+                dependencies[dependent] -= 1;
+                if dependencies[dependent] == 0 {
+                    ready.push(Reverse((&keys[dependent], dependent)));
+                }
+            }
+        }
+
+        self.toposort = sorted;
+        Ok(&self.toposort)
+    }
+
     /// Returns both the topological ordering and the underlying graph,
     /// computing the toposort if the graph is dirty.
     ///
@@ -783,6 +842,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn toposort_by_key() {
+        let mut dag = Dag::<TestNode>::new();
+        dag.add_edge(TestNode(3), TestNode(1));
+        dag.add_node(TestNode(2));
+        dag.add_node(TestNode(4));
+
+        assert_eq!(
+            dag.toposort_by_key(|node| node.0).unwrap(),
+            &[TestNode(2), TestNode(3), TestNode(1), TestNode(4)]
+        );
+        assert_eq!(
+            dag.toposort_by_key(|node| core::cmp::Reverse(node.0))
+                .unwrap(),
+            &[TestNode(4), TestNode(3), TestNode(2), TestNode(1)]
+        );
+    }
+
     #[test]
     fn analyze() {
         let mut dag1 = Dag::<TestNode>::new();
@@ -14,7 +14,7 @@ use crate::{
     change_detection::{CheckChangeTicks, Tick},
     component::{ComponentId, Components},
     prelude::{SystemIn, SystemSet},
-    query::{AccessConflicts, FilteredAccessSet},
+    query::{AccessConflictExplanation, AccessConflicts, FilteredAccessSet},
     schedule::{
         graph::{
             DagAnalysis, DagGroups, DiGraph,
@@ -695,6 +695,34 @@ impl ConflictingSystems {
             (name_a, name_b, conflict_names)
         })
     }
+
+    /// Explains which accesses of each pair of systems conflict, in the same order as the pairs.
+    ///
+    /// `access` returns the access of a system, such as [`SystemWithAccess::access`]. Pairs with
+    /// a system whose access isn't available get no explanation.
+    ///
+    /// See [`FilteredAccessSet::explain_conflicts`]. The explanations only cover the components
+    /// listed for the pair, so conflicts on ignored ambiguities are left out.
+    pub fn explain<'a>(
+        &'a self,
+        access: impl Fn(SystemKey) -> Option<&'a FilteredAccessSet> + 'a,
+    ) -> impl Iterator<Item = Vec<AccessConflictExplanation>> + 'a {
+        self.iter().map(move |(system_a, system_b, conflicts)| {
+            let (Some(access_a), Some(access_b)) = (access(*system_a), access(*system_b)) else {
+                return Vec::new();
+            };
+            access_a
+                .explain_conflicts(access_b)
+                .into_iter()
+                .filter(|explanation| {
+                    conflicts.is_empty()
+                        || explanation
+                            .component
+                            .is_some_and(|id| conflicts.contains(&id))
+                })
+                .collect()
+        })
+    }
 }
 
 impl Deref for ConflictingSystems {
@@ -710,6 +738,13 @@ impl Deref for ConflictingSystems {
 #[error("Systems with conflicting access have indeterminate run order: {:?}", .0.0)]
 pub struct AmbiguousSystemConflictsWarning(pub ConflictingSystems);
 
+/// Warning returned when [`deterministic_order`](crate::schedule::ScheduleBuildSettings::deterministic_order)
+/// ordered systems with conflicting access by their names. Each pair lists the system that runs
+/// first, then the system that runs second.
+#[derive(Error, Debug)]
+#[error("Systems with conflicting access were ordered by name: {:?}", .0.0)]
+pub struct DeterministicOrderWarning(pub ConflictingSystems);
+
 /// Container for system sets in a schedule.
 #[derive(Default)]
 pub struct SystemSets {
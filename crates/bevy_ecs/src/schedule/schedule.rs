@@ -572,6 +572,13 @@ impl Schedule {
 
         let error_handler = world.fallback_error_handler();
 
+        self.executable.tracer = world
+            .get_resource::<ScheduleTracing>()
+            .map(|tracing| tracing.for_schedule(self.label));
+        if let Some(tracer) = &self.executable.tracer {
+            tracer.schedule_started();
+        }
+
         #[cfg(not(feature = "bevy_debug_stepping"))]
         self.executor
             .run(&mut self.executable, world, None, error_handler);
@@ -590,6 +597,10 @@ impl Schedule {
                 error_handler,
             );
         }
+
+        if let Some(tracer) = self.executable.tracer.take() {
+            tracer.schedule_finished();
+        }
     }
 
     /// Initializes any newly-added systems and conditions, rebuilds the executable schedule,
@@ -1282,6 +1293,16 @@ impl ScheduleGraph {
             }
         }
 
+        if self.settings.deterministic_order {
+            let resolved =
+                self.order_deterministically(&mut flat_dependency, &flat_dependency_analysis);
+            if self.settings.ambiguity_detection != LogLevel::Ignore && !resolved.is_empty() {
+                warnings.push(ScheduleBuildWarning::DeterministicOrder(
+                    DeterministicOrderWarning(resolved),
+                ));
+            }
+        }
+
         // build the schedule
         Ok((
             self.build_schedule_inner(flat_dependency, hierarchy_analysis),
@@ -1292,6 +1313,58 @@ impl ScheduleGraph {
         ))
     }
 
+    /// Orders every pair of conflicting systems in `flat_dependency` by the names of the systems,
+    /// and sorts its topological ordering by name, so that the schedule always runs in the same
+    /// order.
+    ///
+    /// Returns the pairs of conflicting systems that were ordered, with the system that runs
+    /// first on the left.
+    fn order_deterministically(
+        &self,
+        flat_dependency: &mut Dag<SystemKey>,
+        flat_dependency_analysis: &DagAnalysis<SystemKey>,
+    ) -> ConflictingSystems {
+        // Without the `debug` feature all systems have the same name, so fall back to their type.
+        // `TypeId`s are only stable within a single build.
+        let key = |key: SystemKey| {
+            let system = &self.systems[key];
+            (system.name().to_string(), system.system.system_type())
+        };
+        let positions = flat_dependency
+            .toposort_by_key(key)
+            .unwrap()
+            .iter()
+            .enumerate()
+            .map(|(position, &key)| (key, position))
+            .collect::<HashMap<_, _>>();
+
+        // Accepted ambiguities still run in an arbitrary order, so order them too.
+        let conflicts = self.systems.get_conflicting_systems(
+            flat_dependency_analysis,
+            &UnGraph::default(),
+            &HashSet::default(),
+            &BTreeSet::new(),
+        );
+        let graph = flat_dependency.graph_mut();
+        let resolved = conflicts
+            .0
+            .into_iter()
+            .map(|(a, b, components)| {
+                let (first, second) = if positions[&a] < positions[&b] {
+                    (a, b)
+                } else {
+                    (b, a)
+                };
+                graph.add_edge(first, second);
+                (first, second, components)
+            })
+            .collect();
+
+        // The new edges follow the previous ordering, so sorting again yields the same one.
+        flat_dependency.toposort_by_key(key).unwrap();
+        ConflictingSystems(resolved)
+    }
+
     fn build_schedule_inner(
         &self,
         flat_dependency: Dag<SystemKey>,
@@ -1384,6 +1457,7 @@ impl ScheduleGraph {
             system_dependents,
             sets_with_conditions_of_systems,
             systems_in_sets_with_conditions,
+            tracer: None,
         }
     }
 
@@ -1633,6 +1707,26 @@ pub struct ScheduleBuildSettings {
     ///
     /// Defaults to `true`.
     pub report_sets: bool,
+    /// If set to true, the schedule always runs its systems in the same order, which only depends
+    /// on the ordering constraints and the names of the systems.
+    ///
+    /// Systems whose order is not constrained are sorted by name, and every pair of systems with
+    /// conflicting access is ordered accordingly, including pairs whose ambiguity was accepted
+    /// with [`ambiguous_with`](crate::schedule::IntoScheduleConfigs::ambiguous_with) or
+    /// [`Schedule::ignore_ambiguity`]. This makes the order deterministic for both the
+    /// single-threaded and the multi-threaded executor, at the cost of some parallelism.
+    /// Systems with the same name are sorted by type. Without the `debug` feature, system names
+    /// are not available, so systems are only sorted by their [`TypeId`](core::any::TypeId),
+    /// which is not stable: the order is only reproducible across runs of the same build.
+    ///
+    /// Set [`ambiguity_detection`](Self::ambiguity_detection) to report the ambiguities which were
+    /// resolved by name, with a [`DeterministicOrder`](ScheduleBuildWarning::DeterministicOrder)
+    /// warning, as renaming a system may change their order. Work inside a system, such
+    /// as [`Query::par_iter`](crate::system::Query::par_iter), is not affected and must not
+    /// depend on how it is split across threads.
+    ///
+    /// Defaults to `false`.
+    pub deterministic_order: bool,
 }
 
 impl Default for ScheduleBuildSettings {
@@ -1651,6 +1745,7 @@ impl ScheduleBuildSettings {
             auto_insert_apply_deferred: true,
             use_shortnames: true,
             report_sets: true,
+            deterministic_order: false,
         }
     }
 }
@@ -1696,9 +1791,9 @@ mod tests {
         error::{ignore, panic, FallbackErrorHandler, Result},
         prelude::{ApplyDeferred, IntoSystemSet, Res, Resource},
         schedule::{
-            passes::AutoInsertApplyDeferredPass, tests::ResMut, FlattenedDependencies,
-            IntoScheduleConfigs, Schedule, ScheduleBuildPass, ScheduleBuildSettings,
-            ScheduleCleanupPolicy, SystemSet,
+            passes::AutoInsertApplyDeferredPass, tests::ResMut, DeterministicOrderWarning,
+            FlattenedDependencies, IntoScheduleConfigs, LogLevel, Schedule, ScheduleBuildPass,
+            ScheduleBuildSettings, ScheduleBuildWarning, ScheduleCleanupPolicy, SystemSet,
         },
         system::Commands,
         world::World,
@@ -2730,4 +2825,94 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn deterministic_order() {
+        #[derive(Resource, Default)]
+        struct Log(Vec<&'static str>);
+
+        fn a(mut log: ResMut<Log>) {
+            log.0.push("a");
+        }
+        fn b(mut log: ResMut<Log>) {
+            log.0.push("b");
+        }
+        fn c(mut log: ResMut<Log>) {
+            log.0.push("c");
+        }
+        fn z(mut log: ResMut<Log>) {
+            log.0.push("z");
+        }
+
+        fn run_twice(mut schedule: Schedule) -> Vec<&'static str> {
+            let mut world = World::new();
+            world.init_resource::<Log>();
+            schedule.set_build_settings(ScheduleBuildSettings {
+                deterministic_order: true,
+                ..Default::default()
+            });
+            schedule.run(&mut world);
+            let first = core::mem::take(&mut world.resource_mut::<Log>().0);
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Log>().0, first);
+            first
+        }
+
+        // `z` is explicitly ordered, and `a` and `c` accepted their ambiguity, but they all
+        // conflict and must run in the same order regardless of how they were added.
+        let mut schedule = Schedule::default();
+        schedule.add_systems((c.ambiguous_with(a), z.before(b), b, a));
+        let order = run_twice(schedule);
+        assert!(
+            order.iter().position(|&name| name == "z") < order.iter().position(|&name| name == "b")
+        );
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(a);
+        schedule.add_systems((b, z.before(b)));
+        schedule.add_systems(c.ambiguous_with(a));
+        assert_eq!(run_twice(schedule), order);
+
+        #[cfg(feature = "debug")]
+        assert_eq!(order, ["a", "c", "z", "b"]);
+
+        // The accepted ambiguity of `a` and `c` is reported, along with the unaccepted ones.
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((c.ambiguous_with(a), z.before(b), b, a));
+        schedule.set_build_settings(ScheduleBuildSettings {
+            deterministic_order: true,
+            ambiguity_detection: LogLevel::Warn,
+            ..Default::default()
+        });
+        let metadata = schedule.initialize(&mut world).unwrap().unwrap();
+        let Some(ScheduleBuildWarning::DeterministicOrder(DeterministicOrderWarning(resolved))) =
+            metadata.warnings.last()
+        else {
+            panic!("expected the resolved ambiguities to be reported");
+        };
+        // Every pair of conflicting systems except `z` and `b` was ordered by name.
+        assert_eq!(resolved.len(), 5);
+        #[cfg(feature = "debug")]
+        {
+            use crate::schedule::SystemKey;
+            use alloc::string::ToString;
+
+            let name = |key: SystemKey| {
+                let (_, system) = schedule
+                    .systems()
+                    .unwrap()
+                    .find(|(system_key, _)| *system_key == key)
+                    .unwrap();
+                system.name().shortname().to_string()
+            };
+            let resolved = resolved
+                .iter()
+                .map(|(first, second, _)| (name(*first), name(*second)))
+                .collect::<Vec<_>>();
+            assert!(resolved.contains(&("a".into(), "c".into())));
+            assert!(!resolved.contains(&("c".into(), "a".into())));
+        }
+    }
 }
@@ -625,6 +625,14 @@ mod tests {
         run_system(&mut world, sys);
     }
 
+    #[test]
+    #[should_panic = "would make them disjoint"]
+    fn conflict_panic_suggests_disjoint_filter() {
+        fn sys(_: Query<(&mut A, &B)>, _: Query<&mut A>) {}
+        let mut world = World::default();
+        run_system(&mut world, sys);
+    }
+
     #[test]
     fn option_doesnt_remove_unrelated_filter_with() {
         fn sys(_: Query<(Option<&A>, &mut B, &A)>, _: Query<&mut B, Without<A>>) {}
@@ -11,8 +11,8 @@ use crate::{
     component::{ComponentId, Components, Mutable},
     entity::{Entities, EntityAllocator},
     query::{
-        Access, FilteredAccess, FilteredAccessSet, IterQueryData, QueryData, QueryFilter,
-        QuerySingleError, QueryState, ReadOnlyQueryData,
+        format_explanations, Access, FilteredAccess, FilteredAccessSet, IterQueryData, QueryData,
+        QueryFilter, QuerySingleError, QueryState, ReadOnlyQueryData,
     },
     resource::{Resource, IS_RESOURCE},
     system::{Query, Single, SystemMeta},
@@ -687,18 +687,16 @@ unsafe impl<'a, T: Resource> SystemParam for Res<'a, T> {
         filter.add_read(component_id);
         filter.and_with(IS_RESOURCE);
 
+        let source = DebugName::type_name::<Self>();
         let conflicts = component_access_set.get_conflicts_single(&filter);
         if conflicts.is_empty() {
-            component_access_set.add(filter);
+            component_access_set.add_with_source(filter, source);
             return;
         }
 
-        let mut accesses = conflicts.format_conflict_list(world.as_unsafe_world_cell());
-        // Access list may be empty (if access to all components requested)
-        if !accesses.is_empty() {
-            accesses.push(' ');
-        }
-        panic!("error[B0002]: Res<{}> in system {} conflicts with a previous system parameter. Consider removing the duplicate access using `Without<IsResource>` to create disjoint Queries or merging conflicting Queries into a `ParamSet`. See: https://bevy.org/learn/errors/b0002", DebugName::type_name::<T>(), system_meta.name);
+        let explanations = component_access_set.explain_conflicts_single(&filter, Some(source));
+        let explanations = format_explanations(&explanations, world.components());
+        panic!("error[B0002]: Res<{}> in system {} conflicts with a previous system parameter. Consider removing the duplicate access using `Without<IsResource>` to create disjoint Queries or merging conflicting Queries into a `ParamSet`. See: https://bevy.org/learn/errors/b0002{}", DebugName::type_name::<T>(), system_meta.name, explanations);
     }
 
     #[inline]
@@ -744,18 +742,16 @@ unsafe impl<'a, T: Resource<Mutability = Mutable>> SystemParam for ResMut<'a, T>
         filter.add_write(component_id);
         filter.and_with(IS_RESOURCE);
 
+        let source = DebugName::type_name::<Self>();
         let conflicts = component_access_set.get_conflicts_single(&filter);
         if conflicts.is_empty() {
-            component_access_set.add(filter);
+            component_access_set.add_with_source(filter, source);
             return;
         }
 
-        let mut accesses = conflicts.format_conflict_list(world.as_unsafe_world_cell());
-        // Access list may be empty (if access to all components requested)
-        if !accesses.is_empty() {
-            accesses.push(' ');
-        }
-        panic!("error[B0002]: ResMut<{}> in system {} conflicts with a previous system parameter. Consider removing the duplicate access or using `Without<IsResource>` to create disjoint Queries or merging conflicting Queries into a `ParamSet`. See: https://bevy.org/learn/errors/b0002", DebugName::type_name::<T>(), system_meta.name);
+        let explanations = component_access_set.explain_conflicts_single(&filter, Some(source));
+        let explanations = format_explanations(&explanations, world.components());
+        panic!("error[B0002]: ResMut<{}> in system {} conflicts with a previous system parameter. Consider removing the duplicate access or using `Without<IsResource>` to create disjoint Queries or merging conflicting Queries into a `ParamSet`. See: https://bevy.org/learn/errors/b0002{}", DebugName::type_name::<T>(), system_meta.name, explanations);
     }
 
     #[inline]
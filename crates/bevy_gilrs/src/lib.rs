@@ -23,7 +23,7 @@ use core::cell::RefCell;
 use bevy_app::{App, Plugin, PostUpdate, PreStartup, PreUpdate};
 use bevy_ecs::entity::EntityHashMap;
 use bevy_ecs::prelude::*;
-use bevy_input::InputSystems;
+use bevy_input::InputSourceSystems;
 use bevy_platform::collections::HashMap;
 use gilrs::GilrsBuilder;
 use gilrs_system::{gilrs_event_startup_system, gilrs_event_system};
@@ -107,7 +107,7 @@ impl Plugin for GilrsPlugin {
                 app.init_resource::<GilrsGamepads>();
                 app.init_resource::<RunningRumbleEffects>()
                     .add_systems(PreStartup, gilrs_event_startup_system)
-                    .add_systems(PreUpdate, gilrs_event_system.before(InputSystems))
+                    .add_systems(PreUpdate, gilrs_event_system.in_set(InputSourceSystems))
                     .add_systems(PostUpdate, play_gilrs_rumble.in_set(RumbleSystems));
             }
             Err(err) => error!("Failed to start Gilrs. {}", err),
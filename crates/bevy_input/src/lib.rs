@@ -105,10 +105,20 @@ pub struct InputPlugin;
 #[derive(Debug, PartialEq, Eq, Clone, Hash, SystemSet)]
 pub struct InputSystems;
 
+/// Label for systems that read input from devices and write the raw input messages, such as
+/// gamepad backends.
+///
+/// Runs in [`PreUpdate`], before [`InputSystems`]. Systems that need to see (or replace) all of
+/// the raw input of a frame should run after this set and before [`InputSystems`].
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemSet)]
+pub struct InputSourceSystems;
+
 impl Plugin for InputPlugin {
     #[expect(clippy::allow_attributes, reason = "this is only sometimes unused")]
     #[allow(unused, reason = "all features could be disabled")]
     fn build(&self, app: &mut App) {
+        app.configure_sets(PreUpdate, InputSourceSystems.before(InputSystems));
+
         #[cfg(feature = "keyboard")]
         app.add_message::<KeyboardInput>()
             .add_message::<KeyboardFocusLost>()
@@ -6,16 +6,19 @@ use std::sync::Mutex;
 
 use anyhow::{anyhow, Result as AnyhowResult};
 use bevy_dev_tools::schedule_data::serde::ScheduleData;
+use bevy_diagnostic::StorageStats;
 use bevy_ecs::{
+    archetype::ArchetypeId,
+    change_detection::Tick,
     component::ComponentId,
-    entity::Entity,
+    entity::{Entity, EntityHashMap},
     hierarchy::ChildOf,
     lifecycle::RemovedComponentEntity,
     message::MessageCursor,
-    query::QueryBuilder,
+    query::{QueryBuilder, QueryState},
     reflect::{AppTypeRegistry, ReflectComponent, ReflectEvent, ReflectMessage, ReflectResource},
     resource::Resource,
-    schedule::Schedules,
+    schedule::{InternedScheduleLabel, NodeId, Schedules, Stepping},
     system::{In, Local},
     world::{DeferredWorld, EntityRef, EntityWorldMut, FilteredEntityRef, Mut, World},
 };
@@ -35,10 +38,13 @@ use crate::{
         json_schema::{export_type, JsonSchemaBevyType},
         open_rpc::OpenRpcDocument,
     },
-    BrpError, BrpResult, PreviousScheduleBuildMetadata,
+    BrpError, BrpResult, PreviousScheduleBuildMetadata, WatchingRequestId,
 };
 
-#[cfg(all(feature = "http", not(target_family = "wasm")))]
+#[cfg(all(
+    any(feature = "http", feature = "websocket"),
+    not(target_family = "wasm")
+))]
 use {crate::schemas::open_rpc::ServerObject, bevy_utils::default};
 
 /// The method path for a `world.get_components` request.
@@ -71,6 +77,9 @@ pub const BRP_MUTATE_COMPONENTS_METHOD: &str = "world.mutate_components";
 /// The method path for a `world.get_components+watch` request.
 pub const BRP_GET_COMPONENTS_AND_WATCH_METHOD: &str = "world.get_components+watch";
 
+/// The method path for a `world.query+watch` request.
+pub const BRP_QUERY_AND_WATCH_METHOD: &str = "world.query+watch";
+
 /// The method path for a `world.list_components+watch` request.
 pub const BRP_LIST_COMPONENTS_AND_WATCH_METHOD: &str = "world.list_components+watch";
 
@@ -107,6 +116,25 @@ pub const BRP_OBSERVE_METHOD: &str = "world.observe+watch";
 /// The method path for a `schedule.graph` request.
 pub const BRP_SCHEDULE_GRAPH: &str = "schedule.graph";
 
+/// The method path for a `stepping.enable` request.
+pub const BRP_STEPPING_ENABLE_METHOD: &str = "stepping.enable";
+
+/// The method path for a `stepping.step_system` request.
+pub const BRP_STEPPING_STEP_SYSTEM_METHOD: &str = "stepping.step_system";
+
+/// The method path for a `stepping.set_breakpoint` request.
+pub const BRP_STEPPING_SET_BREAKPOINT_METHOD: &str = "stepping.set_breakpoint";
+
+/// The method path for a `stepping.cursor` request.
+pub const BRP_STEPPING_CURSOR_METHOD: &str = "stepping.cursor";
+
+/// The method path for a `diagnostics.storage` request.
+pub const BRP_STORAGE_DIAGNOSTICS_METHOD: &str = "diagnostics.storage";
+
+/// The method path for a `assets.dependency_graph` request.
+#[cfg(feature = "bevy_asset")]
+pub const BRP_ASSET_DEPENDENCY_GRAPH_METHOD: &str = "assets.dependency_graph";
+
 /// The method path for a `rpc.discover` request.
 pub const RPC_DISCOVER_METHOD: &str = "rpc.discover";
 
@@ -378,6 +406,107 @@ struct BrpScheduleGraphParams {
     pub schedule_label: String,
 }
 
+/// `stepping.enable`: Enables or disables [`Stepping`] for the app.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct BrpSteppingEnableParams {
+    /// Whether stepping should be enabled or disabled. Defaults to `true`.
+    pub enabled: bool,
+
+    /// The labels of the schedules to enable stepping for, as returned by `schedule.list`.
+    ///
+    /// These are added to the schedules stepping was already enabled for.
+    pub schedules: Vec<String>,
+}
+
+impl Default for BrpSteppingEnableParams {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            schedules: Vec::new(),
+        }
+    }
+}
+
+/// `stepping.step_system`: Advances [`Stepping`] during the next frame.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BrpSteppingStepSystemParams {
+    /// If `true`, runs all remaining systems of the stepping frame until the next breakpoint,
+    /// instead of only the next system.
+    #[serde(default)]
+    pub continue_frame: bool,
+}
+
+/// `stepping.set_breakpoint`: Sets the [`Stepping`] behavior of a system.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSteppingSetBreakpointParams {
+    /// The label of the schedule containing the system, as returned by `schedule.list`.
+    pub schedule: String,
+
+    /// The name of the system, as returned by `schedule.graph`.
+    ///
+    /// If the system appears several times in the schedule, every instance is affected.
+    pub system: String,
+
+    /// The behavior to give the system. Defaults to [`BrpSteppingBehavior::Break`].
+    #[serde(default)]
+    pub behavior: BrpSteppingBehavior,
+}
+
+/// How a system behaves while [`Stepping`] is enabled.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpSteppingBehavior {
+    /// Stop before running the system when continuing through a stepping frame.
+    #[default]
+    Break,
+    /// Always run the system, even when stepping is waiting.
+    AlwaysRun,
+    /// Never run the system while stepping is enabled.
+    NeverRun,
+    /// Clear any behavior previously set for the system.
+    Clear,
+}
+
+/// `assets.dependency_graph`: Exports the dependency graph of the assets tracked by the
+/// [`AssetServer`](bevy_asset::AssetServer).
+///
+/// The server responds with an [`AssetDependencyGraph`](bevy_asset::AssetDependencyGraph), or a
+/// string in the DOT language if [`BrpAssetDependencyGraphFormat::Dot`] is requested.
+#[cfg(feature = "bevy_asset")]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BrpAssetDependencyGraphParams {
+    /// If set, only the asset loaded from this path and its recursive dependencies are included.
+    pub path: Option<String>,
+
+    /// If set along with `path`, the assets that (recursively) depend on the asset are included
+    /// instead of its dependencies. Defaults to false.
+    #[serde(default)]
+    pub dependents: bool,
+
+    /// The format of the response. Defaults to [`BrpAssetDependencyGraphFormat::Json`].
+    #[serde(default)]
+    pub format: BrpAssetDependencyGraphFormat,
+}
+
+/// The format of the response to an `assets.dependency_graph` request.
+#[cfg(feature = "bevy_asset")]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpAssetDependencyGraphFormat {
+    /// The graph as a JSON object with `nodes` and `edges`.
+    #[default]
+    Json,
+    /// The graph as a string in the DOT language, to be rendered with Graphviz.
+    Dot,
+}
+
 /// Describes the data that is to be fetched in a query.
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct BrpQuery {
@@ -527,6 +656,18 @@ pub struct BrpListComponentsWatchingResponse {
 /// The response to a `world.query` request.
 pub type BrpQueryResponse = Vec<BrpQueryRow>;
 
+/// A single response from a `world.query+watch` request.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpQueryWatchingResponse {
+    /// Rows of the entities that started matching the query since the last response.
+    pub added: Vec<BrpQueryRow>,
+    /// Rows of the entities whose fetched components were added, changed or removed since the
+    /// last response.
+    pub changed: Vec<BrpQueryRow>,
+    /// The entities that stopped matching the query (or were despawned) since the last response.
+    pub removed: Vec<Entity>,
+}
+
 /// The response to a `schedule.list` request.
 ///
 /// Returns [`ScheduleLabel`](bevy_ecs::schedule::ScheduleLabel)s as [`String`]s.
@@ -559,6 +700,33 @@ pub struct BrpScheduleGraphResponse {
     pub schedule_data: ScheduleData,
 }
 
+/// The response to a `stepping.cursor` request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSteppingCursorResponse {
+    /// Whether stepping is currently enabled.
+    pub enabled: bool,
+
+    /// The labels of the schedules with stepping enabled, in the order they are run.
+    ///
+    /// This is `None` until every schedule with stepping enabled has been run once.
+    pub schedules: Option<Vec<String>>,
+
+    /// The position of the next system to be run by stepping, if any.
+    pub cursor: Option<BrpSteppingCursor>,
+}
+
+/// The position of the [`Stepping`] cursor within a stepping frame.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSteppingCursor {
+    /// The label of the schedule containing the next system to run.
+    pub schedule: String,
+
+    /// The name of the next system to run.
+    ///
+    /// This is `None` if the schedule is currently running and cannot be inspected.
+    pub system: Option<String>,
+}
+
 /// One query match result: a single entity paired with the requested components.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct BrpQueryRow {
@@ -660,7 +828,7 @@ pub fn process_remote_get_resources_request(
 
 /// Handles a `world.get_components+watch` request coming from a client.
 pub fn process_remote_get_components_watching_request(
-    In(params): In<Option<Value>>,
+    In((_, params)): In<(WatchingRequestId, Option<Value>)>,
     world: &World,
     mut removal_cursors: Local<HashMap<ComponentId, MessageCursor<RemovedComponentEntity>>>,
 ) -> BrpResult<Option<Value>> {
@@ -855,15 +1023,7 @@ impl Default for ComponentSelector {
 
 /// Handles a `world.query` request coming from a client.
 pub fn process_remote_query_request(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
-    let BrpQueryParams {
-        data: BrpQuery {
-            components,
-            option,
-            has,
-        },
-        filter,
-        strict,
-    } = match params {
+    let params = match params {
         Some(params) => parse_some(Some(params))?,
         None => BrpQueryParams {
             data: BrpQuery {
@@ -879,87 +1039,217 @@ pub fn process_remote_query_request(In(params): In<Option<Value>>, world: &mut W
     let app_type_registry = world.resource::<AppTypeRegistry>().clone();
     let type_registry = app_type_registry.read();
 
-    // Required components: must be present
-    let (required, unregistered_in_required) =
-        get_component_ids(&type_registry, world, components.clone(), strict)
-            .map_err(BrpError::component_error)?;
-
-    // Optional components: Option<&T> or all reflectable if "all"
-    let (optional, _) = match &option {
-        ComponentSelector::Paths(paths) => {
-            get_component_ids(&type_registry, world, paths.clone(), strict)
-                .map_err(BrpError::component_error)?
-        }
-        ComponentSelector::All => (Vec::new(), Vec::new()),
+    let Some(query) = RemoteQuery::new(params, world, &type_registry)? else {
+        return serde_json::to_value(BrpQueryResponse::default()).map_err(BrpError::internal);
     };
+    let has_paths_and_reflect_components = query.has_reflect_components(&type_registry)?;
 
-    // Has components: presence check
-    let (has_ids, unregistered_in_has) =
-        get_component_ids(&type_registry, world, has, strict).map_err(BrpError::component_error)?;
+    let mut response = BrpQueryResponse::default();
+    for row in query.state.iter_manual(world) {
+        response.push(query.row(
+            row,
+            world,
+            &type_registry,
+            &has_paths_and_reflect_components,
+        ));
+    }
 
-    // Filters
-    let (without, _) = get_component_ids(&type_registry, world, filter.without.clone(), strict)
-        .map_err(BrpError::component_error)?;
-    let (with, unregistered_in_with) =
-        get_component_ids(&type_registry, world, filter.with.clone(), strict)
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// A [`BrpQueryParams`] resolved against a [`World`], shared by `world.query` and
+/// `world.query+watch`.
+struct RemoteQuery {
+    /// The query matching the requested components and filters.
+    state: QueryState<FilteredEntityRef<'static, 'static>>,
+    /// The components that must be present.
+    required: Vec<(TypeId, ComponentId)>,
+    /// The components that are fetched if present.
+    optional: Vec<(TypeId, ComponentId)>,
+    /// How optional components were selected.
+    option: ComponentSelector,
+    /// The components whose presence is reported.
+    has: Vec<(TypeId, ComponentId)>,
+    /// Components in `has` that are unknown to the world, and thus always absent.
+    unregistered_in_has: Vec<String>,
+}
+
+impl RemoteQuery {
+    /// Resolves the given params, returning `None` if the query can never match anything.
+    fn new(
+        params: BrpQueryParams,
+        world: &mut World,
+        type_registry: &TypeRegistry,
+    ) -> BrpResult<Option<Self>> {
+        let BrpQueryParams {
+            data:
+                BrpQuery {
+                    components,
+                    option,
+                    has,
+                },
+            filter,
+            strict,
+        } = params;
+
+        // Required components: must be present
+        let (required, unregistered_in_required) =
+            get_component_ids(type_registry, world, components, strict)
+                .map_err(BrpError::component_error)?;
+
+        // Optional components: Option<&T> or all reflectable if "all"
+        let (optional, _) = match &option {
+            ComponentSelector::Paths(paths) => {
+                get_component_ids(type_registry, world, paths.clone(), strict)
+                    .map_err(BrpError::component_error)?
+            }
+            ComponentSelector::All => (Vec::new(), Vec::new()),
+        };
+
+        // Has components: presence check
+        let (has, unregistered_in_has) = get_component_ids(type_registry, world, has, strict)
             .map_err(BrpError::component_error)?;
 
-    // When "strict" is false:
-    // - Unregistered components in "option" and "without" are ignored.
-    // - Unregistered components in "has" are considered absent from the entity.
-    // - Unregistered components in "components" and "with" result in an empty
-    // response since they specify hard requirements.
-    // If strict, fail if any required or with components are unregistered
-    if !unregistered_in_required.is_empty() || !unregistered_in_with.is_empty() {
-        return serde_json::to_value(BrpQueryResponse::default()).map_err(BrpError::internal);
-    }
+        // Filters
+        let (without, _) = get_component_ids(type_registry, world, filter.without, strict)
+            .map_err(BrpError::component_error)?;
+        let (with, unregistered_in_with) =
+            get_component_ids(type_registry, world, filter.with, strict)
+                .map_err(BrpError::component_error)?;
+
+        // When "strict" is false:
+        // - Unregistered components in "option" and "without" are ignored.
+        // - Unregistered components in "has" are considered absent from the entity.
+        // - Unregistered components in "components" and "with" result in an empty
+        // response since they specify hard requirements.
+        // If strict, fail if any required or with components are unregistered
+        if !unregistered_in_required.is_empty() || !unregistered_in_with.is_empty() {
+            return Ok(None);
+        }
 
-    let mut query = QueryBuilder::<FilteredEntityRef>::new(world);
-    for (_, component) in &required {
-        query.ref_id(*component);
-    }
-    for (_, option) in &optional {
-        query.optional(|query| {
-            query.ref_id(*option);
-        });
-    }
-    for (_, has) in &has_ids {
-        query.optional(|query| {
-            query.ref_id(*has);
-        });
-    }
-    for (_, without) in without {
-        query.without_id(without);
+        let mut query = QueryBuilder::<FilteredEntityRef>::new(world);
+        for (_, component) in &required {
+            query.ref_id(*component);
+        }
+        for (_, option) in &optional {
+            query.optional(|query| {
+                query.ref_id(*option);
+            });
+        }
+        for (_, has) in &has {
+            query.optional(|query| {
+                query.ref_id(*has);
+            });
+        }
+        for (_, without) in without {
+            query.without_id(without);
+        }
+        for (_, with) in with {
+            query.with_id(with);
+        }
+
+        Ok(Some(Self {
+            state: query.build(),
+            required,
+            optional,
+            option,
+            has,
+            unregistered_in_has,
+        }))
     }
-    for (_, with) in with {
-        query.with_id(with);
+
+    /// Looks up the reflect information of the components whose presence is reported.
+    fn has_reflect_components<'r>(
+        &self,
+        type_registry: &'r TypeRegistry,
+    ) -> BrpResult<Vec<(&'r str, &'r ReflectComponent)>> {
+        self.has
+            .iter()
+            .map(|(type_id, _)| reflect_component_from_id(*type_id, type_registry))
+            .collect::<AnyhowResult<Vec<(&str, &ReflectComponent)>>>()
+            .map_err(BrpError::component_error)
     }
 
-    // Prepare has reflect info
-    let has_paths_and_reflect_components: Vec<(&str, &ReflectComponent)> = has_ids
-        .iter()
-        .map(|(type_id, _)| reflect_component_from_id(*type_id, &type_registry))
-        .collect::<AnyhowResult<Vec<(&str, &ReflectComponent)>>>()
-        .map_err(BrpError::component_error)?;
+    /// Returns `true` if any component fetched for the given entity was added or changed
+    /// between `last_run` and `this_run`.
+    fn fetched_components_changed(
+        &self,
+        entity_ref: EntityRef,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> bool {
+        let is_changed = |component_id: ComponentId| {
+            entity_ref
+                .get_change_ticks_by_id(component_id)
+                .is_some_and(|ticks| ticks.is_changed(last_run, this_run))
+        };
+        let optional_changed = match &self.option {
+            ComponentSelector::All => entity_ref
+                .archetype()
+                .components()
+                .iter()
+                .any(|component_id| is_changed(*component_id)),
+            ComponentSelector::Paths(_) => self
+                .optional
+                .iter()
+                .any(|(_, component_id)| is_changed(*component_id)),
+        };
+        optional_changed
+            || self
+                .required
+                .iter()
+                .any(|(_, component_id)| is_changed(*component_id))
+    }
 
-    let mut response = BrpQueryResponse::default();
-    let mut query = query.build();
+    /// Returns `true` if a fetched or presence-checked component was added to or removed from an
+    /// entity that moved from the `previous` archetype to the `current` one.
+    fn fetched_components_moved(
+        &self,
+        world: &World,
+        previous: ArchetypeId,
+        current: ArchetypeId,
+    ) -> bool {
+        if previous == current {
+            return false;
+        }
+        if self.option == ComponentSelector::All {
+            return true;
+        }
+        let archetypes = world.archetypes();
+        let (Some(previous), Some(current)) = (archetypes.get(previous), archetypes.get(current))
+        else {
+            return true;
+        };
+        self.optional
+            .iter()
+            .chain(&self.has)
+            .any(|(_, component_id)| {
+                previous.contains(*component_id) != current.contains(*component_id)
+            })
+    }
 
-    for row in query.iter(world) {
+    /// Serializes a single query match.
+    fn row(
+        &self,
+        row: FilteredEntityRef,
+        world: &World,
+        type_registry: &TypeRegistry,
+        has_paths_and_reflect_components: &[(&str, &ReflectComponent)],
+    ) -> BrpQueryRow {
         let entity_id = row.id();
         let entity_ref = world.get_entity(entity_id).expect("Entity should exist");
 
         // Required components
         let mut components_map = serialize_components(
             entity_ref,
-            &type_registry,
-            required
+            type_registry,
+            self.required
                 .iter()
                 .map(|(type_id, component_id)| (*type_id, Some(*component_id))),
         );
 
         // Optional components
-        match &option {
+        match &self.option {
             ComponentSelector::All => {
                 // Add all reflectable components present on the entity (as Option<&T>)
                 let all_optionals =
@@ -971,29 +1261,27 @@ pub fn process_remote_query_request(In(params): In<Option<Value>>, world: &mut W
                             let info = world.components().get_info(component_id)?;
                             let type_id = info.type_id()?;
                             // Skip required components (already included)
-                            if required.iter().any(|(_, cid)| cid == &component_id) {
+                            if self.required.iter().any(|(_, cid)| cid == &component_id) {
                                 return None;
                             }
                             Some((type_id, Some(component_id)))
                         });
                 components_map.extend(serialize_components(
                     entity_ref,
-                    &type_registry,
+                    type_registry,
                     all_optionals,
                 ));
             }
             ComponentSelector::Paths(_) => {
                 // Add only the requested optional components (as Option<&T>)
-                let optionals = optional.iter().filter(|(_, component_id)| {
+                let optionals = self.optional.iter().filter(|(_, component_id)| {
                     // Skip required components (already included)
-                    !required.iter().any(|(_, cid)| cid == component_id)
+                    !self.required.iter().any(|(_, cid)| cid == component_id)
                 });
                 components_map.extend(serialize_components(
                     entity_ref,
-                    &type_registry,
-                    optionals
-                        .clone()
-                        .map(|(type_id, component_id)| (*type_id, Some(*component_id))),
+                    type_registry,
+                    optionals.map(|(type_id, component_id)| (*type_id, Some(*component_id))),
                 ));
             }
         }
@@ -1002,19 +1290,137 @@ pub fn process_remote_query_request(In(params): In<Option<Value>>, world: &mut W
         let has_map = build_has_map(
             row,
             has_paths_and_reflect_components.iter().copied(),
-            &unregistered_in_has,
+            &self.unregistered_in_has,
         );
 
-        let query_row = BrpQueryRow {
-            entity: row.id(),
+        BrpQueryRow {
+            entity: entity_id,
             components: components_map,
             has: has_map,
-        };
+        }
+    }
+}
 
-        response.push(query_row);
+/// Stores the state of `world.query+watch` requests between polls.
+///
+/// Each request is keyed by its [`WatchingRequestId`], so that every stream gets its own initial
+/// snapshot even if another stream with identical params is already open.
+#[derive(Resource, Default)]
+pub struct BrpQueryWatchers {
+    /// Map from the id of a request to its state.
+    watchers: HashMap<WatchingRequestId, QueryWatcher>,
+    /// The frame in which requests were last polled.
+    frame: Tick,
+}
+
+struct QueryWatcher {
+    /// The change tick at which the query was last evaluated.
+    last_run: Tick,
+    /// The frame in which the query was last evaluated.
+    frame: Tick,
+    /// The entities that matched the query, along with their archetype, when it was last evaluated.
+    rows: EntityHashMap<ArchetypeId>,
+}
+
+/// Handles a `world.query+watch` request coming from a client.
+///
+/// The first poll reports every matching entity as `added`. Each subsequent poll reports the
+/// entities that started matching, the entities whose fetched components were added, changed
+/// or removed, and the entities that stopped matching since the previous poll.
+pub fn process_remote_query_watching_request(
+    In((id, params)): In<(WatchingRequestId, Option<Value>)>,
+    world: &mut World,
+) -> BrpResult<Option<Value>> {
+    let params: BrpQueryParams = parse_some(params)?;
+
+    if !world.contains_resource::<BrpQueryWatchers>() {
+        world.init_resource::<BrpQueryWatchers>();
     }
 
-    serde_json::to_value(response).map_err(BrpError::internal)
+    // `last_change_tick` is advanced once per frame, by `World::clear_trackers`.
+    let frame = world.last_change_tick();
+    let this_run = world.read_change_tick();
+
+    let mut watcher = {
+        let mut watchers = world.resource_mut::<BrpQueryWatchers>();
+        if watchers.frame != frame {
+            // Forget requests that were not polled during the previous frame: they have been closed.
+            let previous_frame = watchers.frame;
+            watchers
+                .watchers
+                .retain(|_, watcher| watcher.frame == previous_frame);
+            watchers.frame = frame;
+        }
+        watchers
+            .watchers
+            .remove(&id)
+            .unwrap_or_else(|| QueryWatcher {
+                last_run: this_run,
+                frame,
+                rows: EntityHashMap::default(),
+            })
+    };
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+
+    let mut response = BrpQueryWatchingResponse::default();
+    let mut rows = EntityHashMap::default();
+    if let Some(query) = RemoteQuery::new(params, world, &type_registry)? {
+        let has_paths_and_reflect_components = query.has_reflect_components(&type_registry)?;
+        for row in query.state.iter_manual(world) {
+            let entity = row.id();
+            let entity_ref = world.get_entity(entity).expect("Entity should exist");
+            let archetype_id = entity_ref.archetype().id();
+            rows.insert(entity, archetype_id);
+
+            let is_added = !watcher.rows.contains_key(&entity);
+            let is_changed = !is_added
+                && (watcher.rows.get(&entity).is_some_and(|previous| {
+                    query.fetched_components_moved(world, *previous, archetype_id)
+                }) || query.fetched_components_changed(entity_ref, watcher.last_run, this_run));
+
+            if is_added || is_changed {
+                let row = query.row(
+                    row,
+                    world,
+                    &type_registry,
+                    &has_paths_and_reflect_components,
+                );
+                if is_added {
+                    response.added.push(row);
+                } else {
+                    response.changed.push(row);
+                }
+            }
+        }
+    }
+    response.removed = watcher
+        .rows
+        .keys()
+        .filter(|entity| !rows.contains_key(*entity))
+        .copied()
+        .collect();
+    response.removed.sort();
+
+    let response = if response.added.is_empty()
+        && response.changed.is_empty()
+        && response.removed.is_empty()
+    {
+        None
+    } else {
+        Some(serde_json::to_value(response).map_err(BrpError::internal)?)
+    };
+
+    watcher.last_run = this_run;
+    watcher.frame = frame;
+    watcher.rows = rows;
+    world
+        .resource_mut::<BrpQueryWatchers>()
+        .watchers
+        .insert(id, watcher);
+
+    Ok(response)
 }
 
 /// Serializes the specified components for an entity.
@@ -1082,26 +1488,46 @@ pub fn process_remote_list_methods_request(
 ) -> BrpResult {
     let remote_methods = world.resource::<crate::RemoteMethods>();
 
+    #[cfg_attr(
+        any(
+            all(not(feature = "http"), not(feature = "websocket")),
+            target_family = "wasm"
+        ),
+        expect(unused_mut, reason = "no transport registers a server")
+    )]
+    let mut servers = Vec::new();
+
     #[cfg(all(feature = "http", not(target_family = "wasm")))]
-    let servers = match (
+    match (
         world.get_resource::<crate::http::HostAddress>(),
         world.get_resource::<crate::http::HostPort>(),
     ) {
-        (Some(url), Some(port)) => Some(vec![ServerObject {
+        (Some(url), Some(port)) => servers.push(ServerObject {
             name: "Server".to_owned(),
             url: format!("{}:{}", url.0, port.0),
             ..default()
-        }]),
-        (Some(url), None) => Some(vec![ServerObject {
+        }),
+        (Some(url), None) => servers.push(ServerObject {
             name: "Server".to_owned(),
             url: url.0.to_string(),
             ..default()
-        }]),
-        _ => None,
-    };
+        }),
+        _ => {}
+    }
 
-    #[cfg(any(not(feature = "http"), target_family = "wasm"))]
-    let servers = None;
+    #[cfg(all(feature = "websocket", not(target_family = "wasm")))]
+    if let (Some(url), Some(port)) = (
+        world.get_resource::<crate::websocket::WebSocketHostAddress>(),
+        world.get_resource::<crate::websocket::WebSocketHostPort>(),
+    ) {
+        servers.push(ServerObject {
+            name: "WebSocket Server".to_owned(),
+            url: format!("ws://{}:{}", url.0, port.0),
+            ..default()
+        });
+    }
+
+    let servers = (!servers.is_empty()).then_some(servers);
 
     let doc = OpenRpcDocument {
         info: Default::default(),
@@ -1433,7 +1859,7 @@ pub fn process_remote_list_resources_request(
 
 /// Handles a `world.list_components+watch` request coming from a client.
 pub fn process_remote_list_components_watching_request(
-    In(params): In<Option<Value>>,
+    In((_, params)): In<(WatchingRequestId, Option<Value>)>,
     world: &World,
     mut removal_cursors: Local<HashMap<ComponentId, MessageCursor<RemovedComponentEntity>>>,
 ) -> BrpResult<Option<Value>> {
@@ -1568,7 +1994,7 @@ pub struct BrpEventObservers {
 ///
 /// When `entity` is provided, the observer is scoped to that entity. Otherwise a global observer is registered.
 pub fn process_remote_observe_watching_request(
-    In(params): In<Option<Value>>,
+    In((_, params)): In<(WatchingRequestId, Option<Value>)>,
     world: &mut World,
 ) -> BrpResult<Option<Value>> {
     let BrpObserveParams { event, entity } = parse_some(params)?;
@@ -1798,6 +2224,196 @@ pub fn schedule_graph(In(params): In<Option<Value>>, world: &mut World) -> BrpRe
     serde_json::to_value(BrpScheduleGraphResponse { schedule_data }).map_err(BrpError::internal)
 }
 
+/// Handles a `stepping.enable` request coming from a client.
+///
+/// Changes to [`Stepping`] take effect at the start of the next frame. Stepping can only be
+/// enabled if the app was built with the `bevy_debug_stepping` feature.
+pub fn process_remote_stepping_enable_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpSteppingEnableParams { enabled, schedules } = match params {
+        None => Default::default(),
+        Some(params) => parse(params)?,
+    };
+
+    let labels = schedules
+        .iter()
+        .map(|schedule| find_schedule_label(world, schedule))
+        .collect::<BrpResult<Vec<_>>>()?;
+
+    let mut stepping = world.get_resource_or_init::<Stepping>();
+    for label in labels {
+        stepping.add_schedule(label);
+    }
+    if enabled {
+        stepping.enable();
+    } else {
+        stepping.disable();
+    }
+
+    Ok(Value::Null)
+}
+
+/// Handles a `stepping.step_system` request coming from a client.
+pub fn process_remote_stepping_step_system_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpSteppingStepSystemParams { continue_frame } = match params {
+        None => Default::default(),
+        Some(params) => parse(params)?,
+    };
+
+    let Some(mut stepping) = world.get_resource_mut::<Stepping>() else {
+        return Err(BrpError::resource_not_present(core::any::type_name::<
+            Stepping,
+        >()));
+    };
+    if continue_frame {
+        stepping.continue_frame();
+    } else {
+        stepping.step_frame();
+    }
+
+    Ok(Value::Null)
+}
+
+/// Handles a `stepping.set_breakpoint` request coming from a client.
+pub fn process_remote_stepping_set_breakpoint_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpSteppingSetBreakpointParams {
+        schedule,
+        system,
+        behavior,
+    } = parse_some(params)?;
+
+    let label = find_schedule_label(world, &schedule)?;
+    let Some(nodes) = world.resource::<Schedules>().get(label).map(|schedule| {
+        schedule.systems().map(|systems| {
+            systems
+                .filter(|(_, instance)| instance.name().to_string() == system)
+                .map(|(key, _)| NodeId::System(key))
+                .collect::<Vec<_>>()
+        })
+    }) else {
+        return Err(BrpError::resource_error(format!(
+            "Schedule with label={schedule} is currently running"
+        )));
+    };
+    let nodes = nodes.map_err(|err| {
+        BrpError::resource_error(format!("Schedule with label={schedule}: {err}"))
+    })?;
+    if nodes.is_empty() {
+        return Err(BrpError::resource_error(format!(
+            "System {system} not found in schedule with label={schedule}"
+        )));
+    }
+
+    let mut stepping = world.get_resource_or_init::<Stepping>();
+    for node in nodes {
+        match behavior {
+            BrpSteppingBehavior::Break => stepping.set_breakpoint_node(label, node),
+            BrpSteppingBehavior::AlwaysRun => stepping.always_run_node(label, node),
+            BrpSteppingBehavior::NeverRun => stepping.never_run_node(label, node),
+            BrpSteppingBehavior::Clear => stepping.clear_node(label, node),
+        };
+    }
+
+    Ok(Value::Null)
+}
+
+/// Handles a `stepping.cursor` request coming from a client.
+pub fn process_remote_stepping_cursor_request(
+    In(_params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let response = match world.get_resource::<Stepping>() {
+        None => BrpSteppingCursorResponse {
+            enabled: false,
+            schedules: None,
+            cursor: None,
+        },
+        Some(stepping) => BrpSteppingCursorResponse {
+            enabled: stepping.is_enabled(),
+            schedules: stepping
+                .schedules()
+                .ok()
+                .map(|labels| labels.iter().map(|label| format!("{:?}", label)).collect()),
+            cursor: stepping.cursor().map(|(label, node)| BrpSteppingCursor {
+                schedule: format!("{:?}", label),
+                system: node.as_system().and_then(|key| {
+                    let schedule = world.resource::<Schedules>().get(label)?;
+                    let (_, system) = schedule.systems().ok()?.find(|(k, _)| *k == key)?;
+                    Some(system.name().to_string())
+                }),
+            }),
+        },
+    };
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `diagnostics.storage` request coming from a client.
+pub fn process_remote_storage_diagnostics_request(
+    In(_params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    serde_json::to_value(StorageStats::from_world(world)).map_err(BrpError::internal)
+}
+
+/// Handles a `assets.dependency_graph` request coming from a client.
+#[cfg(feature = "bevy_asset")]
+pub fn process_remote_asset_dependency_graph_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let BrpAssetDependencyGraphParams {
+        path,
+        dependents,
+        format,
+    } = params.map(parse).transpose()?.unwrap_or_default();
+
+    let asset_server = world
+        .get_resource::<bevy_asset::AssetServer>()
+        .ok_or_else(|| BrpError::resource_not_present("AssetServer"))?;
+    let mut graph = asset_server.dependency_graph();
+    if let Some(path) = path {
+        let id = asset_server.get_path_id(&path).ok_or_else(|| {
+            BrpError::resource_error(format!("No asset is loaded from path `{path}`"))
+        })?;
+        graph = if dependents {
+            graph.recursive_dependents(id)
+        } else {
+            graph.recursive_dependencies(id)
+        };
+    }
+
+    match format {
+        BrpAssetDependencyGraphFormat::Json => {
+            serde_json::to_value(graph).map_err(BrpError::internal)
+        }
+        BrpAssetDependencyGraphFormat::Dot => Ok(Value::String(graph.to_dot())),
+    }
+}
+
+/// Finds the interned label of the schedule whose debug representation is `schedule`.
+///
+/// Schedules that are currently running are included.
+fn find_schedule_label(world: &World, schedule: &str) -> BrpResult<InternedScheduleLabel> {
+    let schedules = world.resource::<Schedules>();
+    schedules
+        .iter()
+        .map(|(_, schedule)| schedule.label())
+        .chain(schedules.get_temporarily_removed())
+        .find(|label| format!("{:?}", label) == schedule)
+        .ok_or_else(|| {
+            BrpError::resource_error(format!("Schedule with label={schedule} not found"))
+        })
+}
+
 /// Immutably retrieves an entity from the [`World`], returning an error if the
 /// entity isn't present.
 fn get_entity(world: &World, entity: Entity) -> Result<EntityRef<'_>, BrpError> {
@@ -2055,7 +2671,7 @@ mod tests {
         observer::On,
         resource::Resource,
         schedule::{IntoScheduleConfigs as _, Schedule, ScheduleLabel, SystemSet},
-        system::{Commands, Res, ResMut},
+        system::{Commands, IntoSystem, Res, ResMut, System},
     };
     use bevy_reflect::Reflect;
     use serde_json::Value::Null;
@@ -2088,6 +2704,143 @@ mod tests {
         insert_reflected_components(e, deserialized_components).expect("FAIL");
     }
 
+    #[test]
+    fn query_watching_reports_added_changed_and_removed_rows() {
+        #[derive(Component, Reflect, PartialEq, Debug)]
+        #[reflect(Component)]
+        struct Health(u32);
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Dead;
+
+        #[derive(Component)]
+        struct Unrelated;
+
+        let atr = AppTypeRegistry::default();
+        {
+            let mut register = atr.write();
+            register.register::<Health>();
+            register.register::<Dead>();
+        }
+        let mut world = World::new();
+        world.insert_resource(atr);
+        world.register_component::<Dead>();
+
+        let a = world.spawn(Health(10)).id();
+        let b = world.spawn(Health(20)).id();
+        let c = world.spawn(Health(30)).id();
+
+        let params = serde_json::to_value(&BrpQueryParams {
+            data: BrpQuery {
+                components: vec!["bevy_remote::builtin_methods::tests::Health".to_owned()],
+                ..default()
+            },
+            filter: BrpQueryFilter {
+                without: vec!["bevy_remote::builtin_methods::tests::Dead".to_owned()],
+                ..default()
+            },
+            strict: true,
+        })
+        .expect("FAIL");
+        let poll = |world: &mut World| -> Option<BrpQueryWatchingResponse> {
+            process_remote_query_watching_request(
+                In((WatchingRequestId(0), Some(params.clone()))),
+                world,
+            )
+            .expect("poll should succeed")
+            .map(|value| serde_json::from_value(value).expect("FAIL"))
+        };
+
+        let response = poll(&mut world).expect("first poll reports all rows");
+        assert_eq!(response.added.len(), 3);
+        assert!(response.changed.is_empty() && response.removed.is_empty());
+
+        world.clear_trackers();
+        assert_eq!(poll(&mut world), None);
+
+        world.clear_trackers();
+        world.get_mut::<Health>(a).unwrap().0 = 5;
+        world.entity_mut(b).insert(Dead);
+        world.entity_mut(c).insert(Unrelated);
+        let d = world.spawn(Health(40)).id();
+
+        let response = poll(&mut world).expect("changes should be reported");
+        assert_eq!(
+            response
+                .added
+                .iter()
+                .map(|row| row.entity)
+                .collect::<Vec<_>>(),
+            vec![d]
+        );
+        assert_eq!(
+            response
+                .changed
+                .iter()
+                .map(|row| row.entity)
+                .collect::<Vec<_>>(),
+            vec![a]
+        );
+        assert_eq!(
+            response.changed[0].components["bevy_remote::builtin_methods::tests::Health"],
+            serde_json::json!(5)
+        );
+        assert_eq!(response.removed, vec![b]);
+
+        world.clear_trackers();
+        world.despawn(d);
+        let response = poll(&mut world).expect("despawn should be reported");
+        assert_eq!(response.removed, vec![d]);
+    }
+
+    #[test]
+    fn query_watching_streams_have_separate_state() {
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Health(u32);
+
+        let atr = AppTypeRegistry::default();
+        atr.write().register::<Health>();
+        let mut world = World::new();
+        world.insert_resource(atr);
+        world.spawn(Health(10));
+        world.spawn(Health(20));
+
+        let params = serde_json::to_value(&BrpQueryParams {
+            data: BrpQuery {
+                components: vec!["bevy_remote::builtin_methods::tests::Health".to_owned()],
+                ..default()
+            },
+            filter: default(),
+            strict: true,
+        })
+        .expect("FAIL");
+        let poll = |world: &mut World, id: u64| -> Option<BrpQueryWatchingResponse> {
+            process_remote_query_watching_request(
+                In((WatchingRequestId(id), Some(params.clone()))),
+                world,
+            )
+            .expect("poll should succeed")
+            .map(|value| serde_json::from_value(value).expect("FAIL"))
+        };
+
+        let response = poll(&mut world, 0).expect("first poll reports all rows");
+        assert_eq!(response.added.len(), 2);
+
+        world.clear_trackers();
+        assert_eq!(poll(&mut world, 0), None);
+
+        // A second stream with identical params, opened after the first one has polled, still
+        // gets its own initial snapshot.
+        let response = poll(&mut world, 1).expect("first poll reports all rows");
+        assert_eq!(response.added.len(), 2);
+
+        world.clear_trackers();
+        assert_eq!(poll(&mut world, 0), None);
+        assert_eq!(poll(&mut world, 1), None);
+    }
+
     #[test]
     fn trigger_reflect_only_event() {
         #[derive(Event, Reflect)]
@@ -2142,7 +2895,10 @@ mod tests {
         .expect("FAIL");
 
         assert_eq!(
-            process_remote_observe_watching_request(In(Some(observe_params.clone())), &mut world,),
+            process_remote_observe_watching_request(
+                In((WatchingRequestId(0), Some(observe_params.clone()))),
+                &mut world,
+            ),
             Ok(None)
         );
         assert!(world.contains_resource::<BrpEventObservers>());
@@ -2157,17 +2913,22 @@ mod tests {
             Ok(Null)
         );
 
-        let captured =
-            process_remote_observe_watching_request(In(Some(observe_params.clone())), &mut world)
-                .expect("poll should succeed")
-                .expect("events should be returned");
+        let captured = process_remote_observe_watching_request(
+            In((WatchingRequestId(0), Some(observe_params.clone()))),
+            &mut world,
+        )
+        .expect("poll should succeed")
+        .expect("events should be returned");
         let events: Vec<Value> =
             serde_json::from_value(captured).expect("captured events are a JSON array");
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].get("value"), Some(&serde_json::json!(42)));
 
         assert_eq!(
-            process_remote_observe_watching_request(In(Some(observe_params)), &mut world),
+            process_remote_observe_watching_request(
+                In((WatchingRequestId(0), Some(observe_params))),
+                &mut world
+            ),
             Ok(None)
         );
     }
@@ -2486,4 +3247,116 @@ mod tests {
             .dependency
             .contains(&(apply_deferred_index, f2_index)));
     }
+
+    #[test]
+    fn storage_diagnostics_reports_archetypes() {
+        #[derive(Component)]
+        struct Health;
+
+        let mut world = World::default();
+        world.spawn(Health);
+        world.spawn(Health);
+
+        let response = process_remote_storage_diagnostics_request(In(None), &world).expect("FAIL");
+        let stats = serde_json::from_value::<StorageStats>(response).unwrap();
+        let health = world.component_id::<Health>().unwrap().index();
+        assert!(stats.tables.iter().any(|table| table.entity_count == 2
+            && table
+                .columns
+                .iter()
+                .any(|column| column.component == health)));
+    }
+
+    #[cfg(feature = "bevy_asset")]
+    #[test]
+    fn asset_dependency_graph_formats() {
+        use bevy_app::{App, TaskPoolPlugin};
+
+        let world = World::default();
+        let err = process_remote_asset_dependency_graph_request(In(None), &world).unwrap_err();
+        assert_eq!(err.code, error_codes::RESOURCE_NOT_PRESENT);
+
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            bevy_asset::AssetPlugin::default(),
+        ));
+        let world = app.world();
+
+        let graph = process_remote_asset_dependency_graph_request(In(None), world).unwrap();
+        assert_eq!(graph, serde_json::json!({ "nodes": [], "edges": [] }));
+
+        let dot = process_remote_asset_dependency_graph_request(
+            In(Some(serde_json::json!({ "format": "dot" }))),
+            world,
+        )
+        .unwrap();
+        assert_eq!(dot, Value::String("digraph assets {\n}\n".into()));
+
+        let err = process_remote_asset_dependency_graph_request(
+            In(Some(serde_json::json!({ "path": "missing.png" }))),
+            world,
+        )
+        .unwrap_err();
+        assert_eq!(err.code, error_codes::RESOURCE_ERROR);
+    }
+
+    #[test]
+    fn stepping_methods_resolve_schedules_and_systems() {
+        #[derive(ScheduleLabel, Hash, Clone, PartialEq, Eq, Debug)]
+        struct MySchedule;
+
+        fn f1() {}
+
+        let mut world = World::default();
+        let mut schedule = Schedule::new(MySchedule);
+        schedule.add_systems(f1);
+        let _ = schedule.initialize(&mut world);
+        world.add_schedule(schedule);
+
+        let cursor = process_remote_stepping_cursor_request(In(None), &world).expect("FAIL");
+        let cursor = serde_json::from_value::<BrpSteppingCursorResponse>(cursor).unwrap();
+        assert!(!cursor.enabled);
+        assert_eq!(cursor.schedules, None);
+        assert_eq!(cursor.cursor, None);
+
+        // Stepping has to be enabled before it can be advanced.
+        let err = process_remote_stepping_step_system_request(In(None), &mut world).unwrap_err();
+        assert_eq!(err.code, error_codes::RESOURCE_NOT_PRESENT);
+
+        let params = |schedules: &[&str]| {
+            serde_json::to_value(BrpSteppingEnableParams {
+                enabled: true,
+                schedules: schedules.iter().map(ToString::to_string).collect(),
+            })
+            .ok()
+        };
+        assert!(
+            process_remote_stepping_enable_request(In(params(&["Unknown"])), &mut world).is_err()
+        );
+        assert!(!world.contains_resource::<Stepping>());
+        process_remote_stepping_enable_request(In(params(&["MySchedule"])), &mut world)
+            .expect("FAIL");
+        assert!(world.contains_resource::<Stepping>());
+
+        let breakpoint = |system: String| {
+            serde_json::to_value(BrpSteppingSetBreakpointParams {
+                schedule: "MySchedule".to_string(),
+                system,
+                behavior: BrpSteppingBehavior::Break,
+            })
+            .ok()
+        };
+        let err = process_remote_stepping_set_breakpoint_request(
+            In(breakpoint("f2".to_string())),
+            &mut world,
+        )
+        .unwrap_err();
+        assert_eq!(err.code, error_codes::RESOURCE_ERROR);
+
+        let name = IntoSystem::into_system(f1).name().to_string();
+        process_remote_stepping_set_breakpoint_request(In(breakpoint(name)), &mut world)
+            .expect("FAIL");
+        process_remote_stepping_step_system_request(In(None), &mut world).expect("FAIL");
+    }
 }
@@ -3,8 +3,9 @@
 //! Adding the [`RemotePlugin`] to your [`App`] will setup everything needed without
 //! starting any transports. To start accepting remote connections you will need to
 //! add a second plugin like the [`RemoteHttpPlugin`](http::RemoteHttpPlugin) to enable communication
-//! over HTTP. These *remote clients* can inspect and alter the state of the
-//! entity-component system.
+//! over HTTP, or the `RemoteWebSocketPlugin` (behind the `websocket` feature) to multiplex
+//! requests and `+watch` streams over a single WebSocket connection. These *remote clients* can
+//! inspect and alter the state of the entity-component system.
 //!
 //! The Bevy Remote Protocol is based on the JSON-RPC 2.0 protocol.
 //!
@@ -406,6 +407,20 @@
 //! - `removed`: An array of fully-qualified type names of components removed from the entity
 //!   in the last tick.
 //!
+//! ### `world.query+watch`
+//!
+//! Watch the results of a query, receiving the rows that were added, changed or removed.
+//!
+//! `params`: The same as for `world.query`.
+//!
+//! `result`:
+//! - `added`: An array of rows, in the format returned by `world.query`, for the entities that
+//!   started matching the query. The first response contains every matching entity.
+//! - `changed`: An array of rows for the entities whose fetched components were added, changed
+//!   or removed since the previous response.
+//! - `removed`: An array of IDs of the entities that stopped matching the query, or were
+//!   despawned, since the previous response.
+//!
 //! ### `world.get_resources`
 //!
 //! Extract the value of a given resource from the world.
@@ -478,6 +493,87 @@
 //! This contains schema information about that type, including field definitions, type information, reflect type information, and other metadata
 //! helpful for understanding the structure of the type.
 //!
+//! ### `stepping.enable`
+//!
+//! Enable or disable [system stepping](bevy_ecs::schedule::Stepping). Changes take effect at the
+//! start of the next frame. Stepping can only be enabled if the app was built with the
+//! `bevy_debug_stepping` feature.
+//!
+//! `params` (optional):
+//! - `enabled` (optional): Whether stepping should be enabled. Defaults to `true`.
+//! - `schedules` (optional): An array of schedule labels, as returned by `schedule.list`, to
+//!   enable stepping for.
+//!
+//! `result`: null.
+//!
+//! ### `stepping.step_system`
+//!
+//! Run the next system of the stepping frame during the next frame.
+//!
+//! `params` (optional):
+//! - `continue_frame` (optional): If `true`, run all remaining systems of the stepping frame up to
+//!   the next breakpoint instead of a single system.
+//!
+//! `result`: null.
+//!
+//! ### `stepping.set_breakpoint`
+//!
+//! Set how a system behaves while stepping is enabled.
+//!
+//! `params`:
+//! - `schedule`: The label of the schedule containing the system.
+//! - `system`: The name of the system, as returned by `schedule.graph`.
+//! - `behavior` (optional): One of `break`, `always_run`, `never_run` or `clear`. Defaults to
+//!   `break`.
+//!
+//! `result`: null.
+//!
+//! ### `stepping.cursor`
+//!
+//! Report the state of stepping. This method has no parameters.
+//!
+//! `result`:
+//! - `enabled`: Whether stepping is enabled.
+//! - `schedules`: The labels of the schedules with stepping enabled, in the order they run, or null
+//!   if they have not all run yet.
+//! - `cursor`: The `schedule` and `system` that will run next, or null.
+//!
+//! ### `diagnostics.storage`
+//!
+//! Report how the components and resources of the world are laid out in memory, as measured by
+//! [`StorageStats`](bevy_diagnostic::StorageStats). This method has no parameters.
+//!
+//! `result`:
+//! - `archetypes`: An array of archetypes, with their `id`, `table`, `entity_count` and
+//!   `component_count`.
+//! - `tables`: An array of tables, with their `id`, `entity_count`, `capacity` and `columns`.
+//! - `sparse_sets`: An array of sparse sets. Like table columns, each has a `component` id, a
+//!   `name`, a `len` and a size in `bytes`.
+//! - `resources`: An array of resources, with their `component` id, `name` and size in `bytes`.
+//! - `archetype_moves`: The total number of times an entity moved to another archetype, or `null`
+//!   without the `track_archetype_moves` feature.
+//!
+//! ### `assets.dependency_graph`
+//!
+//! Export the dependency graph of the assets tracked by the `AssetServer`. This is only available
+//! with the `bevy_asset` feature.
+//!
+//! `params` (optional):
+//! - `path` (optional): Only include the asset loaded from this path and its recursive
+//!   dependencies.
+//! - `dependents` (optional): If set along with `path`, include the assets that (recursively)
+//!   depend on the asset instead. Defaults to false.
+//! - `format` (optional): Either `json` (the default) or `dot`.
+//!
+//! `result`: For `json`, an object with:
+//! - `nodes`: An array of assets sorted by path, with their `id`, `type_path`, `path`,
+//!   `load_state`, number of `strong_handles` and `loader_dependencies`.
+//! - `edges`: An array of dependencies, each with the index of the `dependent` node and of its
+//!   `dependency` node.
+//!
+//! For `dot`, a string in the DOT language, with edges pointing from each asset to its
+//! dependencies.
+//!
 //! ### `rpc.discover`
 //!
 //! Discover available remote methods and server information. This follows the [`OpenRPC` specification for service discovery](https://spec.open-rpc.org/#service-discovery-method).
@@ -486,7 +582,7 @@
 //!
 //! `result`: An `OpenRPC` document containing:
 //! - Information about all available remote methods
-//! - Server connection information (when using HTTP transport)
+//! - Server connection information (when using the HTTP or WebSocket transports)
 //! - `OpenRPC` specification version
 //!
 //! ## Custom methods
@@ -560,6 +656,8 @@ pub mod builtin_methods;
 #[cfg(feature = "http")]
 pub mod http;
 pub mod schemas;
+#[cfg(feature = "websocket")]
+pub mod websocket;
 
 const CHANNEL_SIZE: usize = 16;
 
@@ -632,7 +730,7 @@ impl RemotePlugin {
     pub fn with_watching_method_main<M>(
         self,
         name: impl Into<String>,
-        handler: impl IntoSystem<In<Option<Value>>, BrpResult<Option<Value>>, M>,
+        handler: impl IntoSystem<In<(WatchingRequestId, Option<Value>)>, BrpResult<Option<Value>>, M>,
     ) -> Self {
         self.with_watching_method(name, handler, true)
     }
@@ -642,7 +740,7 @@ impl RemotePlugin {
     pub fn with_watching_method_render<M>(
         self,
         name: impl Into<String>,
-        handler: impl IntoSystem<In<Option<Value>>, BrpResult<Option<Value>>, M>,
+        handler: impl IntoSystem<In<(WatchingRequestId, Option<Value>)>, BrpResult<Option<Value>>, M>,
     ) -> Self {
         self.with_watching_method(name, handler, false)
     }
@@ -652,7 +750,7 @@ impl RemotePlugin {
     fn with_watching_method<M>(
         mut self,
         name: impl Into<String>,
-        handler: impl IntoSystem<In<Option<Value>>, BrpResult<Option<Value>>, M>,
+        handler: impl IntoSystem<In<(WatchingRequestId, Option<Value>)>, BrpResult<Option<Value>>, M>,
         to_main: bool,
     ) -> Self {
         (if to_main {
@@ -730,6 +828,11 @@ impl RemotePlugin {
             builtin_methods::process_remote_list_components_watching_request,
             to_main,
         )
+        .with_watching_method(
+            builtin_methods::BRP_QUERY_AND_WATCH_METHOD,
+            builtin_methods::process_remote_query_watching_request,
+            to_main,
+        )
         .with_method(
             builtin_methods::BRP_GET_RESOURCE_METHOD,
             builtin_methods::process_remote_get_resources_request,
@@ -785,6 +888,46 @@ impl RemotePlugin {
             builtin_methods::schedule_graph,
             to_main,
         )
+        .with_method(
+            builtin_methods::BRP_STEPPING_ENABLE_METHOD,
+            builtin_methods::process_remote_stepping_enable_request,
+            to_main,
+        )
+        .with_method(
+            builtin_methods::BRP_STEPPING_STEP_SYSTEM_METHOD,
+            builtin_methods::process_remote_stepping_step_system_request,
+            to_main,
+        )
+        .with_method(
+            builtin_methods::BRP_STEPPING_SET_BREAKPOINT_METHOD,
+            builtin_methods::process_remote_stepping_set_breakpoint_request,
+            to_main,
+        )
+        .with_method(
+            builtin_methods::BRP_STEPPING_CURSOR_METHOD,
+            builtin_methods::process_remote_stepping_cursor_request,
+            to_main,
+        )
+        .with_method(
+            builtin_methods::BRP_STORAGE_DIAGNOSTICS_METHOD,
+            builtin_methods::process_remote_storage_diagnostics_request,
+            to_main,
+        )
+        .add_asset_methods(to_main)
+    }
+
+    #[cfg(feature = "bevy_asset")]
+    fn add_asset_methods(self, to_main: bool) -> Self {
+        self.with_method(
+            builtin_methods::BRP_ASSET_DEPENDENCY_GRAPH_METHOD,
+            builtin_methods::process_remote_asset_dependency_graph_request,
+            to_main,
+        )
+    }
+
+    #[cfg(not(feature = "bevy_asset"))]
+    fn add_asset_methods(self, _to_main: bool) -> Self {
+        self
     }
 }
 
@@ -838,6 +981,7 @@ impl Plugin for RemotePlugin {
             .init_resource::<schemas::SchemaTypesMetadata>()
             .init_resource::<RemoteWatchingRequests>()
             .init_resource::<builtin_methods::BrpEventObservers>()
+            .init_resource::<builtin_methods::BrpQueryWatchers>()
             .add_systems(PreStartup, setup_mailbox_channel)
             .configure_sets(
                 RemoteLast,
@@ -927,7 +1071,11 @@ pub enum RemoteMethodHandler {
     /// A handler that only runs once and returns one response.
     Instant(Box<dyn System<In = In<Option<Value>>, Out = BrpResult>>),
     /// A handler that watches for changes and response when a change is detected.
-    Watching(Box<dyn System<In = In<Option<Value>>, Out = BrpResult<Option<Value>>>>),
+    Watching(
+        Box<
+            dyn System<In = In<(WatchingRequestId, Option<Value>)>, Out = BrpResult<Option<Value>>>,
+        >,
+    ),
 }
 
 /// The [`SystemId`] of a function that implements a remote instant method (`world.get_components`, `world.query`, etc.)
@@ -941,13 +1089,14 @@ pub type RemoteInstantMethodSystemId = SystemId<In<Option<Value>>, BrpResult>;
 
 /// The [`SystemId`] of a function that implements a remote watching method (`world.get_components+watch`, `world.list_components+watch`, etc.)
 ///
-/// The first parameter is the JSON value of the `params`. Typically, an
-/// implementation will deserialize these as the first thing they do.
+/// The first parameter is the [`WatchingRequestId`] of the request, along with the JSON value of
+/// the `params`. Typically, an implementation will deserialize these as the first thing they do.
 ///
 /// The optional returned JSON value will be sent as a response. If no
 /// changes were detected this should be [`None`]. Re-running of this
 /// handler is done in the [`RemotePlugin`].
-pub type RemoteWatchingMethodSystemId = SystemId<In<Option<Value>>, BrpResult<Option<Value>>>;
+pub type RemoteWatchingMethodSystemId =
+    SystemId<In<(WatchingRequestId, Option<Value>)>, BrpResult<Option<Value>>>;
 
 /// The [`SystemId`] of a function that can be used as a remote method.
 #[derive(Debug, Clone, Copy)]
@@ -994,7 +1143,18 @@ impl RemoteMethods {
 
 /// Holds the [`BrpMessage`]'s of all ongoing watching requests along with their handlers.
 #[derive(Debug, Resource, Default)]
-pub struct RemoteWatchingRequests(Vec<(BrpMessage, RemoteWatchingMethodSystemId)>);
+pub struct RemoteWatchingRequests {
+    requests: Vec<(BrpMessage, RemoteWatchingMethodSystemId, WatchingRequestId)>,
+    next_id: u64,
+}
+
+/// Identifies an ongoing watching request.
+///
+/// This is passed to the handler of a watching request along with its params. Handlers that keep
+/// state between polls can key it by this id, so that concurrent requests with identical params
+/// don't share their state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WatchingRequestId(pub u64);
 
 /// A single request from a Bevy Remote Protocol client to the server,
 /// serialized in JSON.
@@ -1513,10 +1673,10 @@ fn process_remote_requests(world: &mut World) {
                 let _ = message.sender.force_send(result);
             }
             RemoteMethodSystemId::Watching(id) => {
-                world
-                    .resource_mut::<RemoteWatchingRequests>()
-                    .0
-                    .push((message, id));
+                let mut requests = world.resource_mut::<RemoteWatchingRequests>();
+                let watching_id = WatchingRequestId(requests.next_id);
+                requests.next_id += 1;
+                requests.requests.push((message, id, watching_id));
             }
         }
     }
@@ -1526,8 +1686,9 @@ fn process_remote_requests(world: &mut World) {
 /// and handles it if so.
 fn process_ongoing_watching_requests(world: &mut World) {
     world.resource_scope::<RemoteWatchingRequests, ()>(|world, requests| {
-        for (message, system_id) in requests.0.iter() {
-            let handler_result = process_single_ongoing_watching_request(world, message, system_id);
+        for (message, system_id, watching_id) in requests.requests.iter() {
+            let handler_result =
+                process_single_ongoing_watching_request(world, message, system_id, *watching_id);
             let sender_result = match handler_result {
                 Ok(Some(value)) => message.sender.try_send(Ok(value)),
                 Err(err) => message.sender.try_send(Err(err)),
@@ -1546,9 +1707,10 @@ fn process_single_ongoing_watching_request(
     world: &mut World,
     message: &BrpMessage,
     system_id: &RemoteWatchingMethodSystemId,
+    watching_id: WatchingRequestId,
 ) -> BrpResult<Option<Value>> {
     world
-        .run_system_with(*system_id, message.params.clone())
+        .run_system_with(*system_id, (watching_id, message.params.clone()))
         .map_err(|error| BrpError {
             code: error_codes::INTERNAL_ERROR,
             message: format!("Failed to run method handler: {error}"),
@@ -1557,13 +1719,13 @@ fn process_single_ongoing_watching_request(
 }
 
 fn remove_closed_watching_requests(mut requests: ResMut<RemoteWatchingRequests>) {
-    for i in (0..requests.0.len()).rev() {
-        let Some((message, _)) = requests.0.get(i) else {
+    for i in (0..requests.requests.len()).rev() {
+        let Some((message, _, _)) = requests.requests.get(i) else {
             unreachable!()
         };
 
         if message.sender.is_closed() {
-            requests.0.swap_remove(i);
+            requests.requests.swap_remove(i);
         }
     }
 }
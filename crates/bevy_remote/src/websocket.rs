@@ -0,0 +1,824 @@
+//! The BRP transport using JSON-RPC over WebSocket connections.
+//!
+//! Adding the [`RemoteWebSocketPlugin`] to your [`App`] causes Bevy to accept
+//! WebSocket connections (by default, on port 15704) while your app is running.
+//!
+//! When `bevy_render` is enabled, a second port is available to query the render subapp.
+//!
+//! Unlike the [HTTP transport](crate::http), a single WebSocket connection can carry any number
+//! of requests at once. Each text message sent by the client holds a JSON-RPC request (or a batch
+//! of requests), and the responses are sent back as they complete, tagged with the `id` of the
+//! request they answer. Responses are therefore not guaranteed to arrive in the order the requests
+//! were sent.
+//!
+//! Notifications, which are requests without an `id`, are processed but never answered.
+//!
+//! Requests to `+watch` methods open a stream: every time the method produces a new value, a
+//! response carrying the `id` of the original request is sent. A stream stays open until the
+//! client cancels it with an [`rpc.unsubscribe`](UNSUBSCRIBE_METHOD) request or the connection
+//! is closed. Streaming requests must have a non-null `id` that is unique among the streams open
+//! on the connection, and can not be used in batch requests.
+//!
+//! ### `rpc.unsubscribe`
+//!
+//! Cancel a stream opened by an earlier `+watch` request on the same connection. This method is
+//! handled by the transport itself and never reaches the [`World`](bevy_ecs::world::World).
+//!
+//! `params`:
+//! - `id`: The `id` of the `+watch` request whose stream should be closed.
+//!
+//! `result`: `null`.
+
+#![cfg(not(target_family = "wasm"))]
+
+#[cfg(feature = "bevy_render")]
+use crate::setup_mailbox_channel;
+use crate::{error_codes, BrpBatch, BrpError, BrpMessage, BrpRequest, BrpResponse, BrpSender};
+use anyhow::Result as AnyhowResult;
+use async_channel::Sender;
+use async_io::Async;
+use async_tungstenite::{accept_async, tungstenite::Message};
+use bevy_app::{App, Plugin, Startup};
+use bevy_ecs::resource::Resource;
+#[cfg(feature = "bevy_render")]
+use bevy_ecs::schedule::IntoScheduleConfigs as _;
+use bevy_ecs::system::Res;
+use bevy_platform::collections::HashMap;
+#[cfg(feature = "bevy_render")]
+use bevy_render::{RenderApp, RenderStartup};
+use bevy_tasks::{
+    futures_lite::{AsyncRead, AsyncWrite, StreamExt},
+    IoTaskPool, Task,
+};
+use core::net::{IpAddr, Ipv4Addr};
+use serde::Deserialize;
+use serde_json::Value;
+use std::net::TcpListener;
+
+/// The default port that Bevy will listen on for WebSocket connections.
+///
+/// This value was chosen to follow the ports used by the [HTTP transport](crate::http).
+pub const DEFAULT_PORT: u16 = 15704;
+
+/// The default port that Bevy will listen on for WebSocket connections to the render subapp.
+///
+/// The render subapp is available for requests if the `bevy_render` feature is enabled.
+pub const DEFAULT_RENDER_PORT: u16 = 15705;
+
+/// The default host address that Bevy will use for its server.
+pub const DEFAULT_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
+
+/// The method path for a `rpc.unsubscribe` request.
+///
+/// This method is handled by the WebSocket transport and cancels a `+watch` stream.
+pub const UNSUBSCRIBE_METHOD: &str = "rpc.unsubscribe";
+
+/// Add this plugin to your [`App`] to allow remote WebSocket connections to inspect and
+/// modify entities. It requires the [`RemotePlugin`](super::RemotePlugin).
+///
+/// This BRP transport cannot be used when targeting WASM.
+///
+/// The defaults are:
+/// - [`DEFAULT_ADDR`] : 127.0.0.1.
+/// - [`DEFAULT_PORT`] : 15704.
+/// - [`DEFAULT_RENDER_PORT`] : 15705. (when `bevy_render` is enabled)
+///
+/// This plugin can be used alongside the [`RemoteHttpPlugin`](crate::http::RemoteHttpPlugin),
+/// as long as the two are configured to listen on different ports.
+pub struct RemoteWebSocketPlugin {
+    /// The address that Bevy will bind to.
+    address: IpAddr,
+    /// The port that Bevy will listen on.
+    port: u16,
+    /// The port that Bevy will listen on for render subapp.
+    render_port: u16,
+}
+
+impl Default for RemoteWebSocketPlugin {
+    fn default() -> Self {
+        Self {
+            address: DEFAULT_ADDR,
+            port: DEFAULT_PORT,
+            render_port: DEFAULT_RENDER_PORT,
+        }
+    }
+}
+
+impl Plugin for RemoteWebSocketPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WebSocketHostAddress(self.address))
+            .insert_resource(WebSocketHostPort(self.port))
+            .add_systems(Startup, start_websocket_server);
+
+        #[cfg(feature = "bevy_render")]
+        {
+            use bevy_ecs::schedule::common_conditions::run_once;
+
+            let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+                return;
+            };
+
+            render_app
+                .insert_resource(WebSocketHostAddress(self.address))
+                .insert_resource(WebSocketHostPort(self.render_port))
+                .add_systems(
+                    RenderStartup,
+                    start_websocket_server
+                        .run_if(run_once)
+                        .after(setup_mailbox_channel),
+                );
+        }
+    }
+}
+
+impl RemoteWebSocketPlugin {
+    /// Set the IP address that the server will use.
+    #[must_use]
+    pub fn with_address(mut self, address: impl Into<IpAddr>) -> Self {
+        self.address = address.into();
+        self
+    }
+    /// Set the remote port that the server will listen on.
+    #[must_use]
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+    /// Set the remote port that the server will listen on for the render subapp.
+    #[must_use]
+    pub fn with_render_port(mut self, port: u16) -> Self {
+        self.render_port = port;
+        self
+    }
+}
+
+/// A resource containing the IP address that the WebSocket server will host on.
+///
+/// Currently, changing this while the application is running has no effect; this merely
+/// reflects the IP address that is set during the setup of the [`RemoteWebSocketPlugin`].
+#[derive(Debug, Resource)]
+pub struct WebSocketHostAddress(pub IpAddr);
+
+/// A resource containing the port number that the WebSocket server will listen on.
+///
+/// Currently, changing this while the application is running has no effect; this merely
+/// reflects the port that is set during the setup of the [`RemoteWebSocketPlugin`].
+#[derive(Debug, Resource)]
+pub struct WebSocketHostPort(pub u16);
+
+/// `rpc.unsubscribe`: The parameters of a request cancelling a `+watch` stream.
+#[derive(Debug, Deserialize)]
+struct UnsubscribeParams {
+    /// The `id` of the request that opened the stream.
+    id: Value,
+}
+
+/// A system that starts up the Bevy Remote Protocol WebSocket server.
+fn start_websocket_server(
+    request_sender: Res<BrpSender>,
+    address: Res<WebSocketHostAddress>,
+    remote_port: Res<WebSocketHostPort>,
+) {
+    IoTaskPool::get()
+        .spawn(server_main(
+            address.0,
+            remote_port.0,
+            request_sender.clone(),
+        ))
+        .detach();
+}
+
+/// The Bevy Remote Protocol WebSocket server main loop.
+async fn server_main(
+    address: IpAddr,
+    port: u16,
+    request_sender: Sender<BrpMessage>,
+) -> AnyhowResult<()> {
+    let listener = Async::<TcpListener>::bind((address, port))?;
+
+    loop {
+        let (client, _) = listener.accept().await?;
+
+        let request_sender = request_sender.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let _ = handle_client(client, request_sender).await;
+            })
+            .detach();
+    }
+}
+
+/// Serves a single WebSocket connection until either side closes it.
+async fn handle_client<S>(client: S, request_sender: Sender<BrpMessage>) -> AnyhowResult<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (mut socket_sender, mut socket_receiver) = accept_async(client).await?.split();
+
+    // Responses are produced by many tasks at once, so they are funneled through a channel and
+    // written to the socket by a single task.
+    let (response_sender, response_receiver) = async_channel::unbounded::<String>();
+    let writer = IoTaskPool::get().spawn(async move {
+        while let Ok(response) = response_receiver.recv().await {
+            if socket_sender.send(Message::text(response)).await.is_err() {
+                break;
+            }
+        }
+        let _ = socket_sender.close(None).await;
+    });
+
+    let mut connection = Connection {
+        request_sender,
+        response_sender,
+        streams: HashMap::default(),
+    };
+
+    while let Some(message) = socket_receiver.next().await {
+        let batch = match message? {
+            Message::Text(text) => serde_json::from_str::<BrpBatch>(&text),
+            Message::Binary(bytes) => serde_json::from_slice::<BrpBatch>(&bytes),
+            Message::Close(_) => break,
+            _ => continue,
+        };
+
+        connection.process_request_batch(batch).await?;
+    }
+
+    // Dropping the connection cancels every stream that is still open, which closes the
+    // corresponding watching requests in the world, and lets the writer task finish.
+    drop(connection);
+    writer.await;
+
+    Ok(())
+}
+
+/// The state of a single WebSocket connection.
+struct Connection {
+    /// The channel on which requests are forwarded to the world.
+    request_sender: Sender<BrpMessage>,
+    /// The channel on which serialized responses are queued to be written to the socket.
+    response_sender: Sender<String>,
+    /// The tasks forwarding `+watch` streams to the client, keyed by their serialized request `id`.
+    ///
+    /// Dropping one of these tasks cancels the stream.
+    streams: HashMap<String, Task<()>>,
+}
+
+impl Connection {
+    /// Handles a message containing a single request or a batch of requests.
+    async fn process_request_batch(
+        &mut self,
+        batch: Result<BrpBatch, serde_json::Error>,
+    ) -> AnyhowResult<()> {
+        self.streams.retain(|_, stream| !stream.is_finished());
+
+        match batch {
+            Ok(BrpBatch::Single(request)) => {
+                if let Some(response) = self.process_single_request(request).await? {
+                    self.response_sender
+                        .send(serde_json::to_string(&response)?)
+                        .await?;
+                }
+            }
+            Ok(BrpBatch::Batch(requests)) => {
+                let mut pending = Vec::with_capacity(requests.len());
+                for request in requests {
+                    pending.push(self.start_request(request, true).await);
+                }
+
+                // Answering the batch may take several frames, so it is done in the background
+                // in order to keep serving the other requests on this connection.
+                let response_sender = self.response_sender.clone();
+                IoTaskPool::get()
+                    .spawn(async move {
+                        let mut responses = Vec::with_capacity(pending.len());
+                        for request in pending {
+                            responses.extend(request.into_response().await);
+                        }
+                        // A batch made only of notifications isn't answered at all.
+                        if responses.is_empty() {
+                            return;
+                        }
+                        if let Ok(serialized) = serde_json::to_string(&responses) {
+                            let _ = response_sender.send(serialized).await;
+                        }
+                    })
+                    .detach();
+            }
+            Err(err) => {
+                let response = BrpResponse::new(
+                    None,
+                    Err(BrpError {
+                        code: error_codes::INVALID_REQUEST,
+                        message: err.to_string(),
+                        data: None,
+                    }),
+                );
+                self.response_sender
+                    .send(serde_json::to_string(&response)?)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handles a single request that is not part of a batch.
+    ///
+    /// Returns the response if it is immediately available; otherwise it will be sent to the
+    /// client once the world has processed the request.
+    async fn process_single_request(
+        &mut self,
+        request: Value,
+    ) -> AnyhowResult<Option<BrpResponse>> {
+        match self.start_request(request, false).await {
+            PendingRequest::Complete(response) => Ok(Some(response)),
+            PendingRequest::Waiting { id, receiver } => {
+                let response_sender = self.response_sender.clone();
+                IoTaskPool::get()
+                    .spawn(async move {
+                        let Ok(result) = receiver.recv().await else {
+                            return;
+                        };
+                        if let Ok(serialized) = serde_json::to_string(&BrpResponse::new(id, result))
+                        {
+                            let _ = response_sender.send(serialized).await;
+                        }
+                    })
+                    .detach();
+                Ok(None)
+            }
+            PendingRequest::Streaming | PendingRequest::Notification => Ok(None),
+        }
+    }
+
+    /// Parses a request and forwards it to the world.
+    ///
+    /// Requests are forwarded in the order they are received, so that a client may rely on the
+    /// effects of an earlier request being visible to a later one.
+    async fn start_request(&mut self, request: Value, in_batch: bool) -> PendingRequest {
+        // Reach in and get the request ID early so that we can report it even when parsing fails.
+        let id = request.as_object().and_then(|map| map.get("id")).cloned();
+
+        let request: BrpRequest = match serde_json::from_value(request) {
+            Ok(v) => v,
+            Err(err) => {
+                return PendingRequest::error(id, error_codes::INVALID_REQUEST, err.to_string());
+            }
+        };
+
+        if id.is_none() {
+            self.start_notification(request).await;
+            return PendingRequest::Notification;
+        }
+
+        if request.method == UNSUBSCRIBE_METHOD {
+            return PendingRequest::Complete(BrpResponse::new(
+                request.id,
+                self.unsubscribe(request.params),
+            ));
+        }
+
+        let watch = request.method.contains("+watch");
+        let stream_key = if watch {
+            if in_batch {
+                return PendingRequest::error(
+                    request.id,
+                    error_codes::INVALID_REQUEST,
+                    "Streaming can not be used in batch requests".to_string(),
+                );
+            }
+            let Some(key) = request
+                .id
+                .as_ref()
+                .filter(|id| !id.is_null())
+                .map(stream_key)
+            else {
+                return PendingRequest::error(
+                    request.id,
+                    error_codes::INVALID_REQUEST,
+                    "Streaming requests must have an id".to_string(),
+                );
+            };
+            if self.streams.contains_key(&key) {
+                return PendingRequest::error(
+                    request.id,
+                    error_codes::INVALID_REQUEST,
+                    format!("A stream with the id {key} is already open"),
+                );
+            }
+            Some(key)
+        } else {
+            None
+        };
+
+        let size = if watch { 8 } else { 1 };
+        let (result_sender, result_receiver) = async_channel::bounded(size);
+
+        let _ = self
+            .request_sender
+            .send(BrpMessage {
+                method: request.method,
+                params: request.params,
+                sender: result_sender,
+            })
+            .await;
+
+        let Some(key) = stream_key else {
+            return PendingRequest::Waiting {
+                id: request.id,
+                receiver: result_receiver,
+            };
+        };
+
+        let id = request.id;
+        let response_sender = self.response_sender.clone();
+        let stream = IoTaskPool::get().spawn(async move {
+            while let Ok(result) = result_receiver.recv().await {
+                let Ok(serialized) = serde_json::to_string(&BrpResponse::new(id.clone(), result))
+                else {
+                    continue;
+                };
+                if response_sender.send(serialized).await.is_err() {
+                    break;
+                }
+            }
+        });
+        self.streams.insert(key, stream);
+
+        PendingRequest::Streaming
+    }
+
+    /// Forwards a notification to the world, without waiting for its result.
+    ///
+    /// Streams can't be delivered without an `id`, so `+watch` notifications are ignored.
+    async fn start_notification(&mut self, request: BrpRequest) {
+        if request.method == UNSUBSCRIBE_METHOD {
+            let _ = self.unsubscribe(request.params);
+            return;
+        }
+        if request.method.contains("+watch") {
+            return;
+        }
+
+        // The receiver is dropped right away, so the result of the request is discarded.
+        let (result_sender, _) = async_channel::bounded(1);
+        let _ = self
+            .request_sender
+            .send(BrpMessage {
+                method: request.method,
+                params: request.params,
+                sender: result_sender,
+            })
+            .await;
+    }
+
+    /// Handles a `rpc.unsubscribe` request by cancelling the matching stream.
+    fn unsubscribe(&mut self, params: Option<Value>) -> Result<Value, BrpError> {
+        let Some(params) = params else {
+            return Err(BrpError {
+                code: error_codes::INVALID_PARAMS,
+                message: String::from("Params not provided"),
+                data: None,
+            });
+        };
+        let UnsubscribeParams { id } = serde_json::from_value(params).map_err(|err| BrpError {
+            code: error_codes::INVALID_PARAMS,
+            message: err.to_string(),
+            data: None,
+        })?;
+
+        let key = stream_key(&id);
+        // Dropping the task stops forwarding the stream and drops its receiver, which closes
+        // the watching request on the world side.
+        match self.streams.remove(&key) {
+            Some(_) => Ok(Value::Null),
+            None => Err(BrpError {
+                code: error_codes::INVALID_PARAMS,
+                message: format!("No stream with the id {key} is open"),
+                data: None,
+            }),
+        }
+    }
+}
+
+/// A request that has been forwarded to the world, or answered right away.
+enum PendingRequest {
+    /// The request has already been answered.
+    Complete(BrpResponse),
+    /// The request is waiting for the world to process it.
+    Waiting {
+        id: Option<Value>,
+        receiver: async_channel::Receiver<crate::BrpResult>,
+    },
+    /// The request opened a stream, whose responses are sent as they are produced.
+    Streaming,
+    /// The request is a notification, which is never answered.
+    Notification,
+}
+
+impl PendingRequest {
+    fn error(id: Option<Value>, code: i16, message: String) -> Self {
+        Self::Complete(BrpResponse::new(
+            id,
+            Err(BrpError {
+                code,
+                message,
+                data: None,
+            }),
+        ))
+    }
+
+    /// Waits for the world to answer the request, returning `None` for notifications.
+    ///
+    /// Streaming requests are never part of a batch, so they never reach this point.
+    async fn into_response(self) -> Option<BrpResponse> {
+        let response = match self {
+            PendingRequest::Complete(response) => response,
+            PendingRequest::Waiting { id, receiver } => {
+                let result = receiver.recv().await.unwrap_or_else(|_| {
+                    Err(BrpError {
+                        code: error_codes::INTERNAL_ERROR,
+                        message: "The request was dropped before being processed".to_string(),
+                        data: None,
+                    })
+                });
+                BrpResponse::new(id, result)
+            }
+            PendingRequest::Notification => return None,
+            PendingRequest::Streaming => unreachable!(),
+        };
+        Some(response)
+    }
+}
+
+/// The key identifying a stream by the `id` of the request that opened it.
+fn stream_key(id: &Value) -> String {
+    id.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_tungstenite::{client_async, WebSocketStream};
+    use bevy_tasks::{block_on, futures_lite::future::poll_once, TaskPool};
+    use core::{
+        pin::{pin, Pin},
+        task::{Context, Poll},
+    };
+    use serde_json::json;
+    use std::io;
+
+    /// One end of an in-memory, bidirectional byte stream.
+    struct Duplex {
+        reader: piper::Reader,
+        writer: piper::Writer,
+    }
+
+    impl AsyncRead for Duplex {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.reader).poll_read(cx, buf)
+        }
+    }
+
+    impl AsyncWrite for Duplex {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.writer).poll_write(cx, buf)
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.writer).poll_flush(cx)
+        }
+
+        fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.writer).poll_close(cx)
+        }
+    }
+
+    /// Runs `future` to completion, along with the tasks spawned on this thread's local executor,
+    /// which is where the single-threaded [`TaskPool`] runs them.
+    fn run<T>(future: impl Future<Output = T>) -> T {
+        let mut future = pin!(future);
+        loop {
+            if let Some(output) = block_on(poll_once(&mut future)) {
+                return output;
+            }
+            IoTaskPool::get().with_local_executor(|executor| while executor.try_tick() {});
+            std::thread::yield_now();
+        }
+    }
+
+    /// Connects a client to a server running [`handle_client`] over an in-memory stream.
+    ///
+    /// Returns the client, and the receiver of the requests the server forwards to the world.
+    fn connect() -> (WebSocketStream<Duplex>, async_channel::Receiver<BrpMessage>) {
+        IoTaskPool::get_or_init(TaskPool::new);
+        let (client_reader, server_writer) = piper::pipe(4096);
+        let (server_reader, client_writer) = piper::pipe(4096);
+        let (request_sender, request_receiver) = async_channel::unbounded();
+        let server = Duplex {
+            reader: server_reader,
+            writer: server_writer,
+        };
+        IoTaskPool::get()
+            .spawn(async move {
+                let _ = handle_client(server, request_sender).await;
+            })
+            .detach();
+
+        let client = Duplex {
+            reader: client_reader,
+            writer: client_writer,
+        };
+        let (client, _) = run(client_async("ws://localhost/", client)).unwrap();
+        (client, request_receiver)
+    }
+
+    fn send(client: &mut WebSocketStream<Duplex>, request: Value) {
+        run(client.send(Message::text(request.to_string()))).unwrap();
+    }
+
+    fn receive(client: &mut WebSocketStream<Duplex>) -> Value {
+        match run(client.next()).unwrap().unwrap() {
+            Message::Text(text) => serde_json::from_str(&text).unwrap(),
+            message => panic!("unexpected message {message:?}"),
+        }
+    }
+
+    fn request(id: Value, method: &str) -> Value {
+        json!({ "jsonrpc": "2.0", "id": id, "method": method })
+    }
+
+    /// Waits for the server to drop the receiving end of a request.
+    fn wait_until_closed(sender: &Sender<crate::BrpResult>) {
+        run(core::future::poll_fn(|_| {
+            if sender.is_closed() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }));
+    }
+
+    #[test]
+    fn routes_responses_by_request_id() {
+        let (mut client, requests) = connect();
+
+        send(&mut client, request(json!(1), "a"));
+        send(&mut client, request(json!(2), "b"));
+        let a = run(requests.recv()).unwrap();
+        let b = run(requests.recv()).unwrap();
+        assert_eq!((a.method.as_str(), b.method.as_str()), ("a", "b"));
+
+        // Responses are sent as soon as they are ready, tagged with the id of their request.
+        run(b.sender.send(Ok(json!("b")))).unwrap();
+        let response = receive(&mut client);
+        assert_eq!(
+            (&response["id"], &response["result"]),
+            (&json!(2), &json!("b"))
+        );
+        run(a.sender.send(Ok(json!("a")))).unwrap();
+        let response = receive(&mut client);
+        assert_eq!(
+            (&response["id"], &response["result"]),
+            (&json!(1), &json!("a"))
+        );
+
+        // Batches are answered with a single message, in request order.
+        send(
+            &mut client,
+            json!([
+                request(json!(3), "c"),
+                request(json!(4), "world.query+watch")
+            ]),
+        );
+        let c = run(requests.recv()).unwrap();
+        run(c.sender.send(Ok(json!("c")))).unwrap();
+        let responses = receive(&mut client);
+        assert_eq!(responses[0]["result"], json!("c"));
+        assert_eq!(responses[1]["id"], json!(4));
+        assert_eq!(
+            responses[1]["error"]["code"],
+            json!(error_codes::INVALID_REQUEST)
+        );
+
+        run(client.send(Message::text("not json"))).unwrap();
+        let response = receive(&mut client);
+        assert_eq!(response["id"], Value::Null);
+        assert_eq!(
+            response["error"]["code"],
+            json!(error_codes::INVALID_REQUEST)
+        );
+    }
+
+    #[test]
+    fn notifications_are_not_answered() {
+        let (mut client, requests) = connect();
+        let notification = |method: &str| json!({ "jsonrpc": "2.0", "method": method });
+
+        // Notifications still reach the world, but their results are discarded.
+        send(&mut client, notification("a"));
+        let a = run(requests.recv()).unwrap();
+        assert_eq!(a.method, "a");
+        assert!(a.sender.is_closed());
+
+        // Batches only answer the requests which have an id, and aren't answered at all if they
+        // only hold notifications.
+        send(
+            &mut client,
+            json!([notification("b"), request(json!(1), "c")]),
+        );
+        let b = run(requests.recv()).unwrap();
+        let c = run(requests.recv()).unwrap();
+        assert_eq!((b.method.as_str(), c.method.as_str()), ("b", "c"));
+        run(c.sender.send(Ok(json!("c")))).unwrap();
+        let responses = receive(&mut client);
+        assert_eq!(responses.as_array().map(Vec::len), Some(1));
+        assert_eq!(responses[0]["id"], json!(1));
+
+        send(&mut client, json!([notification("d")]));
+        send(&mut client, request(json!(2), "e"));
+        let d = run(requests.recv()).unwrap();
+        let e = run(requests.recv()).unwrap();
+        assert_eq!((d.method.as_str(), e.method.as_str()), ("d", "e"));
+        run(e.sender.send(Ok(json!("e")))).unwrap();
+        let response = receive(&mut client);
+        assert_eq!(response["id"], json!(2));
+    }
+
+    #[test]
+    fn watch_streams_are_independent_and_cancellable() {
+        let (mut client, requests) = connect();
+
+        send(&mut client, request(json!("x"), "world.query+watch"));
+        send(&mut client, request(json!("y"), "world.query+watch"));
+        let x = run(requests.recv()).unwrap();
+        let y = run(requests.recv()).unwrap();
+
+        // Each stream keeps its own order, and is tagged with the id that opened it.
+        run(x.sender.send(Ok(json!(1)))).unwrap();
+        run(y.sender.send(Ok(json!(2)))).unwrap();
+        run(x.sender.send(Ok(json!(3)))).unwrap();
+        let mut x_results = Vec::new();
+        let mut y_results = Vec::new();
+        for _ in 0..3 {
+            let response = receive(&mut client);
+            match response["id"].as_str() {
+                Some("x") => x_results.push(response["result"].clone()),
+                Some("y") => y_results.push(response["result"].clone()),
+                _ => panic!("unexpected response {response}"),
+            }
+        }
+        assert_eq!(x_results, [json!(1), json!(3)]);
+        assert_eq!(y_results, [json!(2)]);
+
+        // Stream ids must be unique on the connection.
+        send(&mut client, request(json!("x"), "world.query+watch"));
+        let response = receive(&mut client);
+        assert_eq!(
+            response["error"]["code"],
+            json!(error_codes::INVALID_REQUEST)
+        );
+
+        // Cancelling a stream closes its request, and leaves the other stream open.
+        send(
+            &mut client,
+            json!({ "jsonrpc": "2.0", "id": 5, "method": UNSUBSCRIBE_METHOD, "params": { "id": "x" } }),
+        );
+        let response = receive(&mut client);
+        assert_eq!(
+            (&response["id"], &response["result"]),
+            (&json!(5), &Value::Null)
+        );
+        wait_until_closed(&x.sender);
+
+        run(y.sender.send(Ok(json!(4)))).unwrap();
+        let response = receive(&mut client);
+        assert_eq!(
+            (&response["id"], &response["result"]),
+            (&json!("y"), &json!(4))
+        );
+
+        send(
+            &mut client,
+            json!({ "jsonrpc": "2.0", "id": 6, "method": UNSUBSCRIBE_METHOD, "params": { "id": "x" } }),
+        );
+        let response = receive(&mut client);
+        assert_eq!(
+            response["error"]["code"],
+            json!(error_codes::INVALID_PARAMS)
+        );
+
+        // Closing the connection closes the streams that are still open.
+        drop(client);
+        wait_until_closed(&y.sender);
+    }
+}
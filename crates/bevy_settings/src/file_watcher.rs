@@ -0,0 +1,116 @@
+//! Watches settings files for changes made outside of the app.
+
+use async_channel::{Receiver, Sender};
+use bevy_ecs::resource::Resource;
+use bevy_log::{debug, error, warn};
+use core::time::Duration;
+use notify_debouncer_full::{
+    new_debouncer,
+    notify::{
+        self,
+        event::{AccessKind, AccessMode},
+        EventKind, RecommendedWatcher, RecursiveMode,
+    },
+    DebounceEventResult, Debouncer, RecommendedCache,
+};
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// How long to wait (and debounce duplicate events) before reporting a change.
+///
+/// Settings are saved by writing a temporary file and renaming it, which produces several
+/// events in quick succession.
+const DEBOUNCE_WAIT_TIME: Duration = Duration::from_millis(300);
+
+/// Watches the directories containing settings files, and reports the names (without
+/// extension) of the settings files which were created, modified or removed.
+///
+/// This uses [`notify_debouncer_full`] to retrieve "debounced" filesystem events, like the
+/// `bevy_asset` file watcher.
+#[derive(Resource)]
+pub(crate) struct SettingsWatcher {
+    _watcher: Debouncer<RecommendedWatcher, RecommendedCache>,
+    receiver: Receiver<String>,
+}
+
+impl SettingsWatcher {
+    /// Starts watching the given directories. Directories which don't exist are created if
+    /// `create` is set, and skipped otherwise.
+    pub(crate) fn new<'a>(
+        dirs: impl IntoIterator<Item = (&'a Path, bool)>,
+    ) -> Result<Self, notify::Error> {
+        let (sender, receiver) = async_channel::unbounded();
+        let mut watcher = new_debouncer(DEBOUNCE_WAIT_TIME, None, move |result| {
+            send_changed_files(result, &sender);
+        })?;
+
+        for (dir, create) in dirs {
+            if create && let Err(e) = fs::DirBuilder::new().recursive(true).create(dir) {
+                warn!("Could not create settings directory: {:?}", e);
+                continue;
+            }
+            if !dir.is_dir() {
+                continue;
+            }
+            debug!("Watching settings in {:?}", dir);
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+
+        Ok(Self {
+            _watcher: watcher,
+            receiver,
+        })
+    }
+
+    /// Returns the settings files which changed since the last call, without duplicates.
+    pub(crate) fn changed_files(&self) -> HashSet<String> {
+        core::iter::from_fn(|| self.receiver.try_recv().ok()).collect()
+    }
+}
+
+fn send_changed_files(result: DebounceEventResult, sender: &Sender<String>) {
+    let events = match result {
+        Ok(events) => events,
+        Err(errors) => {
+            errors.iter().for_each(|error| {
+                error!("Encountered a filesystem watcher error {error:?}");
+            });
+            return;
+        }
+    };
+
+    let mut changed = HashSet::new();
+    for event in events.iter() {
+        match event.kind {
+            EventKind::Create(_)
+            | EventKind::Modify(_)
+            | EventKind::Remove(_)
+            | EventKind::Access(AccessKind::Close(AccessMode::Write)) => {}
+            _ => continue,
+        }
+        // The temporary `.toml.new` files written while saving are ignored here, but the
+        // rename which replaces the settings file is not.
+        changed.extend(
+            event
+                .paths
+                .iter()
+                .map(PathBuf::as_path)
+                .filter_map(settings_file_name),
+        );
+    }
+
+    for filename in changed {
+        let _ = sender.send_blocking(filename);
+    }
+}
+
+/// Returns the name of the settings file at `path`, or `None` if it isn't a settings file.
+fn settings_file_name(path: &Path) -> Option<String> {
+    if path.extension()? != "toml" {
+        return None;
+    }
+    Some(path.file_stem()?.to_str()?.to_string())
+}
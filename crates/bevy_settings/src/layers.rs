@@ -0,0 +1,261 @@
+//! Layered resolution of settings files.
+//!
+//! Each settings file is resolved from several layers, from lowest to highest precedence:
+//! shipped defaults, an optional system-wide file, the user file, and finally overrides from the
+//! command line or environment together with values locked by the system-wide file.
+
+use bevy_log::warn;
+
+/// The key of the list of locked settings in a system-wide settings file.
+///
+/// Each entry is a dotted path such as `"audio_settings.volume"`, or the name of a whole group.
+/// Locked values take precedence over the user file and over command line and environment
+/// overrides, and are never written to the user file.
+pub const SETTINGS_LOCKED_KEY: &str = "locked";
+
+/// The layers a single settings file is resolved from.
+#[derive(Default, Clone, Debug)]
+pub(crate) struct SettingsLayers {
+    /// The shipped defaults merged with the system-wide file.
+    pub(crate) base: toml::Table,
+    /// The contents of the user file, as last loaded or saved.
+    pub(crate) user: toml::Table,
+    /// The values which take precedence over the user file: overrides and locked values.
+    pub(crate) fixed: toml::Table,
+}
+
+impl SettingsLayers {
+    /// Merges all layers into the table that is applied to the settings resources.
+    pub(crate) fn resolve(&self) -> toml::Table {
+        let mut table = self.base.clone();
+        merge(&mut table, &self.user);
+        merge(&mut table, &self.fixed);
+        table
+    }
+
+    /// Computes the contents of the user file from the `current` values of the settings
+    /// resources.
+    ///
+    /// Only values which differ from the base layer are kept. Values coming from the fixed layer
+    /// are not written; whatever the user file previously held for them is preserved instead.
+    pub(crate) fn user_table(&self, current: &toml::Table) -> toml::Table {
+        let mut table = diff(current, &self.base);
+        for path in leaf_paths(&self.fixed) {
+            match get_path(&self.user, &path) {
+                Some(value) => set_path(&mut table, &path, value.clone()),
+                None => remove_path(&mut table, &path),
+            }
+        }
+        table
+    }
+}
+
+/// Recursively merges `from` into `into`, with the values of `from` taking precedence.
+pub(crate) fn merge(into: &mut toml::Table, from: &toml::Table) {
+    for (key, value) in from {
+        match (into.get_mut(key), value) {
+            (Some(toml::Value::Table(into)), toml::Value::Table(from)) => merge(into, from),
+            _ => {
+                into.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Returns the values of `current` which are missing from or different in `base`.
+fn diff(current: &toml::Table, base: &toml::Table) -> toml::Table {
+    let mut table = toml::Table::new();
+    for (key, value) in current {
+        match (value, base.get(key)) {
+            (toml::Value::Table(current), Some(toml::Value::Table(base))) => {
+                let nested = diff(current, base);
+                if !nested.is_empty() {
+                    table.insert(key.clone(), toml::Value::Table(nested));
+                }
+            }
+            (value, Some(base)) if value == base => {}
+            (value, _) => {
+                table.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    table
+}
+
+/// Returns the path of every non-table value in `table`.
+fn leaf_paths(table: &toml::Table) -> Vec<Vec<String>> {
+    let mut paths = Vec::new();
+    for (key, value) in table {
+        match value {
+            toml::Value::Table(nested) => {
+                for mut path in leaf_paths(nested) {
+                    path.insert(0, key.clone());
+                    paths.push(path);
+                }
+            }
+            _ => paths.push(vec![key.clone()]),
+        }
+    }
+    paths
+}
+
+fn get_path<'a>(table: &'a toml::Table, path: &[String]) -> Option<&'a toml::Value> {
+    let (last, parents) = path.split_last()?;
+    let mut table = table;
+    for key in parents {
+        table = table.get(key)?.as_table()?;
+    }
+    table.get(last)
+}
+
+fn set_path(table: &mut toml::Table, path: &[String], value: toml::Value) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    let mut table = table;
+    for key in parents {
+        let entry = table
+            .entry(key.clone())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if !entry.is_table() {
+            *entry = toml::Value::Table(toml::Table::new());
+        }
+        table = entry.as_table_mut().unwrap();
+    }
+    table.insert(last.clone(), value);
+}
+
+/// Removes the value at `path`, along with any table left empty by its removal.
+fn remove_path(table: &mut toml::Table, path: &[String]) {
+    let Some((first, rest)) = path.split_first() else {
+        return;
+    };
+    if rest.is_empty() {
+        table.remove(first);
+        return;
+    }
+    if let Some(toml::Value::Table(nested)) = table.get_mut(first) {
+        remove_path(nested, rest);
+        if nested.is_empty() {
+            table.remove(first);
+        }
+    }
+}
+
+/// Removes the [`SETTINGS_LOCKED_KEY`] list from a system-wide settings file, and returns the
+/// values it locks.
+pub(crate) fn take_locked(system: &mut toml::Table, filename: &str) -> toml::Table {
+    let mut locked = toml::Table::new();
+    let Some(paths) = system.remove(SETTINGS_LOCKED_KEY) else {
+        return locked;
+    };
+    let Some(paths) = paths.as_array() else {
+        warn!("{SETTINGS_LOCKED_KEY} in the system-wide {filename}.toml must be an array");
+        return locked;
+    };
+
+    for path in paths {
+        let Some(path) = path.as_str() else {
+            warn!("Invalid entry {path} in {SETTINGS_LOCKED_KEY} of {filename}.toml");
+            continue;
+        };
+        let path = split_path(path);
+        match get_path(system, &path) {
+            Some(value) => set_path(&mut locked, &path, value.clone()),
+            None => warn!(
+                "Locked setting {} not found in the system-wide {filename}.toml",
+                path.join(".")
+            ),
+        }
+    }
+    locked
+}
+
+/// Splits a dotted settings path such as `audio_settings.volume` into its keys.
+pub(crate) fn split_path(path: &str) -> Vec<String> {
+    path.split('.').map(ToString::to_string).collect()
+}
+
+/// Adds the value at the dotted `path` to `overrides`.
+pub(crate) fn insert_override(overrides: &mut toml::Table, path: &str, value: toml::Value) {
+    set_path(overrides, &split_path(path), value);
+}
+
+/// Parses an override of the form `path=value`.
+///
+/// The value is parsed as a TOML value; if that fails, it is used as a string.
+pub(crate) fn parse_override(assignment: &str) -> Option<(&str, toml::Value)> {
+    let (path, value) = assignment.split_once('=')?;
+    let path = path.trim();
+    if path.is_empty() {
+        return None;
+    }
+    Some((path, parse_value(value.trim())))
+}
+
+/// Parses a TOML value, falling back to a string if `value` is not valid TOML.
+pub(crate) fn parse_value(value: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {value}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(toml: &str) -> toml::Table {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn user_table_only_keeps_differences() {
+        let layers = SettingsLayers {
+            base: table("[audio]\nvolume = 0.8\nmuted = false\n[video]\nvsync = true\n"),
+            user: table("[video]\nfov = 90\n"),
+            fixed: table("[video]\nfov = 110\nvsync = false\n"),
+        };
+
+        let resolved = layers.resolve();
+        assert_eq!(
+            resolved,
+            table("[audio]\nvolume = 0.8\nmuted = false\n[video]\nvsync = false\nfov = 110\n")
+        );
+
+        // The user changed the volume; the fixed `fov` and `vsync` keep their user file values.
+        let current =
+            table("[audio]\nvolume = 0.5\nmuted = false\n[video]\nvsync = false\nfov = 110\n");
+        assert_eq!(
+            layers.user_table(&current),
+            table("[audio]\nvolume = 0.5\n[video]\nfov = 90\n")
+        );
+    }
+
+    #[test]
+    fn locked_values_are_taken_from_the_system_file() {
+        let mut system =
+            table("locked = [\"audio.volume\", \"video\"]\n[audio]\nvolume = 0.2\nmuted = true\n[video]\nvsync = true\n");
+        let locked = take_locked(&mut system, "settings");
+        assert!(!system.contains_key(SETTINGS_LOCKED_KEY));
+        assert_eq!(
+            locked,
+            table("[audio]\nvolume = 0.2\n[video]\nvsync = true\n")
+        );
+    }
+
+    #[test]
+    fn overrides_parse_toml_values() {
+        let mut overrides = toml::Table::new();
+        for assignment in ["audio.volume=0.5", "audio.device = default", "video.fov=90"] {
+            let (path, value) = parse_override(assignment).unwrap();
+            insert_override(&mut overrides, path, value);
+        }
+        assert_eq!(
+            overrides,
+            table("[audio]\nvolume = 0.5\ndevice = \"default\"\n[video]\nfov = 90\n")
+        );
+        assert!(parse_override("=1").is_none());
+        assert!(parse_override("audio.volume").is_none());
+    }
+}
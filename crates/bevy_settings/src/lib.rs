@@ -3,20 +3,24 @@
 //!
 //! Refer to [`SettingsPlugin`] for detailed usage information.
 
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, sync::Arc};
 use core::any::TypeId;
 use core::time::Duration;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use bevy_app::{App, Plugin, PostUpdate};
 use bevy_ecs::{
     change_detection::Tick,
+    message::Message,
     reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
     resource::Resource,
     system::{Command, Commands, Res, ResMut},
     world::World,
 };
 pub use bevy_ecs_macros::SettingsGroup;
-use bevy_log::warn;
+use bevy_log::{debug, error, warn};
 use bevy_reflect::{
     prelude::ReflectDefault,
     serde::{TypedReflectDeserializer, TypedReflectSerializer},
@@ -24,6 +28,10 @@ use bevy_reflect::{
     TypeRegistry,
 };
 
+#[cfg(all(feature = "file_watcher", not(target_arch = "wasm32")))]
+mod file_watcher;
+mod layers;
+
 #[cfg(not(target_arch = "wasm32"))]
 mod store_fs;
 
@@ -38,6 +46,9 @@ use store_fs::SettingsStore;
 #[cfg(target_arch = "wasm32")]
 use store_wasm::SettingsStore;
 
+use layers::SettingsLayers;
+pub use layers::SETTINGS_LOCKED_KEY;
+
 /// Plugin to orchestrate loading and saving settings.
 ///
 /// You are required to provide a unique application name, so that your settings don't overwrite
@@ -107,9 +118,97 @@ use store_wasm::SettingsStore;
 /// Saving is crash-resistant: if the app crashes in the middle of a save, the settings file
 /// will not be corrupted (it writes to a temporary file first, then uses atomic operations to
 /// replace the previous file).
+///
+/// # Versioning and migration
+///
+/// When the shape of a [`SettingsGroup`] changes (for example a field is renamed or moved to
+/// another group), settings files written by older versions of the app would no longer load
+/// correctly. To handle this, each settings file can carry a schema version, stored under the
+/// top-level [`SETTINGS_VERSION_KEY`], and migration functions can be registered with
+/// [`SettingsPlugin::with_migration`].
+///
+/// A migration rewrites the raw TOML table of a file from one version to the next, before it is
+/// applied to the settings resources. The current version of a file is one more than the
+/// highest version a migration is registered for; files without any migration are unversioned.
+/// A file that has no version key is considered to be at version 0.
+///
+/// ```
+/// # use bevy_settings::SettingsPlugin;
+/// // Version 1 renamed `master_volume` to `volume` in the `audio_settings` group.
+/// let plugin = SettingsPlugin::new("com.example.myapp").with_migration("settings", 0, |table| {
+///     if let Some(audio) = table.get_mut("audio_settings").and_then(|v| v.as_table_mut())
+///         && let Some(volume) = audio.remove("master_volume")
+///     {
+///         audio.insert("volume".to_string(), volume);
+///     }
+/// });
+/// ```
+///
+/// A file that has been migrated is written back with its new version the next time settings
+/// are saved, even if none of its resources changed.
+///
+/// # Layered sources
+///
+/// The values of a settings file are resolved from several layers, each one overriding the
+/// values of the layers below it:
+///
+/// 1. The [`Default`] value of each [`SettingsGroup`].
+/// 2. Shipped defaults, registered with [`SettingsPlugin::with_defaults`]. These let a game ship
+///    tuned values without changing the code of its settings types.
+/// 3. An optional system-wide file, located in the directory given to
+///    [`SettingsPlugin::with_system_dir`] (not available on `wasm32`).
+/// 4. The user file described above.
+/// 5. Overrides from the command line ([`SettingsPlugin::with_args`]), the environment
+///    ([`SettingsPlugin::with_env_overrides`]) or code ([`SettingsPlugin::with_override`]).
+///
+/// A system-wide file may also lock some of its values by listing their dotted paths under the
+/// top-level [`SETTINGS_LOCKED_KEY`]. Locked values take precedence over every other layer:
+///
+/// ```toml
+/// locked = ["audio_settings.volume"]
+///
+/// [audio_settings]
+/// volume = 0.5
+/// ```
+///
+/// When saving, the user file only receives the values which differ from the [`Default`] values,
+/// the shipped defaults and the system-wide file, so that changes to those layers still reach
+/// users who never touched the corresponding settings. Overridden and locked values are never
+/// written to the user file.
+///
+/// # Hot reloading
+///
+/// When the `file_watcher` feature is enabled (`settings_file_watcher` in `bevy`), the user and
+/// system-wide settings files are watched while the app is running. When one of them is edited,
+/// the values of every [`SettingsGroup`] whose resolved section changed are reloaded: the
+/// resource is reset to its [`Default`] value and the resolved section is applied to it. A
+/// [`SettingsGroupChanged`] message is written for each reloaded group. Use
+/// [`SettingsPlugin::with_watch_for_changes`] to turn this off.
 pub struct SettingsPlugin {
     /// The unique name of the application.
     pub app_name: String,
+
+    /// The migrations registered for each settings file.
+    migrations: HashMap<String, SettingsFileMigrations>,
+
+    /// The layers settings are resolved from, in addition to the user files.
+    sources: SettingsSources,
+
+    /// If set, overrides whether settings files are watched for changes. By default, they are
+    /// watched if the `file_watcher` feature is enabled.
+    watch_for_changes_override: Option<bool>,
+}
+
+/// The sources of settings values other than the user files.
+#[derive(Default, Clone)]
+struct SettingsSources {
+    /// Shipped defaults for each settings file.
+    defaults: HashMap<String, toml::Table>,
+    /// The directory containing system-wide settings files.
+    #[cfg(not(target_arch = "wasm32"))]
+    system_dir: Option<std::path::PathBuf>,
+    /// Overrides which apply to every settings file.
+    overrides: toml::Table,
 }
 
 impl SettingsPlugin {
@@ -117,7 +216,141 @@ impl SettingsPlugin {
     pub fn new(app_name: &str) -> Self {
         Self {
             app_name: app_name.to_string(),
+            migrations: HashMap::new(),
+            sources: SettingsSources::default(),
+            watch_for_changes_override: None,
+        }
+    }
+
+    /// Set whether settings files are watched and reloaded when they change on disk.
+    ///
+    /// Watching requires the `file_watcher` feature, and is enabled by default when that feature
+    /// is. See [Hot reloading](SettingsPlugin#hot-reloading) for details.
+    #[must_use]
+    pub fn with_watch_for_changes(mut self, watch: bool) -> Self {
+        self.watch_for_changes_override = Some(watch);
+        self
+    }
+
+    /// Set the shipped defaults of the settings file `filename`, in TOML format.
+    ///
+    /// This is typically used with [`include_str!`] to embed a defaults file in the game.
+    /// Calling this several times for the same file merges the defaults, with later calls taking
+    /// precedence. See [Layered sources](SettingsPlugin#layered-sources) for details.
+    #[must_use]
+    pub fn with_defaults(mut self, filename: &str, toml: &str) -> Self {
+        match toml::from_str::<toml::Table>(toml) {
+            Ok(table) => layers::merge(
+                self.sources
+                    .defaults
+                    .entry(filename.to_string())
+                    .or_default(),
+                &table,
+            ),
+            Err(e) => error!("Error parsing default settings for {filename}.toml: {}", e),
+        }
+        self
+    }
+
+    /// Set the directory containing the system-wide settings files.
+    ///
+    /// The system-wide version of the settings file `filename` is read from
+    /// `{dir}/{filename}.toml`, if it exists. See
+    /// [Layered sources](SettingsPlugin#layered-sources) for details.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[must_use]
+    pub fn with_system_dir(mut self, dir: impl Into<std::path::PathBuf>) -> Self {
+        self.sources.system_dir = Some(dir.into());
+        self
+    }
+
+    /// Override the setting at the dotted `path` (such as `"audio_settings.volume"`) with
+    /// `value`, in every settings file.
+    ///
+    /// Overrides are not written to the user file. Later overrides take precedence over earlier
+    /// ones.
+    #[must_use]
+    pub fn with_override(mut self, path: &str, value: impl Into<toml::Value>) -> Self {
+        layers::insert_override(&mut self.sources.overrides, path, value.into());
+        self
+    }
+
+    /// Add overrides from command line arguments, typically [`std::env::args`].
+    ///
+    /// Overrides are given as `--set <path>=<value>` or `--set=<path>=<value>`, where `path` is
+    /// a dotted path such as `audio_settings.volume` and `value` is a TOML value. Values which
+    /// are not valid TOML are used as strings. Other arguments are ignored.
+    #[must_use]
+    pub fn with_args<S: AsRef<str>>(mut self, args: impl IntoIterator<Item = S>) -> Self {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let arg = arg.as_ref();
+            let assignment = if arg == "--set" {
+                let Some(next) = args.next() else {
+                    warn!("Missing setting after --set");
+                    break;
+                };
+                next.as_ref().to_string()
+            } else if let Some(assignment) = arg.strip_prefix("--set=") {
+                assignment.to_string()
+            } else {
+                continue;
+            };
+
+            match layers::parse_override(&assignment) {
+                Some((path, value)) => {
+                    layers::insert_override(&mut self.sources.overrides, path, value);
+                }
+                None => warn!("Invalid setting override {assignment}, expected <path>=<value>"),
+            }
+        }
+        self
+    }
+
+    /// Add overrides from the environment variables whose name starts with `prefix`.
+    ///
+    /// The rest of the variable name is lowercased and split into a path on double
+    /// underscores, so with a prefix of `MYGAME_`, `MYGAME_AUDIO_SETTINGS__VOLUME=0.5` overrides
+    /// `audio_settings.volume`. Values which are not valid TOML are used as strings.
+    #[must_use]
+    pub fn with_env_overrides(mut self, prefix: &str) -> Self {
+        for (key, value) in std::env::vars() {
+            let Some(path) = key.strip_prefix(prefix) else {
+                continue;
+            };
+            let path = path.to_lowercase().replace("__", ".");
+            layers::insert_override(
+                &mut self.sources.overrides,
+                &path,
+                layers::parse_value(&value),
+            );
         }
+        self
+    }
+
+    /// Register a migration which rewrites the settings file `filename` from `from_version` to
+    /// `from_version + 1`.
+    ///
+    /// `filename` is the base name of the file without the extension, as used by
+    /// `settings_group(file = "...")`; the default file is `settings`. Registering a migration
+    /// also raises the current version of that file to at least `from_version + 1`. If no
+    /// migration is registered for some version below the current one, files at that version
+    /// are upgraded without changes.
+    ///
+    /// See [Versioning and migration](SettingsPlugin#versioning-and-migration) for details.
+    #[must_use]
+    pub fn with_migration(
+        mut self,
+        filename: &str,
+        from_version: u32,
+        migrate: impl Fn(&mut toml::Table) + Send + Sync + 'static,
+    ) -> Self {
+        self.migrations
+            .entry(filename.to_string())
+            .or_default()
+            .steps
+            .insert(from_version, Arc::new(migrate));
+        self
     }
 }
 
@@ -135,12 +368,28 @@ impl Plugin for SettingsPlugin {
         let types = app_types.read();
 
         let world = app.world_mut();
-        let file_index = build_settings_registry(&app_name, &types, last_save);
+        let mut file_index = build_settings_registry(&app_name, &types, last_save);
+        file_index.migrations = self.migrations.clone();
+        file_index.sources = self.sources.clone();
 
         // Now load each of the toml files we discovered, and apply their properties to
         // the resources in the world.
+        let store = SettingsStore::new(&app_name);
         for (filename, manifest) in file_index.files.iter() {
-            load_settings_file(world, &app_name, filename, manifest, &types);
+            let migrations = file_index.migrations.get(*filename);
+            let (layers, migrated) = load_settings_layers(
+                &store,
+                filename,
+                manifest,
+                &types,
+                &file_index.sources,
+                migrations,
+            );
+            apply_settings_to_world(world, Some(&layers.resolve()), manifest, &types);
+            file_index.layers.insert(*filename, layers);
+            if migrated {
+                file_index.migrated.insert(*filename);
+            }
         }
 
         // Cache the index so that we don't have to do it again when saving (and also makes
@@ -149,9 +398,49 @@ impl Plugin for SettingsPlugin {
         world.insert_resource::<SettingsFileRegistry>(file_index);
 
         app.add_systems(PostUpdate, handle_delayed_save);
+
+        let watch = self
+            .watch_for_changes_override
+            .unwrap_or(cfg!(feature = "file_watcher"));
+        if watch {
+            #[cfg(all(feature = "file_watcher", not(target_arch = "wasm32")))]
+            {
+                let user_dir = store.base_path().map(|dir| (dir.as_path(), true));
+                let system_dir = self
+                    .sources
+                    .system_dir
+                    .as_ref()
+                    .map(|dir| (dir.as_path(), false));
+                match file_watcher::SettingsWatcher::new(user_dir.into_iter().chain(system_dir)) {
+                    Ok(watcher) => {
+                        app.insert_resource(watcher)
+                            .add_message::<SettingsGroupChanged>()
+                            .add_systems(bevy_app::PreUpdate, reload_changed_settings);
+                    }
+                    Err(e) => error!("Could not watch settings files: {}", e),
+                }
+            }
+            #[cfg(not(all(feature = "file_watcher", not(target_arch = "wasm32"))))]
+            warn!(
+                "Watching settings files for changes requires the `file_watcher` feature, which \
+                is not available on web"
+            );
+        }
     }
 }
 
+/// A [`Message`] written when the values of a [`SettingsGroup`] are reloaded because its
+/// settings file changed on disk.
+///
+/// See [Hot reloading](SettingsPlugin#hot-reloading) for details.
+#[derive(Message, Clone, Debug, PartialEq, Eq)]
+pub struct SettingsGroupChanged {
+    /// The name of the group, as returned by [`SettingsGroup::settings_group_name`].
+    pub group: &'static str,
+    /// The name of the settings file containing the group, without the extension.
+    pub file: &'static str,
+}
+
 /// Trait which identifies a type as corresponding to a section with a settings file.
 ///
 /// You can override the name of the section with `settings_group(group = "<name>")`.
@@ -225,6 +514,91 @@ struct SettingsFileRegistry {
 
     /// Timer used for batched saving.
     save_timer: Timer,
+
+    /// Migrations registered for each settings file, keyed by filename.
+    migrations: HashMap<String, SettingsFileMigrations>,
+
+    /// Settings files which were migrated when loaded and have not been saved since.
+    migrated: HashSet<&'static str>,
+
+    /// The layers each settings file was resolved from.
+    layers: HashMap<&'static str, SettingsLayers>,
+
+    /// The layers settings are resolved from, in addition to the user files.
+    sources: SettingsSources,
+}
+
+/// The key of the schema version of a settings file, stored at the top level of the file.
+///
+/// No [`SettingsGroup`] should use this name. See
+/// [Versioning and migration](SettingsPlugin#versioning-and-migration) for details.
+pub const SETTINGS_VERSION_KEY: &str = "settings_version";
+
+/// A function which rewrites the TOML table of a settings file to the next schema version.
+type SettingsMigrationFn = dyn Fn(&mut toml::Table) + Send + Sync;
+
+/// The migrations registered for a single settings file.
+#[derive(Default, Clone)]
+struct SettingsFileMigrations {
+    /// Migrations keyed by the version they upgrade from.
+    steps: BTreeMap<u32, Arc<SettingsMigrationFn>>,
+}
+
+impl SettingsFileMigrations {
+    /// The version settings files are written with.
+    fn current_version(&self) -> u32 {
+        self.steps
+            .last_key_value()
+            .map_or(0, |(version, _)| version + 1)
+    }
+
+    /// Upgrades `table` to the current version, returning `true` if it was modified.
+    fn migrate(&self, filename: &str, table: &mut toml::Table) -> bool {
+        let current = self.current_version();
+        let version = match table.get(SETTINGS_VERSION_KEY) {
+            None => 0,
+            Some(toml::Value::Integer(version)) => match u32::try_from(*version) {
+                Ok(version) => version,
+                Err(_) => {
+                    warn!("Invalid {SETTINGS_VERSION_KEY} {version} in {filename}.toml");
+                    return false;
+                }
+            },
+            Some(value) => {
+                warn!("Invalid {SETTINGS_VERSION_KEY} {value} in {filename}.toml");
+                return false;
+            }
+        };
+
+        if version > current {
+            warn!(
+                "{filename}.toml has {SETTINGS_VERSION_KEY} {version}, which is newer than the \
+                supported version {current}; loading it without migration"
+            );
+            return false;
+        }
+        if version == current {
+            return false;
+        }
+
+        for (_, migrate) in self.steps.range(version..current) {
+            migrate(table);
+        }
+        self.stamp(table);
+        debug!("Migrated {filename}.toml from version {version} to {current}");
+        true
+    }
+
+    /// Records the current version in `table`.
+    fn stamp(&self, table: &mut toml::Table) {
+        let current = self.current_version();
+        if current > 0 {
+            table.insert(
+                SETTINGS_VERSION_KEY.to_string(),
+                toml::Value::Integer(current.into()),
+            );
+        }
+    }
 }
 
 /// A Command which saves settings to disk. This blocks the command queue until saving
@@ -302,23 +676,39 @@ fn save_settings(world: &mut World, use_async: bool, force: bool) {
     let app_types = app_types.clone();
     let types = app_types.read();
 
+    let mut saved = Vec::new();
     for (filename, manifest) in registry.files.iter() {
-        if force || has_settings_changed(world, manifest) {
-            let table = resources_to_toml(world, &types, manifest);
+        if force || registry.migrated.contains(filename) || has_settings_changed(world, manifest) {
+            let current = resources_to_toml(world, &types, manifest);
+            // Only write the values which aren't already provided by the layers below the user
+            // file.
+            let mut table = match registry.layers.get(filename) {
+                Some(layers) => layers.user_table(&current),
+                None => current,
+            };
+            if let Some(migrations) = registry.migrations.get(*filename) {
+                migrations.stamp(&mut table);
+            }
             let store = SettingsStore::new(&registry.app_name);
             if use_async {
-                store.save_async(filename, table);
+                store.save_async(filename, table.clone());
             } else {
-                store.save(filename, table);
+                store.save(filename, table.clone());
             }
+            saved.push((*filename, table));
         }
     }
+    drop(types);
 
     // Update timestamps
     let mut registry = world.get_resource_mut::<SettingsFileRegistry>().unwrap();
     for manifest in registry.files.values_mut() {
         manifest.last_save = this_run;
     }
+    registry.migrated.clear();
+    for (filename, table) in saved {
+        registry.layers.entry(filename).or_default().user = table;
+    }
 }
 
 fn has_settings_changed(world: &World, manifest: &SettingsFileManifest) -> bool {
@@ -352,9 +742,6 @@ fn resources_to_toml(
             continue;
         };
 
-        let settings_group = reflect_settings_group.settings_group_name;
-        let settings_key = reflect_settings_group.settings_key_name;
-
         let Some(component_id) = world.components().get_id(*tid) else {
             continue;
         };
@@ -367,40 +754,87 @@ fn resources_to_toml(
             continue;
         };
 
-        let serializer = TypedReflectSerializer::new(reflect.as_partial_reflect(), types);
+        insert_settings_group(
+            &mut table,
+            reflect_settings_group,
+            reflect.as_partial_reflect(),
+            types,
+        );
+    }
 
-        let toml_value = if let Some(settings_key) = settings_key {
-            // convert toml value into a key value pair if settings_key is set. settings_key is only set for enums
-            toml::Value::Table(toml::Table::from_iter([(
-                settings_key.to_string(),
-                toml::Value::try_from(serializer).unwrap(),
-            )]))
-        } else {
-            // Otherwise, the whole struct is serialized into toml
-            toml::Value::try_from(serializer).unwrap()
-        };
+    table
+}
 
-        match (
-            toml_value.as_table(),
-            table
-                .get_mut(settings_group)
-                .and_then(|value| value.as_table_mut()),
-        ) {
-            (Some(from), Some(to)) => {
-                // Merge the tables
-                for (key, value) in from.iter() {
-                    to.insert(key.clone(), value.clone());
-                }
-            }
-            _ => {
-                table.insert(settings_group.to_string(), toml_value);
-            }
+/// Serializes the [`Default`] value of every resource in the manifest, in the same layout as
+/// [`resources_to_toml`].
+fn defaults_to_toml(types: &TypeRegistry, manifest: &SettingsFileManifest) -> toml::Table {
+    let mut table = toml::Table::new();
+
+    for tid in manifest.resource_types.iter() {
+        let Some(ty) = types.get(*tid) else {
+            continue;
         };
+        let (Some(reflect_settings_group), Some(reflect_default)) = (
+            ty.data::<ReflectSettingsGroup>(),
+            ty.data::<ReflectDefault>(),
+        ) else {
+            continue;
+        };
+
+        let default_value = reflect_default.default();
+        insert_settings_group(
+            &mut table,
+            reflect_settings_group,
+            default_value.as_partial_reflect(),
+            types,
+        );
     }
 
     table
 }
 
+/// Serializes a settings resource into its group of `table`, merging it with any other
+/// resource already stored in the same group.
+fn insert_settings_group(
+    table: &mut toml::Table,
+    reflect_settings_group: &ReflectSettingsGroup,
+    reflect: &dyn PartialReflect,
+    types: &TypeRegistry,
+) {
+    let settings_group = reflect_settings_group.settings_group_name;
+    let settings_key = reflect_settings_group.settings_key_name;
+
+    let serializer = TypedReflectSerializer::new(reflect, types);
+
+    let toml_value = if let Some(settings_key) = settings_key {
+        // convert toml value into a key value pair if settings_key is set. settings_key is only set for enums
+        toml::Value::Table(toml::Table::from_iter([(
+            settings_key.to_string(),
+            toml::Value::try_from(serializer).unwrap(),
+        )]))
+    } else {
+        // Otherwise, the whole struct is serialized into toml
+        toml::Value::try_from(serializer).unwrap()
+    };
+
+    match (
+        toml_value.as_table(),
+        table
+            .get_mut(settings_group)
+            .and_then(|value| value.as_table_mut()),
+    ) {
+        (Some(from), Some(to)) => {
+            // Merge the tables
+            for (key, value) in from.iter() {
+                to.insert(key.clone(), value.clone());
+            }
+        }
+        _ => {
+            table.insert(settings_group.to_string(), toml_value);
+        }
+    };
+}
+
 /// Builds the settings file registry by scanning the type registry for settings resources.
 /// This is separated from loading to enable testing without file I/O.
 ///
@@ -417,6 +851,10 @@ fn build_settings_registry(
         app_name: app_name.to_string(),
         files: HashMap::new(),
         save_timer: Timer::new(Duration::from_secs(1), TimerMode::Once),
+        migrations: HashMap::new(),
+        migrated: HashSet::new(),
+        layers: HashMap::new(),
+        sources: SettingsSources::default(),
     };
     file_index.save_timer.pause(); // Ensure timer is initially paused
 
@@ -447,22 +885,207 @@ fn build_settings_registry(
     file_index
 }
 
-/// Loads a single settings file and applies its values to the world's resources.
-fn load_settings_file(
-    world: &mut World,
-    app_name: &str,
+/// Loads the layers of a single settings file and migrates them to the current version.
+///
+/// The base layer starts out with the [`Default`] value of each resource in the `manifest`, so
+/// that values which were never changed aren't written to the user file.
+///
+/// Returns the layers, and whether the user file was migrated.
+fn load_settings_layers(
+    store: &SettingsStore,
     filename: &str,
     manifest: &SettingsFileManifest,
     types: &TypeRegistry,
-) {
+    sources: &SettingsSources,
+    migrations: Option<&SettingsFileMigrations>,
+) -> (SettingsLayers, bool) {
+    let mut layers = SettingsLayers {
+        base: defaults_to_toml(types, manifest),
+        user: toml::Table::new(),
+        fixed: sources.overrides.clone(),
+    };
+    if let Some(defaults) = sources.defaults.get(filename) {
+        layers::merge(&mut layers.base, defaults);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(system_dir) = &sources.system_dir
+        && let Some(mut system) =
+            store_fs::decode_toml_file(&system_dir.join(format!("{filename}.toml")))
+    {
+        if let Some(migrations) = migrations {
+            migrations.migrate(filename, &mut system);
+        }
+        let locked = layers::take_locked(&mut system, filename);
+        layers::merge(&mut layers.base, &system);
+        layers::merge(&mut layers.fixed, &locked);
+    }
+
     // Load the TOML file
-    let store = SettingsStore::new(app_name);
-    let toml = store.load(filename);
+    let mut toml = store.load(filename);
     if toml.is_none() {
         warn!("Filename {filename}.toml not found");
     }
 
-    apply_settings_to_world(world, toml.as_ref(), manifest, types);
+    let migrated = match (&mut toml, migrations) {
+        (Some(toml), Some(migrations)) => migrations.migrate(filename, toml),
+        _ => false,
+    };
+    layers.user = toml.unwrap_or_default();
+
+    (layers, migrated)
+}
+
+/// Reloads the settings files which changed on disk since the last frame.
+#[cfg(all(feature = "file_watcher", not(target_arch = "wasm32")))]
+fn reload_changed_settings(world: &mut World) {
+    let changed_files = world
+        .resource::<file_watcher::SettingsWatcher>()
+        .changed_files();
+    if changed_files.is_empty() {
+        return;
+    }
+    let Some(app_types) = world.get_resource::<AppTypeRegistry>() else {
+        return;
+    };
+    let app_types = app_types.clone();
+    let types = app_types.read();
+
+    for changed_file in changed_files {
+        let registry = world.resource::<SettingsFileRegistry>();
+        let Some((&filename, manifest)) = registry.files.get_key_value(changed_file.as_str())
+        else {
+            continue;
+        };
+
+        let store = SettingsStore::new(&registry.app_name);
+        let migrations = registry.migrations.get(filename);
+        let (layers, migrated) = load_settings_layers(
+            &store,
+            filename,
+            manifest,
+            &types,
+            &registry.sources,
+            migrations,
+        );
+        apply_reloaded_settings(world, filename, layers, migrated, &types);
+    }
+}
+
+/// Applies the reloaded `layers` of a settings file to the world.
+///
+/// Only the groups whose resolved values changed are reloaded, so that saving a file doesn't
+/// touch the resources it was saved from.
+#[cfg(all(feature = "file_watcher", not(target_arch = "wasm32")))]
+fn apply_reloaded_settings(
+    world: &mut World,
+    filename: &'static str,
+    layers: SettingsLayers,
+    migrated: bool,
+    types: &TypeRegistry,
+) {
+    let registry = world.resource::<SettingsFileRegistry>();
+    let Some(manifest) = registry.files.get(filename) else {
+        return;
+    };
+    let previous = registry
+        .layers
+        .get(filename)
+        .map(SettingsLayers::resolve)
+        .unwrap_or_default();
+    let resolved = layers.resolve();
+    let (changed, changed_groups) = changed_settings(&previous, &resolved, manifest, types);
+    let unchanged = SettingsFileManifest {
+        last_save: manifest.last_save,
+        resource_types: manifest
+            .resource_types
+            .iter()
+            .filter(|tid| !changed.resource_types.contains(tid))
+            .copied()
+            .collect(),
+    };
+
+    reset_settings_to_default(world, &changed, types);
+    apply_settings_to_world(world, Some(&resolved), &changed, types);
+
+    // The reloaded values don't need to be saved again, but the other groups may still hold
+    // changes which haven't been saved yet. Those must not be treated as saved.
+    let unsaved = has_settings_changed(world, &unchanged);
+    let this_run = world.change_tick();
+    let mut registry = world.resource_mut::<SettingsFileRegistry>();
+    registry.layers.insert(filename, layers);
+    if migrated {
+        registry.migrated.insert(filename);
+    }
+    if !unsaved && let Some(manifest) = registry.files.get_mut(filename) {
+        manifest.last_save = this_run;
+    }
+
+    for group in changed_groups {
+        debug!("Reloaded settings group {group} from {filename}.toml");
+        world.write_message(SettingsGroupChanged {
+            group,
+            file: filename,
+        });
+    }
+}
+
+/// Returns the resources of the manifest whose settings group differs between `previous` and
+/// `resolved`, along with the names of those groups.
+#[cfg(all(feature = "file_watcher", not(target_arch = "wasm32")))]
+fn changed_settings(
+    previous: &toml::Table,
+    resolved: &toml::Table,
+    manifest: &SettingsFileManifest,
+    types: &TypeRegistry,
+) -> (SettingsFileManifest, Vec<&'static str>) {
+    let mut changed = SettingsFileManifest::default();
+    let mut changed_groups = Vec::new();
+    for tid in manifest.resource_types.iter() {
+        let Some(reflect_group) = types
+            .get(*tid)
+            .and_then(|ty| ty.data::<ReflectSettingsGroup>())
+        else {
+            continue;
+        };
+        let group = reflect_group.settings_group_name;
+        if previous.get(group) != resolved.get(group) {
+            changed.resource_types.push(*tid);
+            if !changed_groups.contains(&group) {
+                changed_groups.push(group);
+            }
+        }
+    }
+    (changed, changed_groups)
+}
+
+/// Resets the existing resources of the manifest to their [`Default`] values.
+#[cfg(all(feature = "file_watcher", not(target_arch = "wasm32")))]
+fn reset_settings_to_default(
+    world: &mut World,
+    manifest: &SettingsFileManifest,
+    types: &TypeRegistry,
+) {
+    for tid in manifest.resource_types.iter() {
+        let Some(ty) = types.get(*tid) else {
+            continue;
+        };
+        let (Some(reflect_component), Some(reflect_default)) =
+            (ty.data::<ReflectComponent>(), ty.data::<ReflectDefault>())
+        else {
+            continue;
+        };
+        let Some(res_entity) = world
+            .components()
+            .get_id(*tid)
+            .and_then(|cid| world.resource_entities().get(cid))
+        else {
+            continue;
+        };
+        if let Some(mut reflect) = reflect_component.reflect_mut(world.entity_mut(res_entity)) {
+            reflect.apply(reflect_default.default().as_partial_reflect());
+        }
+    }
 }
 
 /// Applies settings from a TOML table to the world's resources.
@@ -1078,4 +1701,196 @@ mod tests {
         let refresh_rate = world.get_resource::<CounterRefreshRateSettings>().unwrap();
         assert_eq!(*refresh_rate, CounterRefreshRateSettings::Fast);
     }
+
+    #[test]
+    fn test_migration_rewrites_old_tables() {
+        let mut world = World::new();
+        let mut types = TypeRegistry::default();
+        types.register::<CounterSettings>();
+
+        let plugin = SettingsPlugin::new("test_app")
+            .with_migration("settings", 0, |table| {
+                // Version 1 renamed the `counter` group to `counter_settings`.
+                if let Some(counter) = table.remove("counter") {
+                    table.insert("counter_settings".to_string(), counter);
+                }
+            })
+            .with_migration("settings", 1, |table| {
+                // Version 2 renamed `value` to `count`.
+                if let Some(counter) = table
+                    .get_mut("counter_settings")
+                    .and_then(|value| value.as_table_mut())
+                    && let Some(value) = counter.remove("value")
+                {
+                    counter.insert("count".to_string(), value);
+                }
+            });
+        let migrations = plugin.migrations.get("settings").unwrap();
+        assert_eq!(migrations.current_version(), 2);
+
+        // An unversioned file goes through every migration.
+        let mut table: toml::Table = toml::from_str("[counter]\nvalue = 7\n").unwrap();
+        assert!(migrations.migrate("settings", &mut table));
+        assert_eq!(
+            table.get(SETTINGS_VERSION_KEY).unwrap().as_integer(),
+            Some(2)
+        );
+
+        let manifest = SettingsFileManifest {
+            last_save: Tick::new(0),
+            resource_types: vec![TypeId::of::<CounterSettings>()],
+        };
+        apply_settings_to_world(&mut world, Some(&table), &manifest, &types);
+        assert_eq!(world.resource::<CounterSettings>().count, 7);
+
+        // A file at version 1 only goes through the second migration.
+        let mut table: toml::Table =
+            toml::from_str("settings_version = 1\n[counter_settings]\nvalue = 3\n").unwrap();
+        assert!(migrations.migrate("settings", &mut table));
+        apply_settings_to_world(&mut world, Some(&table), &manifest, &types);
+        assert_eq!(world.resource::<CounterSettings>().count, 3);
+
+        // Current and newer files are left untouched.
+        let mut table: toml::Table =
+            toml::from_str("settings_version = 2\n[counter_settings]\nvalue = 3\n").unwrap();
+        assert!(!migrations.migrate("settings", &mut table));
+        let mut table: toml::Table =
+            toml::from_str("settings_version = 5\n[counter_settings]\nvalue = 3\n").unwrap();
+        assert!(!migrations.migrate("settings", &mut table));
+        assert_eq!(
+            table.get(SETTINGS_VERSION_KEY).unwrap().as_integer(),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn test_user_table_skips_default_values() {
+        let mut world = World::new();
+        let mut types = TypeRegistry::default();
+        types.register::<CounterSettings>();
+        types.register::<ExtraCounterSettings>();
+
+        let manifest = SettingsFileManifest {
+            last_save: Tick::new(0),
+            resource_types: vec![
+                TypeId::of::<CounterSettings>(),
+                TypeId::of::<ExtraCounterSettings>(),
+            ],
+        };
+        let layers = SettingsLayers {
+            base: defaults_to_toml(&types, &manifest),
+            ..Default::default()
+        };
+        assert_eq!(
+            layers.base,
+            toml::from_str::<toml::Table>("[counter_settings]\ncount = 0\nenabled = false\n")
+                .unwrap()
+        );
+
+        apply_settings_to_world(&mut world, Some(&layers.resolve()), &manifest, &types);
+        world.resource_mut::<ExtraCounterSettings>().enabled = true;
+
+        // Only the value which differs from its default is written to the user file.
+        let current = resources_to_toml(&world, &types, &manifest);
+        assert_eq!(
+            layers.user_table(&current),
+            toml::from_str::<toml::Table>("[counter_settings]\nenabled = true\n").unwrap()
+        );
+    }
+
+    #[cfg(all(feature = "file_watcher", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_reload_only_resets_changed_groups() {
+        let mut world = World::new();
+        let mut types = TypeRegistry::default();
+        types.register::<CounterSettings>();
+        types.register::<ExtraCounterSettings>();
+        types.register::<AudioSettings>();
+
+        let manifest = SettingsFileManifest {
+            last_save: Tick::new(0),
+            resource_types: vec![
+                TypeId::of::<CounterSettings>(),
+                TypeId::of::<ExtraCounterSettings>(),
+                TypeId::of::<AudioSettings>(),
+            ],
+        };
+        let previous: toml::Table = toml::from_str(
+            "[counter_settings]\ncount = 3\nenabled = true\n[audio]\nvolume = 0.5\n",
+        )
+        .unwrap();
+        apply_settings_to_world(&mut world, Some(&previous), &manifest, &types);
+
+        // `count` was removed from the file, so it goes back to its default value.
+        let resolved: toml::Table =
+            toml::from_str("[counter_settings]\nenabled = true\n[audio]\nvolume = 0.5\n").unwrap();
+        let (changed, groups) = changed_settings(&previous, &resolved, &manifest, &types);
+        assert_eq!(groups, vec!["counter_settings"]);
+        assert_eq!(changed.resource_types.len(), 2);
+
+        world.resource_mut::<AudioSettings>().volume = 0.1;
+        reset_settings_to_default(&mut world, &changed, &types);
+        apply_settings_to_world(&mut world, Some(&resolved), &changed, &types);
+        assert_eq!(world.resource::<CounterSettings>().count, 0);
+        assert!(world.resource::<ExtraCounterSettings>().enabled);
+        // Groups which didn't change on disk keep their in-memory values.
+        assert_eq!(world.resource::<AudioSettings>().volume, 0.1);
+    }
+
+    #[cfg(all(feature = "file_watcher", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_reload_keeps_unsaved_changes_of_other_groups() {
+        use bevy_ecs::message::Messages;
+
+        #[derive(Resource, SettingsGroup, Reflect, Default)]
+        #[reflect(Resource, SettingsGroup, Default)]
+        #[settings_group(group = "graphics")]
+        struct GraphicsSettings {
+            fullscreen: bool,
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Messages<SettingsGroupChanged>>();
+        let mut types = TypeRegistry::default();
+        types.register::<CounterSettings>();
+        types.register::<GraphicsSettings>();
+
+        let mut registry = build_settings_registry("test_app", &types, Tick::new(0));
+        let layers = SettingsLayers {
+            user: toml::from_str("[counter_settings]\ncount = 1\n").unwrap(),
+            ..Default::default()
+        };
+        let manifest = registry.files.get_mut("settings").unwrap();
+        apply_settings_to_world(&mut world, Some(&layers.resolve()), manifest, &types);
+        manifest.last_save = world.change_tick();
+        registry.layers.insert("settings", layers);
+        world.insert_resource(registry);
+        world.increment_change_tick();
+
+        // `counter_settings` changes in memory, and isn't saved before `graphics` is edited on
+        // disk.
+        world.resource_mut::<CounterSettings>().count = 5;
+        world.increment_change_tick();
+        let layers = SettingsLayers {
+            user: toml::from_str("[counter_settings]\ncount = 1\n[graphics]\nfullscreen = true\n")
+                .unwrap(),
+            ..Default::default()
+        };
+        apply_reloaded_settings(&mut world, "settings", layers, false, &types);
+        assert!(world.resource::<GraphicsSettings>().fullscreen);
+        assert_eq!(world.resource::<CounterSettings>().count, 5);
+
+        // The unsaved change is still written by the next save.
+        let registry = world.resource::<SettingsFileRegistry>();
+        let manifest = registry.files.get("settings").unwrap();
+        assert!(has_settings_changed(&world, manifest));
+        let current = resources_to_toml(&world, &types, manifest);
+        assert_eq!(
+            registry.layers["settings"].user_table(&current),
+            toml::from_str::<toml::Table>(
+                "[counter_settings]\ncount = 5\n[graphics]\nfullscreen = true\n"
+            )
+            .unwrap()
+        );
+    }
 }
@@ -27,6 +27,12 @@ impl SettingsStore {
         }
     }
 
+    /// The directory containing the user settings files, if one could be found.
+    #[cfg(feature = "file_watcher")]
+    pub(crate) fn base_path(&self) -> Option<&PathBuf> {
+        self.base_path.as_ref()
+    }
+
     /// Save a [`toml::Table`] to disk.
     ///
     /// # Arguments
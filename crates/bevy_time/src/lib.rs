@@ -17,6 +17,7 @@ pub mod common_conditions;
 mod delayed_commands;
 mod fixed;
 mod real;
+mod rollback;
 mod stopwatch;
 mod time;
 mod timer;
@@ -25,6 +26,7 @@ mod virt;
 pub use delayed_commands::*;
 pub use fixed::*;
 pub use real::*;
+pub use rollback::*;
 pub use stopwatch::*;
 pub use time::*;
 pub use timer::*;
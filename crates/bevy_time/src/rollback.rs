@@ -0,0 +1,640 @@
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
+use bevy_app::{App, FixedLast, FixedMain, Plugin};
+use bevy_ecs::{
+    change_detection::{DetectChanges, Tick},
+    entity::{EntityHashMap, EntityHashSet},
+    prelude::*,
+};
+use bevy_platform::sync::Arc;
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use core::any::{Any, TypeId};
+use thiserror::Error;
+
+use crate::{Fixed, Time, Virtual};
+
+/// Adds [`Checkpoints`] of the entities marked with [`Rollback`], saved at the end of each fixed
+/// tick, to roll back to and resimulate from.
+///
+/// Rollback netcode predicts the state of the game before the inputs of remote players are
+/// known, and corrects the prediction once they arrive: the world is rolled back to the tick
+/// the inputs apply to, then [`FixedMain`] is run again up to the present tick.
+///
+/// The [`RollbackPlugin`] saves a checkpoint at the end of each fixed tick, in a bounded ring
+/// buffer stored in the [`Checkpoints`] resource. Only the entities marked with [`Rollback`]
+/// are tracked, and only the components and resources registered with
+/// [`RollbackAppExt`] are saved. Values are cloned with [`Clone`], and only when they changed
+/// since the previous checkpoint: unchanged values are shared between checkpoints.
+///
+/// ```
+/// # use bevy_app::{prelude::*, FixedMain};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_time::{prelude::*, RollbackAppExt, RollbackPlugin, RollbackWorldExt, Rollback};
+/// #[derive(Component, Clone)]
+/// struct Position(f32);
+///
+/// fn fall(mut positions: Query<&mut Position>) {
+///     for mut position in &mut positions {
+///         position.0 -= 1.0;
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins(RollbackPlugin::default())
+///     .add_rollback_component::<Position>()
+///     .add_systems(FixedUpdate, fall);
+/// # app.init_resource::<Time>().init_resource::<Time<Virtual>>().init_resource::<Time<Fixed>>();
+///
+/// let world = app.world_mut();
+/// let entity = world.spawn((Position(10.0), Rollback)).id();
+/// world.resimulate(3);
+/// assert_eq!(world.get::<Position>(entity).unwrap().0, 7.0);
+///
+/// // A late input changes the outcome of the second tick.
+/// world.rollback_to(1).unwrap();
+/// world.get_mut::<Position>(entity).unwrap().0 += 5.0;
+/// world.resimulate(2);
+/// assert_eq!(world.get::<Position>(entity).unwrap().0, 12.0);
+/// ```
+pub struct RollbackPlugin {
+    /// The number of checkpoints kept, which bounds how far back the world can be rolled back.
+    pub capacity: usize,
+}
+
+impl Default for RollbackPlugin {
+    fn default() -> Self {
+        Self {
+            capacity: Checkpoints::DEFAULT_CAPACITY,
+        }
+    }
+}
+
+impl Plugin for RollbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Checkpoints>()
+            .world_mut()
+            .resource_mut::<Checkpoints>()
+            .set_capacity(self.capacity);
+
+        #[cfg(feature = "bevy_reflect")]
+        app.register_type::<Rollback>();
+
+        app.add_systems(FixedLast, save_checkpoint.in_set(CheckpointSystems));
+    }
+}
+
+/// Saves a checkpoint at the end of each fixed tick. Systems mutating rollback state in
+/// [`FixedLast`] should run before this set.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemSet)]
+pub struct CheckpointSystems;
+
+/// Marks an entity whose registered components are saved in [`Checkpoints`].
+///
+/// Rolling back despawns the marked entities spawned since the checkpoint, and respawns the
+/// ones despawned since.
+#[derive(Component, Debug, Default, Clone, Copy)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Default, Clone)
+)]
+pub struct Rollback;
+
+/// The saved value of a component for each entity, or of a resource.
+type Snapshot = Box<dyn Any + Send + Sync>;
+
+/// Type-erased functions to save and restore a component or resource.
+struct RollbackType {
+    type_id: TypeId,
+    /// Saves the current value, sharing the previous snapshot's value if it didn't change since
+    /// the previous checkpoint's change tick.
+    save: fn(&mut World, Option<(&Snapshot, Tick)>) -> Snapshot,
+    restore: fn(&mut World, &Snapshot),
+    remap: fn(&mut Snapshot, &EntityHashMap<Entity>),
+}
+
+impl RollbackType {
+    fn component<C: Component + Clone>() -> Self {
+        Self {
+            type_id: TypeId::of::<C>(),
+            save: |world, previous| {
+                let previous = previous.and_then(|(snapshot, tick)| {
+                    Some((snapshot.downcast_ref::<EntityHashMap<Arc<C>>>()?, tick))
+                });
+                let this_run = world.change_tick();
+                let mut query = world.query_filtered::<(Entity, Ref<C>), With<Rollback>>();
+                let saved = query
+                    .iter(world)
+                    .map(|(entity, component)| {
+                        let unchanged = previous.and_then(|(values, last_run)| {
+                            let value = values.get(&entity)?;
+                            (!component.last_changed().is_newer_than(last_run, this_run))
+                                .then(|| value.clone())
+                        });
+                        let value = unchanged.unwrap_or_else(|| Arc::new(C::clone(&component)));
+                        (entity, value)
+                    })
+                    .collect::<EntityHashMap<_>>();
+                Box::new(saved)
+            },
+            restore: |world, snapshot| {
+                let Some(values) = snapshot.downcast_ref::<EntityHashMap<Arc<C>>>() else {
+                    return;
+                };
+                let mut query = world.query_filtered::<Entity, With<Rollback>>();
+                for entity in query.iter(world).collect::<Vec<_>>() {
+                    let mut entity = world.entity_mut(entity);
+                    match values.get(&entity.id()) {
+                        Some(value) => {
+                            entity.insert(C::clone(value));
+                        }
+                        None => {
+                            entity.remove::<C>();
+                        }
+                    }
+                }
+            },
+            remap: |snapshot, mapping| {
+                let Some(values) = snapshot.downcast_mut::<EntityHashMap<Arc<C>>>() else {
+                    return;
+                };
+                *values = values
+                    .drain()
+                    .map(|(entity, value)| (mapping.get(&entity).copied().unwrap_or(entity), value))
+                    .collect();
+            },
+        }
+    }
+
+    fn resource<R: Resource + Clone>() -> Self {
+        Self {
+            type_id: TypeId::of::<R>(),
+            save: |world, previous| {
+                let this_run = world.change_tick();
+                let saved = world.get_resource_ref::<R>().map(|resource| {
+                    previous
+                        .and_then(|(snapshot, last_run)| {
+                            let value = snapshot.downcast_ref::<Option<Arc<R>>>()?.as_ref()?;
+                            (!resource.last_changed().is_newer_than(last_run, this_run))
+                                .then(|| value.clone())
+                        })
+                        .unwrap_or_else(|| Arc::new(R::clone(&resource)))
+                });
+                Box::new(saved)
+            },
+            restore: |world, snapshot| match snapshot.downcast_ref::<Option<Arc<R>>>() {
+                Some(Some(value)) => world.insert_resource(R::clone(value)),
+                Some(None) => {
+                    world.remove_resource::<R>();
+                }
+                None => {}
+            },
+            remap: |_, _| {},
+        }
+    }
+}
+
+/// The state of the rollback entities, components and resources at the end of a fixed tick.
+struct Checkpoint {
+    tick: u64,
+    /// The world change tick when the checkpoint was saved, used to only clone the values which
+    /// changed since.
+    change_tick: Tick,
+    time: Time<Fixed>,
+    entities: EntityHashSet,
+    /// One snapshot per [`RollbackType`], in registration order.
+    snapshots: Vec<Snapshot>,
+}
+
+impl Checkpoint {
+    fn remap(&mut self, types: &[RollbackType], mapping: &EntityHashMap<Entity>) {
+        self.entities = self
+            .entities
+            .iter()
+            .map(|entity| mapping.get(entity).copied().unwrap_or(*entity))
+            .collect();
+        for (ty, snapshot) in types.iter().zip(&mut self.snapshots) {
+            (ty.remap)(snapshot, mapping);
+        }
+    }
+}
+
+/// A bounded ring buffer of checkpoints, one per fixed tick, added by the [`RollbackPlugin`].
+///
+/// Roll back to one of them with [`RollbackWorldExt::rollback_to`].
+#[derive(Resource)]
+pub struct Checkpoints {
+    capacity: usize,
+    tick: u64,
+    checkpoints: VecDeque<Checkpoint>,
+    types: Vec<RollbackType>,
+}
+
+impl Default for Checkpoints {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl Checkpoints {
+    /// The default number of checkpoints kept, which is half a second at the default fixed
+    /// timestep.
+    pub const DEFAULT_CAPACITY: usize = 32;
+
+    /// Creates an empty ring buffer keeping up to `capacity` checkpoints.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tick: 0,
+            checkpoints: VecDeque::with_capacity(capacity),
+            types: Vec::new(),
+        }
+    }
+
+    /// Returns the number of fixed ticks simulated so far, which is the tick of the latest
+    /// checkpoint.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Returns the maximum number of checkpoints kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the maximum number of checkpoints kept, dropping the oldest ones if needed.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.checkpoints.len() > capacity {
+            self.checkpoints.pop_front();
+        }
+    }
+
+    /// Returns the ticks which can be rolled back to, from oldest to newest.
+    pub fn ticks(&self) -> impl DoubleEndedIterator<Item = u64> + '_ {
+        self.checkpoints.iter().map(|checkpoint| checkpoint.tick)
+    }
+
+    /// Returns `true` if the world can be rolled back to `tick`.
+    pub fn contains(&self, tick: u64) -> bool {
+        self.position(tick).is_some()
+    }
+
+    /// Returns the number of checkpoints.
+    pub fn len(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// Returns `true` if there are no checkpoints.
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints.is_empty()
+    }
+
+    /// Drops all the checkpoints, without changing the current tick.
+    pub fn clear(&mut self) {
+        self.checkpoints.clear();
+    }
+
+    fn register(&mut self, ty: RollbackType) {
+        if self
+            .types
+            .iter()
+            .all(|registered| registered.type_id != ty.type_id)
+        {
+            self.types.push(ty);
+        }
+    }
+
+    fn position(&self, tick: u64) -> Option<usize> {
+        let oldest = self.checkpoints.front()?.tick;
+        let position = usize::try_from(tick.checked_sub(oldest)?).ok()?;
+        (position < self.checkpoints.len()).then_some(position)
+    }
+
+    /// Saves the current state of the world as the checkpoint of the next tick.
+    fn save(&mut self, world: &mut World) {
+        if self.capacity == 0 {
+            self.tick += 1;
+            return;
+        }
+        let previous = self.checkpoints.back();
+        let mut entities = world.query_filtered::<Entity, With<Rollback>>();
+        let entities = entities.iter(world).collect();
+        let snapshots = self
+            .types
+            .iter()
+            .enumerate()
+            .map(|(index, ty)| {
+                let previous = previous.and_then(|checkpoint| {
+                    Some((checkpoint.snapshots.get(index)?, checkpoint.change_tick))
+                });
+                (ty.save)(world, previous)
+            })
+            .collect();
+
+        self.tick += 1;
+        if self.checkpoints.len() == self.capacity {
+            self.checkpoints.pop_front();
+        }
+        self.checkpoints.push_back(Checkpoint {
+            tick: self.tick,
+            change_tick: world.change_tick(),
+            time: *world.resource::<Time<Fixed>>(),
+            entities,
+            snapshots,
+        });
+        // Changes made from now on must be newer than the checkpoint's change tick.
+        world.increment_change_tick();
+    }
+
+    fn rollback(
+        &mut self,
+        world: &mut World,
+        tick: u64,
+    ) -> Result<EntityHashMap<Entity>, RollbackError> {
+        let position = self
+            .position(tick)
+            .ok_or(RollbackError::MissingCheckpoint {
+                tick,
+                oldest: self.checkpoints.front().map(|checkpoint| checkpoint.tick),
+            })?;
+        self.checkpoints.truncate(position + 1);
+        self.tick = tick;
+
+        let checkpoint = &self.checkpoints[position];
+        let mut query = world.query_filtered::<Entity, With<Rollback>>();
+        let spawned = query
+            .iter(world)
+            .filter(|entity| !checkpoint.entities.contains(entity))
+            .collect::<Vec<_>>();
+        for entity in spawned {
+            world.despawn(entity);
+        }
+
+        // Despawned entities can't be respawned with the same id, so they get a new one in every
+        // remaining checkpoint.
+        let despawned = checkpoint
+            .entities
+            .iter()
+            .filter(|entity| world.get_entity(**entity).is_err())
+            .copied()
+            .collect::<Vec<_>>();
+        let respawned = despawned
+            .into_iter()
+            .map(|entity| (entity, world.spawn(Rollback).id()))
+            .collect::<EntityHashMap<_>>();
+        if !respawned.is_empty() {
+            for checkpoint in &mut self.checkpoints {
+                checkpoint.remap(&self.types, &respawned);
+            }
+        }
+
+        let checkpoint = &self.checkpoints[position];
+        for (ty, snapshot) in self.types.iter().zip(&checkpoint.snapshots) {
+            (ty.restore)(world, snapshot);
+        }
+
+        let mut time = world.resource_mut::<Time<Fixed>>();
+        let overstep = time.overstep();
+        *time = checkpoint.time;
+        time.discard_overstep(checkpoint.time.overstep());
+        time.accumulate_overstep(overstep);
+
+        Ok(respawned)
+    }
+}
+
+/// An error returned when rolling back the world.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RollbackError {
+    /// The [`Checkpoints`] resource doesn't exist.
+    #[error("the `Checkpoints` resource doesn't exist, add the `RollbackPlugin`")]
+    MissingCheckpoints,
+    /// The requested tick is not in the ring buffer, either because it is too old or because it
+    /// hasn't been simulated yet.
+    #[error("no checkpoint for tick {tick}, the oldest checkpoint is {oldest:?}")]
+    MissingCheckpoint {
+        /// The requested tick.
+        tick: u64,
+        /// The oldest tick which can be rolled back to, if any.
+        oldest: Option<u64>,
+    },
+}
+
+/// Saves a checkpoint of the current state in [`Checkpoints`].
+///
+/// Added to [`FixedLast`] by the [`RollbackPlugin`].
+pub fn save_checkpoint(world: &mut World) {
+    world.resource_scope(|world, mut checkpoints: Mut<Checkpoints>| checkpoints.save(world));
+}
+
+/// Extension trait to register the components and resources saved in [`Checkpoints`].
+pub trait RollbackAppExt {
+    /// Saves the `C` component of the entities marked with [`Rollback`] in each checkpoint.
+    fn add_rollback_component<C: Component + Clone>(&mut self) -> &mut Self;
+
+    /// Saves the `R` resource in each checkpoint. Rolling back removes the resource if it didn't
+    /// exist at the checkpoint.
+    fn add_rollback_resource<R: Resource + Clone>(&mut self) -> &mut Self;
+}
+
+impl RollbackAppExt for App {
+    fn add_rollback_component<C: Component + Clone>(&mut self) -> &mut Self {
+        self.init_resource::<Checkpoints>()
+            .world_mut()
+            .resource_mut::<Checkpoints>()
+            .register(RollbackType::component::<C>());
+        self
+    }
+
+    fn add_rollback_resource<R: Resource + Clone>(&mut self) -> &mut Self {
+        self.init_resource::<Checkpoints>()
+            .world_mut()
+            .resource_mut::<Checkpoints>()
+            .register(RollbackType::resource::<R>());
+        self
+    }
+}
+
+/// Extension trait to roll the [`World`] back to one of its [`Checkpoints`] and resimulate.
+pub trait RollbackWorldExt {
+    /// Restores the state saved at the end of the fixed `tick`, and drops the newer checkpoints.
+    ///
+    /// Entities marked with [`Rollback`] spawned since are despawned, and the ones despawned since
+    /// are respawned. As they can't reuse their former id, the returned map goes from the former
+    /// ids to the respawned ones. Entities stored in the components of other entities are not
+    /// remapped.
+    ///
+    /// [`Time<Fixed>`](Fixed) is restored to the tick, but keeps its current
+    /// [`overstep`](Time::overstep).
+    fn rollback_to(&mut self, tick: u64) -> Result<EntityHashMap<Entity>, RollbackError>;
+
+    /// Runs the [`FixedMain`] schedule `ticks` times, advancing [`Time<Fixed>`](Fixed) by one
+    /// timestep each time, regardless of the accumulated [`overstep`](Time::overstep).
+    fn resimulate(&mut self, ticks: u64);
+
+    /// Rolls back to `tick` with [`rollback_to`](Self::rollback_to), then resimulates up to the
+    /// current tick.
+    fn rollback_and_resimulate(
+        &mut self,
+        tick: u64,
+    ) -> Result<EntityHashMap<Entity>, RollbackError>;
+}
+
+impl RollbackWorldExt for World {
+    fn rollback_to(&mut self, tick: u64) -> Result<EntityHashMap<Entity>, RollbackError> {
+        self.try_resource_scope(|world, mut checkpoints: Mut<Checkpoints>| {
+            checkpoints.rollback(world, tick)
+        })
+        .ok_or(RollbackError::MissingCheckpoints)?
+    }
+
+    fn resimulate(&mut self, ticks: u64) {
+        let _ = self.try_schedule_scope(FixedMain, |world, schedule| {
+            for _ in 0..ticks {
+                let mut time = world.resource_mut::<Time<Fixed>>();
+                let timestep = time.timestep();
+                time.advance_by(timestep);
+                *world.resource_mut::<Time>() = world.resource::<Time<Fixed>>().as_generic();
+                schedule.run(world);
+            }
+        });
+
+        *self.resource_mut::<Time>() = self.resource::<Time<Virtual>>().as_generic();
+    }
+
+    fn rollback_and_resimulate(
+        &mut self,
+        tick: u64,
+    ) -> Result<EntityHashMap<Entity>, RollbackError> {
+        let present = self
+            .get_resource::<Checkpoints>()
+            .ok_or(RollbackError::MissingCheckpoints)?
+            .tick();
+        let respawned = self.rollback_to(tick)?;
+        self.resimulate(present - tick);
+        Ok(respawned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::FixedUpdate;
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Position(i32);
+
+    #[derive(Resource, Clone, Default)]
+    struct Spawned(u32);
+
+    fn step(mut positions: Query<&mut Position>) {
+        for mut position in &mut positions {
+            position.0 += 1;
+        }
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins(RollbackPlugin { capacity: 4 })
+            .init_resource::<Time>()
+            .init_resource::<Time<Virtual>>()
+            .init_resource::<Time<Fixed>>()
+            .add_rollback_component::<Position>()
+            .add_rollback_resource::<Spawned>()
+            .add_systems(FixedUpdate, step);
+        app
+    }
+
+    #[test]
+    fn rollback_restores_components_resources_and_time() {
+        let mut app = app();
+        let world = app.world_mut();
+        let a = world.spawn((Position(0), Rollback)).id();
+        let untracked = world.spawn(Position(0)).id();
+        world.resimulate(2);
+
+        world.insert_resource(Spawned(1));
+        world.resimulate(4);
+        assert_eq!(world.resource::<Checkpoints>().tick(), 6);
+        assert_eq!(
+            world.resource::<Checkpoints>().ticks().collect::<Vec<_>>(),
+            [3, 4, 5, 6]
+        );
+        assert_eq!(
+            world.rollback_to(2),
+            Err(RollbackError::MissingCheckpoint {
+                tick: 2,
+                oldest: Some(3)
+            })
+        );
+
+        world.rollback_to(4).unwrap();
+        assert_eq!(world.get::<Position>(a), Some(&Position(4)));
+        assert_eq!(world.get::<Position>(untracked), Some(&Position(6)));
+        assert_eq!(world.resource::<Spawned>().0, 1);
+        let time = world.resource::<Time<Fixed>>();
+        assert_eq!(time.elapsed(), time.timestep() * 4);
+        assert_eq!(
+            world.resource::<Checkpoints>().ticks().collect::<Vec<_>>(),
+            [3, 4]
+        );
+
+        world.rollback_and_resimulate(3).unwrap();
+        assert_eq!(world.get::<Position>(a), Some(&Position(4)));
+        assert_eq!(world.resource::<Checkpoints>().tick(), 4);
+    }
+
+    #[test]
+    fn rollback_restores_spawned_and_despawned_entities() {
+        let mut app = app();
+        let world = app.world_mut();
+        let kept = world.spawn((Position(0), Rollback)).id();
+        let despawned = world.spawn((Position(10), Rollback)).id();
+        world.resimulate(1);
+
+        world.despawn(despawned);
+        let spawned = world.spawn((Position(20), Rollback)).id();
+        world.entity_mut(kept).remove::<Position>();
+        world.resimulate(2);
+
+        let respawned = world.rollback_to(1).unwrap();
+        assert!(world.get_entity(spawned).is_err());
+        assert_eq!(world.get::<Position>(kept), Some(&Position(1)));
+        let respawned = respawned[&despawned];
+        assert_eq!(world.get::<Position>(respawned), Some(&Position(11)));
+
+        // The checkpoints refer to the respawned entity from now on.
+        world.resimulate(1);
+        world.despawn(respawned);
+        let respawned = world.rollback_to(1).unwrap()[&respawned];
+        assert_eq!(world.get::<Position>(respawned), Some(&Position(11)));
+    }
+
+    #[test]
+    fn unchanged_values_are_shared_between_checkpoints() {
+        let mut app = app();
+        let world = app.world_mut();
+        world.spawn((Position(0), Rollback));
+        world.insert_resource(Spawned(0));
+        world.resimulate(2);
+
+        let checkpoints = world.resource::<Checkpoints>();
+        let [first, second] = [0, 1].map(|i| &checkpoints.checkpoints[i].snapshots);
+        let positions = [first, second]
+            .map(|snapshots| snapshots[0].downcast_ref::<EntityHashMap<Arc<Position>>>());
+        let resources =
+            [first, second].map(|snapshots| snapshots[1].downcast_ref::<Option<Arc<Spawned>>>());
+        let [Some(first), Some(second)] = positions else {
+            panic!("snapshots have the wrong type");
+        };
+        assert!(!Arc::ptr_eq(
+            first.values().next().unwrap(),
+            second.values().next().unwrap()
+        ));
+        let [Some(Some(first)), Some(Some(second))] = resources else {
+            panic!("snapshots have the wrong type");
+        };
+        assert!(Arc::ptr_eq(first, second));
+    }
+}
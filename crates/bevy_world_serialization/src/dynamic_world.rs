@@ -10,6 +10,7 @@ use bevy_reflect::{PartialReflect, TypePath, TypeRegistry};
 
 use bevy_ecs::component::ComponentCloneBehavior;
 use bevy_ecs::relationship::RelationshipHookMode;
+use core::any::TypeId;
 
 #[cfg(feature = "serialize")]
 use {crate::serde::DynamicWorldSerializer, serde::Serialize};
@@ -17,6 +18,10 @@ use {crate::serde::DynamicWorldSerializer, serde::Serialize};
 /// A collection of serializable resources and dynamic entities.
 ///
 /// Each dynamic entity in the collection contains its own run-time defined set of components.
+///
+/// A dynamic world can also describe a *delta* against an earlier snapshot, built with
+/// [`DynamicWorldBuilder::with_baseline`]. Deltas only contain the components that changed since
+/// the snapshot, alongside the components that were removed and the entities that were despawned.
 /// To spawn a dynamic world, you can use either:
 /// * [`WorldInstanceSpawner::spawn_dynamic`](crate::WorldInstanceSpawner::spawn_dynamic)
 /// * adding the [`DynamicWorldRoot`](crate::components::DynamicWorldRoot) component to an entity.
@@ -27,6 +32,10 @@ pub struct DynamicWorld {
     pub resources: Vec<Box<dyn PartialReflect>>,
     /// Entities contained in the dynamic world.
     pub entities: Vec<DynamicEntity>,
+    /// Entities that were despawned since the snapshot this dynamic world is a delta of.
+    ///
+    /// This is always empty for full snapshots.
+    pub despawned_entities: Vec<Entity>,
 }
 
 /// A reflection-powered serializable representation of an entity and its components.
@@ -38,6 +47,11 @@ pub struct DynamicEntity {
     /// A vector of boxed components that belong to the given entity and
     /// implement the [`PartialReflect`] trait.
     pub components: Vec<Box<dyn PartialReflect>>,
+    /// The [`TypeId`]s of the components that were removed from the entity since the snapshot
+    /// this dynamic world is a delta of.
+    ///
+    /// This is always empty for full snapshots.
+    pub removed_components: Vec<TypeId>,
 }
 
 impl DynamicWorld {
@@ -84,8 +98,22 @@ impl DynamicWorld {
             .build()
     }
 
+    /// Returns `true` if this dynamic world removes components or despawns entities when written
+    /// to a world, i.e. if it is a delta that cannot be represented as a plain snapshot.
+    pub fn has_removals(&self) -> bool {
+        !self.despawned_entities.is_empty()
+            || self
+                .entities
+                .iter()
+                .any(|entity| !entity.removed_components.is_empty())
+    }
+
     /// Write the resources, the dynamic entities, and their corresponding components to the given world.
     ///
+    /// If this dynamic world is a delta (see [`DynamicWorldBuilder::with_baseline`]), the world is
+    /// patched: removed components are removed from the mapped entities, and despawned entities that
+    /// are present in the `entity_map` are despawned and removed from the map.
+    ///
     /// This method will return a [`WorldInstanceSpawnError`] if a type either is not registered
     /// in the provided [`AppTypeRegistry`] resource, or doesn't reflect the
     /// [`Component`](bevy_ecs::component::Component) or [`Resource`](bevy_ecs::prelude::Resource) trait.
@@ -155,6 +183,21 @@ impl DynamicWorld {
                     );
                 });
             }
+
+            for &type_id in &dynamic_entity.removed_components {
+                let registration = type_registry.get(type_id).ok_or_else(|| {
+                    WorldInstanceSpawnError::UnregisteredButReflectedType {
+                        type_path: format!("{type_id:?}"),
+                    }
+                })?;
+                let reflect_component =
+                    registration.data::<ReflectComponent>().ok_or_else(|| {
+                        WorldInstanceSpawnError::UnregisteredComponent {
+                            type_path: registration.type_info().type_path().to_string(),
+                        }
+                    })?;
+                reflect_component.remove(&mut world.entity_mut(entity));
+            }
         }
 
         // Insert resources after all entities have been added to the world.
@@ -200,6 +243,14 @@ impl DynamicWorld {
             });
         }
 
+        // Despawn entities last, so that components and resources written above may still
+        // reference them while being mapped.
+        for despawned in &self.despawned_entities {
+            if let Some(entity) = entity_map.remove(despawned) {
+                world.try_despawn(entity).ok();
+            }
+        }
+
         Ok(())
     }
 
@@ -229,6 +280,20 @@ impl DynamicWorld {
     pub fn serialize(&self, registry: &TypeRegistry) -> Result<String, ron::Error> {
         serialize_ron(DynamicWorldSerializer::new(self, registry))
     }
+
+    // TODO: move to AssetSaver when it is implemented
+    /// Serialize this dynamic world into the binary Bevy world format (`.scn.bin`).
+    ///
+    /// The binary format uses the same structure as [`DynamicWorld::serialize`], encoded with
+    /// [postcard]. It is much more compact and faster to parse than RON, at the cost of not being
+    /// human-readable. To deserialize the format, use the [`WorldAssetLoader`].
+    ///
+    /// [`WorldAssetLoader`]: crate::WorldAssetLoader
+    /// [postcard]: https://crates.io/crates/postcard
+    #[cfg(feature = "serialize")]
+    pub fn serialize_binary(&self, registry: &TypeRegistry) -> Result<Vec<u8>, postcard::Error> {
+        serialize_postcard(DynamicWorldSerializer::new(self, registry))
+    }
 }
 
 /// Serialize a given Rust data structure into rust object notation (ron).
@@ -243,6 +308,17 @@ where
     ron::ser::to_string_pretty(&serialize, pretty_config)
 }
 
+/// Serialize a given Rust data structure into the compact [postcard] binary format.
+///
+/// [postcard]: https://crates.io/crates/postcard
+#[cfg(feature = "serialize")]
+pub fn serialize_postcard<S>(serialize: S) -> Result<Vec<u8>, postcard::Error>
+where
+    S: Serialize,
+{
+    postcard::to_allocvec(&serialize)
+}
+
 #[cfg(test)]
 mod tests {
     use bevy_ecs::{
@@ -435,4 +511,66 @@ mod tests {
             .write_to_world(&mut dst_world, &mut Default::default())
             .unwrap();
     }
+
+    #[test]
+    fn write_delta_patches_world() {
+        #[derive(Component, Reflect, PartialEq, Debug)]
+        #[reflect(Component)]
+        struct Health(u32);
+
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Poisoned;
+
+        let reg = AppTypeRegistry::default();
+        {
+            let mut reg_write = reg.write();
+            reg_write.register::<Health>();
+            reg_write.register::<Poisoned>();
+        }
+
+        let mut source_world = World::new();
+        source_world.insert_resource(reg.clone());
+        let player = source_world.spawn((Health(10), Poisoned)).id();
+        let enemy = source_world.spawn(Health(5)).id();
+
+        let (snapshot, baseline) = {
+            let type_registry = reg.read();
+            DynamicWorldBuilder::from_world(&source_world, &type_registry)
+                .extract_entities([player, enemy].into_iter())
+                .build_with_baseline()
+        };
+
+        let mut entity_map = EntityHashMap::default();
+        let mut destination_world = World::new();
+        destination_world.insert_resource(reg.clone());
+        snapshot
+            .write_to_world(&mut destination_world, &mut entity_map)
+            .unwrap();
+
+        source_world.increment_change_tick();
+        source_world.get_mut::<Health>(player).unwrap().0 = 7;
+        source_world.entity_mut(player).remove::<Poisoned>();
+        source_world.despawn(enemy);
+
+        let delta = {
+            let type_registry = reg.read();
+            DynamicWorldBuilder::from_world(&source_world, &type_registry)
+                .with_baseline(&baseline)
+                .extract_entity(player)
+                .build()
+        };
+        assert!(delta.has_removals());
+
+        let mapped_enemy = entity_map[&enemy];
+        delta
+            .write_to_world(&mut destination_world, &mut entity_map)
+            .unwrap();
+
+        let mapped_player = destination_world.entity(entity_map[&player]);
+        assert_eq!(mapped_player.get::<Health>(), Some(&Health(7)));
+        assert!(!mapped_player.contains::<Poisoned>());
+        assert!(destination_world.get_entity(mapped_enemy).is_err());
+        assert!(!entity_map.contains_key(&enemy));
+    }
 }
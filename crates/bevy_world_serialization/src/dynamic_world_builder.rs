@@ -5,7 +5,9 @@ use crate::{DynamicEntity, DynamicWorld, WorldFilter};
 use alloc::collections::BTreeMap;
 use bevy_ecs::resource::IS_RESOURCE;
 use bevy_ecs::{
+    change_detection::Tick,
     component::{Component, ComponentId},
+    entity::EntityHashMap,
     entity_disabling::DefaultQueryFilters,
     prelude::Entity,
     reflect::{ReflectComponent, ReflectResource},
@@ -35,6 +37,43 @@ use bevy_utils::default;
 ///
 /// Extraction happens immediately and uses the filter as it exists during the time of extraction.
 ///
+/// # Delta Snapshots
+///
+/// A builder can be given a [`DynamicWorldBaseline`] from an earlier snapshot with [`with_baseline`].
+/// The resulting [`DynamicWorld`] is then a delta: it only contains the components that were added
+/// or changed since the baseline (according to their change ticks), the components that were removed,
+/// and the entities of the baseline that have been despawned. Entities with no changes are skipped.
+/// Writing a delta to a world that was populated from the previous snapshot patches it in place.
+///
+/// Use [`build_with_baseline`] to get the baseline for the next delta alongside the dynamic world.
+///
+/// ```
+/// # use bevy_world_serialization::DynamicWorldBuilder;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::Reflect;
+/// # #[derive(Component, Reflect, Default)]
+/// # #[reflect(Component)]
+/// # struct Health(u32);
+/// # let mut world = World::default();
+/// # world.init_resource::<AppTypeRegistry>();
+/// # world.resource::<AppTypeRegistry>().write().register::<Health>();
+/// # let entity = world.spawn(Health(10)).id();
+/// # let registry = world.resource::<AppTypeRegistry>().clone();
+/// let (snapshot, baseline) = DynamicWorldBuilder::from_world(&world, &registry.read())
+///     .extract_entity(entity)
+///     .build_with_baseline();
+///
+/// // Make sure later changes are not attributed to the tick the snapshot was taken on.
+/// world.increment_change_tick();
+/// world.get_mut::<Health>(entity).unwrap().0 = 5;
+///
+/// let (delta, next_baseline) = DynamicWorldBuilder::from_world(&world, &registry.read())
+///     .with_baseline(&baseline)
+///     .extract_entity(entity)
+///     .build_with_baseline();
+/// assert_eq!(delta.entities.len(), 1);
+/// ```
+///
 /// # Entity Order
 ///
 /// Extracted entities will always be stored in ascending order based on their [index](Entity::index).
@@ -63,6 +102,8 @@ use bevy_utils::default;
 ///
 /// [`AppTypeRegistry`]: bevy_ecs::reflect::AppTypeRegistry
 /// [`Reflect`]: bevy_reflect::Reflect
+/// [`with_baseline`]: Self::with_baseline
+/// [`build_with_baseline`]: Self::build_with_baseline
 pub struct DynamicWorldBuilder<'w> {
     /// The resources that have been extracted so far.
     extracted_resources: BTreeMap<ComponentId, Box<dyn PartialReflect>>,
@@ -76,6 +117,48 @@ pub struct DynamicWorldBuilder<'w> {
     original_world: &'w World,
     /// The type registry to use for extracting items from the world.
     type_registry: &'w TypeRegistry,
+    /// The snapshot to build a delta against, if any.
+    baseline: Option<&'w DynamicWorldBaseline>,
+    /// The change tick of the world at the time this builder was created.
+    this_run: Tick,
+}
+
+/// The state of a [`World`] at the time a [`DynamicWorld`] snapshot was taken.
+///
+/// Produced by [`DynamicWorldBuilder::build_with_baseline`] and consumed by
+/// [`DynamicWorldBuilder::with_baseline`] to build delta snapshots.
+///
+/// Change detection is based on the world's change tick at the time of the snapshot.
+/// Changes made later within the same tick (for example by mutating the world directly, outside of
+/// systems) are not detected unless [`World::increment_change_tick`] is called after the snapshot.
+#[derive(Clone, Debug, Default)]
+pub struct DynamicWorldBaseline {
+    /// The change tick at which the snapshot was taken.
+    tick: Tick,
+    /// The components each snapshotted entity had at the time of the snapshot.
+    entities: EntityHashMap<Vec<ComponentId>>,
+}
+
+impl DynamicWorldBaseline {
+    /// Returns the change tick at which the snapshot was taken.
+    pub fn tick(&self) -> Tick {
+        self.tick
+    }
+
+    /// Returns `true` if the given entity was part of the snapshot.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities.contains_key(&entity)
+    }
+
+    /// Returns the number of entities that were part of the snapshot.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entities were part of the snapshot.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
 }
 
 impl<'w> DynamicWorldBuilder<'w> {
@@ -95,9 +178,22 @@ impl<'w> DynamicWorldBuilder<'w> {
             resource_filter: WorldFilter::default(),
             original_world: world,
             type_registry,
+            baseline: None,
+            this_run: world.read_change_tick(),
         }
     }
 
+    /// Build a delta against the given [`DynamicWorldBaseline`] instead of a full snapshot.
+    ///
+    /// Extracted entities that were part of the baseline only include the components that were
+    /// added or changed since then, as well as the components they lost. Entities of the baseline
+    /// that no longer exist are recorded as [despawned](DynamicWorld::despawned_entities) when building.
+    #[must_use]
+    pub fn with_baseline(mut self, baseline: &'w DynamicWorldBaseline) -> Self {
+        self.baseline = Some(baseline);
+        self
+    }
+
     /// Specify a custom component [`WorldFilter`] to be used with this builder.
     #[must_use]
     pub fn with_component_filter(mut self, filter: WorldFilter) -> Self {
@@ -230,10 +326,48 @@ impl<'w> DynamicWorldBuilder<'w> {
     /// [`Self::remove_empty_entities`] before building the dynamic world.
     #[must_use]
     pub fn build(self) -> DynamicWorld {
-        DynamicWorld {
+        self.build_with_baseline().0
+    }
+
+    /// Consume the builder, producing a [`DynamicWorld`] and the [`DynamicWorldBaseline`] to build
+    /// the next delta against.
+    ///
+    /// The baseline contains every entity extracted by this builder, as well as the entities of the
+    /// previous baseline (if any) that still exist.
+    #[must_use]
+    pub fn build_with_baseline(self) -> (DynamicWorld, DynamicWorldBaseline) {
+        let mut entities = EntityHashMap::default();
+        let mut despawned_entities = Vec::new();
+        if let Some(baseline) = self.baseline {
+            for (&entity, components) in baseline.entities.iter() {
+                if self.original_world.get_entity(entity).is_ok() {
+                    entities.insert(entity, components.clone());
+                } else {
+                    despawned_entities.push(entity);
+                }
+            }
+            despawned_entities.sort();
+        }
+        for &entity in self.extracted_entities.keys() {
+            let components = self
+                .original_world
+                .entity(entity)
+                .archetype()
+                .components()
+                .to_vec();
+            entities.insert(entity, components);
+        }
+
+        let dynamic_world = DynamicWorld {
             resources: self.extracted_resources.into_values().collect(),
             entities: self.extracted_entities.into_values().collect(),
-        }
+            despawned_entities,
+        };
+        let baseline = DynamicWorldBaseline {
+            tick: self.this_run,
+            entities,
+        };
+        (dynamic_world, baseline)
     }
 
     /// Extract one entity from the builder's [`World`].
@@ -249,8 +383,9 @@ impl<'w> DynamicWorldBuilder<'w> {
     /// These were likely created because none of their components were present in the provided type registry upon extraction.
     #[must_use]
     pub fn remove_empty_entities(mut self) -> Self {
-        self.extracted_entities
-            .retain(|_, entity| !entity.components.is_empty());
+        self.extracted_entities.retain(|_, entity| {
+            !entity.components.is_empty() || !entity.removed_components.is_empty()
+        });
 
         self
     }
@@ -299,6 +434,7 @@ impl<'w> DynamicWorldBuilder<'w> {
             let mut entry = DynamicEntity {
                 entity,
                 components: Vec::new(),
+                removed_components: Vec::new(),
             };
 
             let original_entity = self.original_world.entity(entity);
@@ -306,8 +442,19 @@ impl<'w> DynamicWorldBuilder<'w> {
                 continue;
             }
 
+            let previous_components = self
+                .baseline
+                .and_then(|baseline| baseline.entities.get(&entity));
+
             for &component_id in original_entity.archetype().components().iter() {
                 let mut extract_and_push = || {
+                    if let (Some(baseline), Some(_)) = (self.baseline, previous_components) {
+                        let ticks = original_entity.get_change_ticks_by_id(component_id)?;
+                        if !ticks.is_changed(baseline.tick, self.this_run) {
+                            return None;
+                        }
+                    }
+
                     let type_id = self
                         .original_world
                         .components()
@@ -335,6 +482,37 @@ impl<'w> DynamicWorldBuilder<'w> {
                 };
                 extract_and_push();
             }
+
+            if let Some(previous_components) = previous_components {
+                for &component_id in previous_components {
+                    if original_entity.contains_id(component_id) {
+                        continue;
+                    }
+                    let mut record_removal = || {
+                        let type_id = self
+                            .original_world
+                            .components()
+                            .get_info(component_id)?
+                            .type_id()?;
+
+                        if self.component_filter.is_denied_by_id(type_id) {
+                            return None;
+                        }
+
+                        self.type_registry
+                            .get(type_id)?
+                            .data::<ReflectComponent>()?;
+                        entry.removed_components.push(type_id);
+                        Some(())
+                    };
+                    record_removal();
+                }
+
+                // Unchanged entities are already up to date in the baseline.
+                if entry.components.is_empty() && entry.removed_components.is_empty() {
+                    continue;
+                }
+            }
             self.extracted_entities.insert(entity, entry);
         }
 
@@ -426,6 +604,7 @@ mod tests {
     };
 
     use bevy_reflect::{Reflect, TypeRegistry};
+    use core::any::TypeId;
 
     use super::DynamicWorldBuilder;
 
@@ -732,4 +911,59 @@ mod tests {
             .expect("resource should be concrete due to `FromReflect`")
             .is::<SomeResource>());
     }
+
+    #[test]
+    fn extract_delta_since_baseline() {
+        let mut world = World::default();
+
+        let mut type_registry = TypeRegistry::default();
+        type_registry.register::<ComponentA>();
+        type_registry.register::<ComponentB>();
+
+        let unchanged = world.spawn(ComponentA).id();
+        let changed = world.spawn(ComponentA).id();
+        let removed = world.spawn((ComponentA, ComponentB)).id();
+        let despawned = world.spawn(ComponentA).id();
+
+        let (_, baseline) = DynamicWorldBuilder::from_world(&world, &type_registry)
+            .extract_entities([unchanged, changed, removed, despawned].into_iter())
+            .build_with_baseline();
+        assert_eq!(baseline.len(), 4);
+
+        world.increment_change_tick();
+        world.entity_mut(changed).insert(ComponentB);
+        world.entity_mut(removed).remove::<ComponentB>();
+        world.despawn(despawned);
+        let spawned = world.spawn(ComponentA).id();
+
+        let mut query = world.query::<Entity>();
+        let (delta, next_baseline) = DynamicWorldBuilder::from_world(&world, &type_registry)
+            .with_baseline(&baseline)
+            .extract_entities(query.iter(&world))
+            .build_with_baseline();
+
+        assert_eq!(delta.entities.len(), 3);
+        assert_eq!(delta.despawned_entities, vec![despawned]);
+
+        let changed_entity = delta.entities.iter().find(|e| e.entity == changed).unwrap();
+        assert_eq!(changed_entity.components.len(), 1);
+        assert!(changed_entity.components[0].represents::<ComponentB>());
+        assert!(changed_entity.removed_components.is_empty());
+
+        let removed_entity = delta.entities.iter().find(|e| e.entity == removed).unwrap();
+        assert!(removed_entity.components.is_empty());
+        assert_eq!(
+            removed_entity.removed_components,
+            vec![TypeId::of::<ComponentB>()]
+        );
+
+        let spawned_entity = delta.entities.iter().find(|e| e.entity == spawned).unwrap();
+        assert_eq!(spawned_entity.components.len(), 1);
+
+        assert!(delta.entities.iter().all(|e| e.entity != unchanged));
+        assert_eq!(next_baseline.len(), 4);
+        assert!(next_baseline.contains(unchanged));
+        assert!(next_baseline.contains(spawned));
+        assert!(!next_baseline.contains(despawned));
+    }
 }
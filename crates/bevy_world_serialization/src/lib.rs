@@ -140,6 +140,20 @@ mod tests {
         DynamicWorld, DynamicWorldBuilder, DynamicWorldRoot, WorldAsset, WorldAssetRoot,
         WorldSerializationPlugin,
     };
+    #[cfg(feature = "serialize")]
+    use {
+        crate::WorldAssetFormat,
+        bevy_app::TaskPoolPlugin,
+        bevy_asset::{
+            io::{
+                memory::{Dir, MemoryAssetReader},
+                AssetSourceBuilder, AssetSourceId,
+            },
+            AssetApp, AssetServer,
+        },
+        bevy_reflect::FromReflect,
+        std::path::Path,
+    };
 
     #[derive(Component, Reflect, PartialEq, Debug)]
     #[reflect(Component)]
@@ -297,6 +311,69 @@ mod tests {
         assert_eq!(child_of.0, child_root);
     }
 
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn loads_binary_world_assets() {
+        assert_eq!(
+            WorldAssetFormat::from_extension("scn.bin"),
+            WorldAssetFormat::Binary
+        );
+        assert_eq!(
+            WorldAssetFormat::from_extension("scn.ron"),
+            WorldAssetFormat::Ron
+        );
+        assert_eq!(
+            WorldAssetFormat::from_extension("scn"),
+            WorldAssetFormat::Ron
+        );
+
+        let mut app = App::new();
+        let dir = Dir::default();
+        let dir_clone = dir.clone();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || {
+                Box::new(MemoryAssetReader {
+                    root: dir_clone.clone(),
+                })
+            }),
+        );
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            WorldSerializationPlugin,
+        ))
+        .register_type::<Circle>();
+
+        let mut world = World::new();
+        world.insert_resource(app.world().resource::<AppTypeRegistry>().clone());
+        world.spawn(Circle { radius: 7.0 });
+        let type_registry = app.world().resource::<AppTypeRegistry>().read();
+        let bytes = DynamicWorld::from_world(&world)
+            .serialize_binary(&type_registry)
+            .unwrap();
+        drop(type_registry);
+        dir.insert_asset(Path::new("circle.scn.bin"), bytes);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let handle = asset_server.load::<DynamicWorld>("circle.scn.bin");
+        for _ in 0..10000 {
+            app.update();
+            if asset_server.is_loaded(&handle) {
+                break;
+            }
+        }
+
+        let dynamic_world = app
+            .world()
+            .resource::<Assets<DynamicWorld>>()
+            .get(&handle)
+            .expect("the binary world should have been loaded");
+        assert_eq!(dynamic_world.entities.len(), 1);
+        let circle = Circle::from_reflect(dynamic_world.entities[0].components[0].as_ref());
+        assert_eq!(circle, Some(Circle { radius: 7.0 }));
+    }
+
     #[test]
     fn dynamic_world_spawns_and_respawns_after_change() {
         let mut app = App::new();
@@ -13,10 +13,10 @@ use bevy_reflect::{
     },
     PartialReflect, ReflectFromReflect, TypeRegistry,
 };
-use core::fmt::Formatter;
+use core::{any::TypeId, fmt::Formatter};
 use serde::{
     de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
-    ser::{SerializeMap, SerializeStruct},
+    ser::{SerializeMap, SerializeSeq, SerializeStruct},
     Deserialize, Deserializer, Serialize, Serializer,
 };
 
@@ -26,17 +26,24 @@ pub const WORLD_STRUCT: &str = "World";
 pub const WORLD_RESOURCES: &str = "resources";
 /// Name of the serialized entities field in a world struct.
 pub const WORLD_ENTITIES: &str = "entities";
+/// Name of the serialized despawned entities field in a world struct.
+pub const WORLD_DESPAWNED: &str = "despawned";
 
 /// Name of the serialized entity struct type.
 pub const ENTITY_STRUCT: &str = "Entity";
 /// Name of the serialized component field in an entity struct.
 pub const ENTITY_FIELD_COMPONENTS: &str = "components";
+/// Name of the serialized removed components field in an entity struct.
+pub const ENTITY_FIELD_REMOVED: &str = "removed";
 
 /// Serializer for a [`DynamicWorld`].
 ///
 /// Helper object defining Bevy's serialize format for a [`DynamicWorld`] and implementing
 /// the [`Serialize`] trait for use with Serde.
 ///
+/// The fields describing a delta (despawned entities and removed components) are omitted
+/// from human-readable formats when they are empty, so that full snapshots stay concise.
+///
 /// # Example
 ///
 /// ```
@@ -81,7 +88,10 @@ impl<'a> Serialize for DynamicWorldSerializer<'a> {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct(WORLD_STRUCT, 2)?;
+        let skip_despawned =
+            serializer.is_human_readable() && self.world.despawned_entities.is_empty();
+        let mut state =
+            serializer.serialize_struct(WORLD_STRUCT, if skip_despawned { 2 } else { 3 })?;
         state.serialize_field(
             WORLD_RESOURCES,
             &WorldMapSerializer {
@@ -96,6 +106,11 @@ impl<'a> Serialize for DynamicWorldSerializer<'a> {
                 registry: self.registry,
             },
         )?;
+        if skip_despawned {
+            state.skip_field(WORLD_DESPAWNED)?;
+        } else {
+            state.serialize_field(WORLD_DESPAWNED, &self.world.despawned_entities)?;
+        }
         state.end()
     }
 }
@@ -140,7 +155,10 @@ impl<'a> Serialize for EntitySerializer<'a> {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct(ENTITY_STRUCT, 1)?;
+        let skip_removed =
+            serializer.is_human_readable() && self.entity.removed_components.is_empty();
+        let mut state =
+            serializer.serialize_struct(ENTITY_STRUCT, if skip_removed { 1 } else { 2 })?;
         state.serialize_field(
             ENTITY_FIELD_COMPONENTS,
             &WorldMapSerializer {
@@ -148,6 +166,43 @@ impl<'a> Serialize for EntitySerializer<'a> {
                 registry: self.registry,
             },
         )?;
+        if skip_removed {
+            state.skip_field(ENTITY_FIELD_REMOVED)?;
+        } else {
+            state.serialize_field(
+                ENTITY_FIELD_REMOVED,
+                &RemovedComponentsSerializer {
+                    type_ids: &self.entity.removed_components,
+                    registry: self.registry,
+                },
+            )?;
+        }
+        state.end()
+    }
+}
+
+/// Handles serialization of the components removed from an entity as a list of type paths.
+pub struct RemovedComponentsSerializer<'a> {
+    /// The [`TypeId`]s of the removed components.
+    pub type_ids: &'a [TypeId],
+    /// Type registry in which the removed component types are registered.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for RemovedComponentsSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.type_ids.len()))?;
+        for type_id in self.type_ids {
+            let registration = self.registry.get(*type_id).ok_or_else(|| {
+                serde::ser::Error::custom(format_args!(
+                    "removed component type `{type_id:?}` is not registered",
+                ))
+            })?;
+            state.serialize_element(registration.type_info().type_path())?;
+        }
         state.end()
     }
 }
@@ -209,12 +264,14 @@ impl<'a> Serialize for WorldMapSerializer<'a> {
 enum WorldField {
     Resources,
     Entities,
+    Despawned,
 }
 
 #[derive(Deserialize)]
 #[serde(field_identifier, rename_all = "lowercase")]
 enum EntityField {
     Components,
+    Removed,
 }
 
 /// Handles world deserialization.
@@ -234,7 +291,7 @@ impl<'a, 'de> DeserializeSeed<'de> for WorldDeserializer<'a> {
     {
         deserializer.deserialize_struct(
             WORLD_STRUCT,
-            &[WORLD_RESOURCES, WORLD_ENTITIES],
+            &[WORLD_RESOURCES, WORLD_ENTITIES, WORLD_DESPAWNED],
             WorldVisitor {
                 type_registry: self.type_registry,
                 load_from_path: self.load_from_path,
@@ -273,9 +330,12 @@ impl<'a, 'de> Visitor<'de> for WorldVisitor<'a> {
             })?
             .ok_or_else(|| Error::missing_field(WORLD_ENTITIES))?;
 
+        let despawned_entities = seq.next_element()?.unwrap_or_default();
+
         Ok(DynamicWorld {
             resources,
             entities,
+            despawned_entities,
         })
     }
 
@@ -285,6 +345,7 @@ impl<'a, 'de> Visitor<'de> for WorldVisitor<'a> {
     {
         let mut resources = None;
         let mut entities = None;
+        let mut despawned_entities = None;
         while let Some(key) = map.next_key()? {
             match key {
                 WorldField::Resources => {
@@ -305,6 +366,12 @@ impl<'a, 'de> Visitor<'de> for WorldVisitor<'a> {
                         load_from_path: self.load_from_path,
                     })?);
                 }
+                WorldField::Despawned => {
+                    if despawned_entities.is_some() {
+                        return Err(Error::duplicate_field(WORLD_DESPAWNED));
+                    }
+                    despawned_entities = Some(map.next_value()?);
+                }
             }
         }
 
@@ -314,6 +381,7 @@ impl<'a, 'de> Visitor<'de> for WorldVisitor<'a> {
         Ok(DynamicWorld {
             resources,
             entities,
+            despawned_entities: despawned_entities.unwrap_or_default(),
         })
     }
 }
@@ -389,7 +457,7 @@ impl<'a, 'de> DeserializeSeed<'de> for WorldEntityDeserializer<'a> {
     {
         deserializer.deserialize_struct(
             ENTITY_STRUCT,
-            &[ENTITY_FIELD_COMPONENTS],
+            &[ENTITY_FIELD_COMPONENTS, ENTITY_FIELD_REMOVED],
             WorldEntityVisitor {
                 entity: self.entity,
                 registry: self.type_registry,
@@ -423,9 +491,16 @@ impl<'a, 'de> Visitor<'de> for WorldEntityVisitor<'a> {
             })?
             .ok_or_else(|| Error::missing_field(ENTITY_FIELD_COMPONENTS))?;
 
+        let removed_components = seq
+            .next_element_seed(RemovedComponentsDeserializer {
+                registry: self.registry,
+            })?
+            .unwrap_or_default();
+
         Ok(DynamicEntity {
             entity: self.entity,
             components,
+            removed_components,
         })
     }
 
@@ -434,6 +509,7 @@ impl<'a, 'de> Visitor<'de> for WorldEntityVisitor<'a> {
         A: MapAccess<'de>,
     {
         let mut components = None;
+        let mut removed_components = None;
         while let Some(key) = map.next_key()? {
             match key {
                 EntityField::Components => {
@@ -446,6 +522,16 @@ impl<'a, 'de> Visitor<'de> for WorldEntityVisitor<'a> {
                         load_from_path: self.load_from_path,
                     })?);
                 }
+                EntityField::Removed => {
+                    if removed_components.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_FIELD_REMOVED));
+                    }
+
+                    removed_components =
+                        Some(map.next_value_seed(RemovedComponentsDeserializer {
+                            registry: self.registry,
+                        })?);
+                }
             }
         }
 
@@ -455,10 +541,56 @@ impl<'a, 'de> Visitor<'de> for WorldEntityVisitor<'a> {
         Ok(DynamicEntity {
             entity: self.entity,
             components,
+            removed_components: removed_components.unwrap_or_default(),
+        })
+    }
+}
+
+/// Handles deserialization of the list of type paths of the components removed from an entity.
+pub struct RemovedComponentsDeserializer<'a> {
+    /// Type registry in which the removed component types are registered.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for RemovedComponentsDeserializer<'a> {
+    type Value = Vec<TypeId>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(RemovedComponentsVisitor {
+            registry: self.registry,
         })
     }
 }
 
+struct RemovedComponentsVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for RemovedComponentsVisitor<'a> {
+    type Value = Vec<TypeId>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("list of removed component type paths")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut type_ids = Vec::new();
+        while let Some(registration) =
+            seq.next_element_seed(TypeRegistrationDeserializer::new(self.registry))?
+        {
+            type_ids.push(registration.type_id());
+        }
+
+        Ok(type_ids)
+    }
+}
+
 /// Handles deserialization of a sequence of values with unique types.
 pub struct WorldMapDeserializer<'a> {
     /// Type registry in which the types of the values to deserialize are registered.
@@ -924,7 +1056,7 @@ mod tests {
                 100, 95, 115, 101, 114, 105, 97, 108, 105, 122, 97, 116, 105, 111, 110, 58, 58,
                 115, 101, 114, 100, 101, 58, 58, 116, 101, 115, 116, 115, 58, 58, 77, 121, 67, 111,
                 109, 112, 111, 110, 101, 110, 116, 1, 2, 3, 102, 102, 166, 63, 205, 204, 108, 64,
-                1, 12, 72, 101, 108, 108, 111, 32, 87, 111, 114, 108, 100, 33
+                1, 12, 72, 101, 108, 108, 111, 32, 87, 111, 114, 108, 100, 33, 0, 0
             ],
             serialized_world
         );
@@ -941,6 +1073,59 @@ mod tests {
         assert_world_eq(&dynamic_world, &deserialized_world);
     }
 
+    #[test]
+    fn should_roundtrip_delta() {
+        let mut world = create_world();
+
+        let a = world.spawn((Foo(1), Bar(2))).id();
+        let b = world.spawn(Foo(3)).id();
+
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let (_, baseline) = DynamicWorldBuilder::from_world(&world, &registry.read())
+            .extract_entities([a, b].into_iter())
+            .build_with_baseline();
+
+        world.increment_change_tick();
+        world.entity_mut(a).remove::<Bar>();
+        world.despawn(b);
+
+        let delta = DynamicWorldBuilder::from_world(&world, &registry.read())
+            .with_baseline(&baseline)
+            .extract_entity(a)
+            .build();
+
+        let registry = registry.read();
+        let serialized = delta.serialize(&registry).unwrap();
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized).unwrap();
+        let deserialized = WorldDeserializer {
+            type_registry: &registry,
+            load_from_path: &mut FakeHandleCreator,
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+
+        assert_eq!(deserialized.despawned_entities, vec![b]);
+        assert_eq!(deserialized.entities.len(), 1);
+        assert_eq!(
+            deserialized.entities[0].removed_components,
+            vec![TypeId::of::<Bar>()]
+        );
+
+        let serialized = delta.serialize_binary(&registry).unwrap();
+        let deserialized = WorldDeserializer {
+            type_registry: &registry,
+            load_from_path: &mut FakeHandleCreator,
+        }
+        .deserialize(&mut postcard::Deserializer::from_bytes(&serialized))
+        .unwrap();
+
+        assert_eq!(deserialized.despawned_entities, vec![b]);
+        assert_eq!(
+            deserialized.entities[0].removed_components,
+            vec![TypeId::of::<Bar>()]
+        );
+    }
+
     #[test]
     fn should_roundtrip_messagepack() {
         let mut world = create_world();
@@ -963,12 +1148,12 @@ mod tests {
 
         assert_eq!(
             vec![
-                146, 128, 129, 206, 255, 255, 255, 253, 145, 129, 217, 51, 98, 101, 118, 121, 95,
+                147, 128, 129, 206, 255, 255, 255, 253, 146, 129, 217, 51, 98, 101, 118, 121, 95,
                 119, 111, 114, 108, 100, 95, 115, 101, 114, 105, 97, 108, 105, 122, 97, 116, 105,
                 111, 110, 58, 58, 115, 101, 114, 100, 101, 58, 58, 116, 101, 115, 116, 115, 58, 58,
                 77, 121, 67, 111, 109, 112, 111, 110, 101, 110, 116, 147, 147, 1, 2, 3, 146, 202,
                 63, 166, 102, 102, 202, 64, 108, 204, 205, 129, 165, 84, 117, 112, 108, 101, 172,
-                72, 101, 108, 108, 111, 32, 87, 111, 114, 108, 100, 33
+                72, 101, 108, 108, 111, 32, 87, 111, 114, 108, 100, 33, 144, 144
             ],
             buf
         );
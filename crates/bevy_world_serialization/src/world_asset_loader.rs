@@ -12,9 +12,11 @@ use {
     thiserror::Error,
 };
 
-/// Asset loader for a Bevy dynamic world (`.scn` / `.scn.ron`).
+/// Asset loader for a Bevy dynamic world (`.scn` / `.scn.ron` / `.scn.bin`).
 ///
-/// The loader handles assets serialized with [`DynamicWorld::serialize`].
+/// The loader handles assets serialized with [`DynamicWorld::serialize`] (RON) and
+/// [`DynamicWorld::serialize_binary`] (postcard). The format is picked from the file extension:
+/// `.scn.bin` files are read as binary, everything else as RON.
 #[derive(Debug, TypePath)]
 pub struct WorldAssetLoader {
     #[cfg_attr(
@@ -44,6 +46,33 @@ pub enum WorldAssetLoaderError {
     /// A [RON Error](ron::error::SpannedError)
     #[error("Could not parse RON: {0}")]
     RonSpannedError(#[from] ron::error::SpannedError),
+    /// A [postcard Error](postcard::Error)
+    #[error("Could not parse binary world: {0}")]
+    Postcard(#[from] postcard::Error),
+}
+
+/// The on-disk encodings understood by [`WorldAssetLoader`].
+#[cfg(feature = "serialize")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldAssetFormat {
+    /// The human-readable RON format written by [`DynamicWorld::serialize`].
+    Ron,
+    /// The compact postcard format written by [`DynamicWorld::serialize_binary`].
+    Binary,
+}
+
+#[cfg(feature = "serialize")]
+impl WorldAssetFormat {
+    /// Returns the format matching the given full file extension (e.g. `scn.bin`).
+    ///
+    /// Unknown extensions fall back to [`WorldAssetFormat::Ron`].
+    pub fn from_extension(extension: &str) -> Self {
+        if extension == "bin" || extension.ends_with(".bin") {
+            Self::Binary
+        } else {
+            Self::Ron
+        }
+    }
 }
 
 #[cfg(feature = "serialize")]
@@ -60,17 +89,31 @@ impl AssetLoader for WorldAssetLoader {
     ) -> Result<Self::Asset, Self::Error> {
         let mut bytes = Vec::new();
         reader.read_to_end(&mut bytes).await?;
-        let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+        let format = load_context
+            .path()
+            .get_full_extension()
+            .map(WorldAssetFormat::from_extension)
+            .unwrap_or(WorldAssetFormat::Ron);
+        let type_registry = self.type_registry.read();
         let scene_deserializer = WorldDeserializer {
-            type_registry: &self.type_registry.read(),
+            type_registry: &type_registry,
             load_from_path: load_context,
         };
-        Ok(scene_deserializer
-            .deserialize(&mut deserializer)
-            .map_err(|e| deserializer.span_error(e))?)
+        match format {
+            WorldAssetFormat::Ron => {
+                let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+                Ok(scene_deserializer
+                    .deserialize(&mut deserializer)
+                    .map_err(|e| deserializer.span_error(e))?)
+            }
+            WorldAssetFormat::Binary => {
+                let mut deserializer = postcard::Deserializer::from_bytes(&bytes);
+                Ok(scene_deserializer.deserialize(&mut deserializer)?)
+            }
+        }
     }
 
     fn extensions(&self) -> &[&str] {
-        &["scn", "scn.ron"]
+        &["scn", "scn.ron", "scn.bin"]
     }
 }